@@ -1,5 +1,8 @@
 //! Common structures concerning model-checking.
-use std::{collections::VecDeque, fmt::Display};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Display,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -34,6 +37,97 @@ pub struct Culprit {
     pub atomic_property: AtomicProperty,
 }
 
+/// A trace through the state space that explains a known model-checking result.
+///
+/// The trace starts in an initial state. It is a counterexample if the property
+/// does not hold and a witness if it holds. If the explanation requires an infinite
+/// path, the last step loops back to an earlier one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Trace {
+    pub steps: Vec<TraceStep>,
+    /// Index of the step the last step transitions to, if the trace is a lasso.
+    pub loop_start: Option<usize>,
+    /// Replay of the trace on the concrete system, if it was performed.
+    pub replay: Option<Replay>,
+}
+
+/// A single step of a trace.
+///
+/// The input and parameter are the representative ones used for the transition
+/// to the step state. The values are displayed as in the state space,
+/// i.e. concrete where known and with unknown bits marked otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TraceStep {
+    pub state_id: StateId,
+    pub input: BTreeMap<String, String>,
+    pub param: BTreeMap<String, String>,
+    pub state: BTreeMap<String, String>,
+    /// The panic value of the state, zero if it does not panic.
+    pub panic: String,
+}
+
+/// A replay of a trace on the concrete system.
+///
+/// Concrete inputs and parameter are chosen from the abstract ones of the trace
+/// and the concrete system is run with them. The chosen values form a reproducer
+/// of the trace if the replay is confirmed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Replay {
+    /// The concrete parameter used for the whole run.
+    pub param: BTreeMap<String, String>,
+    /// The replayed steps, corresponding to the trace steps.
+    ///
+    /// If the replay is spurious, the steps end with the first one that does not follow the trace.
+    pub steps: Vec<ReplayStep>,
+    pub verdict: ReplayVerdict,
+}
+
+/// A single step of a concrete replay.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub input: BTreeMap<String, String>,
+    /// The resulting concrete state, empty if the step panicked.
+    pub state: BTreeMap<String, String>,
+    /// The message of the panic the concrete step ended with, if any.
+    pub panic_message: Option<String>,
+}
+
+/// A class of parameter values for which the property is decided.
+///
+/// The values of each parameter field are given as inclusive ranges, e.g. `4..=15`
+/// or `{0..=1, 4..=5}`, interpreted by the declared signedness of the field.
+/// If there would be too many ranges, the values are displayed as in the state space.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ParamClass {
+    pub param: BTreeMap<String, String>,
+    /// Whether the property holds for the parameter values of the class.
+    pub holds: bool,
+}
+
+/// Verdict of a concrete replay.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReplayVerdict {
+    /// The concrete run follows the trace and ends the same way.
+    Confirmed,
+    /// The concrete run does not follow the trace from the given step.
+    Spurious { step: usize, reason: String },
+    /// The concrete run follows the trace, but it could not be decided
+    /// whether it ends the same way.
+    Inconclusive { reason: String },
+}
+
+impl Display for ReplayVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayVerdict::Confirmed => write!(f, "confirmed"),
+            ReplayVerdict::Spurious { step, reason } => {
+                write!(f, "spurious from step {} ({})", step, reason)
+            }
+            ReplayVerdict::Inconclusive { reason } => write!(f, "inconclusive ({})", reason),
+        }
+    }
+}
+
 impl KnownConclusion {
     pub fn try_into_bool(self) -> Option<bool> {
         match self {
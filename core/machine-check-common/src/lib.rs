@@ -13,7 +13,8 @@ pub use value::{param_valuation::ParamValuation, three_valued::ThreeValued};
 
 pub use node_id::{NodeId, StateId};
 
-use crate::check::KnownConclusion;
+use crate::check::{Culprit, KnownConclusion, ParamClass, Trace};
+use crate::property::PropertyDiagnostics;
 
 /// Execution error that occured during **machine-check** execution.
 #[derive(Error, Debug, Serialize, Deserialize, Clone)]
@@ -39,18 +40,37 @@ pub enum ExecError {
     /// was expected.
     #[error("indexing is required for the field '{0}'")]
     IndexRequired(String),
+    /// The used bit slice was invalid.
+    ///
+    /// This can happen either due to the field not being a bit-vector
+    /// or the bits being out of its range.
+    #[error("slice [{0}:{1}] is invalid for the field '{2}'")]
+    SliceInvalid(u32, u32, String),
+    /// An input or parameter field was used, but the state space does not track them.
+    ///
+    /// The inputs and parameters entering the states are only tracked if the verified
+    /// property references them.
+    #[error("field '{0}' cannot be used as the state space does not track its source")]
+    FieldSourceNotTracked(String),
     /// The signedness of the field was required for a comparison, but not estabilished.
     ///
-    /// Currently, if needed, the signedness must be forced by `as_unsigned` or `as_signed`,
-    /// as field signedness currently does not yet propagate to property verification.
+    /// Fields declared as `Unsigned` or `Signed` are interpreted using the declared signedness.
+    /// Otherwise, e.g. for `Bitvector` fields or bit slices, the signedness must be forced
+    /// by `as_unsigned` or `as_signed`.
     #[error("signedness of the use of field '{0}' was not estabilished")]
     SignednessNotEstabilished(String),
-    /// The specified property is invalid and could not be lexed into tokens.
-    #[error("property '{0}' could not be lexed: {1}")]
-    PropertyNotLexable(String, String),
     /// The specified property is invalid and could not be parsed.
-    #[error("property '{0}' could not be parsed: {1}")]
-    PropertyNotParseable(String, String),
+    ///
+    /// The diagnostics contain each of the errors found, with the spans of the property
+    /// string they concern.
+    #[error("property could not be parsed\n{0}")]
+    PropertyNotParseable(PropertyDiagnostics),
+    /// The specified property file is invalid and could not be parsed.
+    ///
+    /// The diagnostics contain the errors of the first invalid statement, with the spans
+    /// of the whole file they concern.
+    #[error("property file could not be parsed\n{0}")]
+    PropertyFileNotParseable(PropertyDiagnostics),
     /// Verification of a standard property was requested, but the inherent property does not hold.
     #[error("inherent panic")]
     InherentPanic,
@@ -62,6 +82,28 @@ pub enum ExecError {
     /// This is prohibited as it can lead to infinite verification loops.
     #[error("non-monotone property")]
     NonMonotoneProperty,
+    /// The property depends on the parameters for a class of parameter values.
+    ///
+    /// This should not happen, as the parameters are kept along the paths when deciding the classes.
+    #[error("property dependent on parameters within a parameter class")]
+    ParamClassDependent,
+    /// The number of steps until a formula is reached is finite, but too high to be computed.
+    #[error("step bound is higher than the maximum of {0} steps")]
+    StepBoundTooHigh(u64),
+    /// A resource limit of the verification was reached before the result was obtained.
+    ///
+    /// The culprit is the one that would be refined next.
+    #[error("{0} reached")]
+    ResourceLimit(ResourceLimit, Box<Culprit>),
+    /// The verification was cancelled by its observer.
+    #[error("verification cancelled")]
+    Cancelled,
+    /// The checkpoint to resume from does not belong to the verified system or properties.
+    #[error("checkpoint incompatible: {0}")]
+    CheckpointIncompatible(String),
+    /// The checkpoint could not be read, written or (de)serialized.
+    #[error("checkpoint not accessible: {0}")]
+    CheckpointNotAccessible(String),
     /// It was requested to verify an inherent property while assuming that it holds.
     #[error("cannot verify inherent property while assuming it")]
     VerifiedInherentAssumed,
@@ -76,6 +118,28 @@ pub enum ExecError {
     OtherError(String),
 }
 
+/// A limit on the resources used by the verification.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimit {
+    /// The time limit.
+    Timeout,
+    /// The maximum number of refinements.
+    Refinements,
+    /// The maximum number of states in the state space.
+    States,
+}
+
+impl std::fmt::Display for ResourceLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ResourceLimit::Timeout => "time limit",
+            ResourceLimit::Refinements => "refinement limit",
+            ResourceLimit::States => "state limit",
+        };
+        write!(f, "{}", str)
+    }
+}
+
 /// Execution result of **machine-check**.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ExecResult {
@@ -85,10 +149,87 @@ pub struct ExecResult {
     pub result: Result<KnownConclusion, ExecError>,
     /// Execution statistics.
     pub stats: ExecStats,
+    /// Trace explaining the verification result.
+    ///
+    /// Present if the verified property was decided to hold or not to hold.
+    pub trace: Option<Trace>,
+    /// Classes of parameter values deciding the verification result.
+    ///
+    /// Present if the verified property was decided to depend on the parameters.
+    pub param_classes: Option<Vec<ParamClass>>,
+    /// Results of the individual properties, in the order of verification.
+    ///
+    /// Present if multiple properties or a property file were verified.
+    /// The verification result then holds only if each of the properties holds.
+    pub property_results: Option<Vec<PropertyResult>>,
+    /// The number of steps bounding the reachability of the queried formula.
+    ///
+    /// Present if the maximum or minimum number of steps until a formula
+    /// was queried and the query was decided.
+    pub step_bound: Option<StepBound>,
+    /// Results of the displayed subproperties of the verified property in the initial states.
+    ///
+    /// Present if requested and a single property was verified.
+    pub subproperty_results: Option<Vec<SubpropertyResult>>,
+}
+
+/// The number of steps until a formula is reached from the initial states.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum StepBound {
+    /// The formula is reached within the number of steps.
+    Finite(u64),
+    /// The formula is not reached within any number of steps.
+    Infinite,
+}
+
+impl std::fmt::Display for StepBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepBound::Finite(steps) => write!(f, "{}", steps),
+            StepBound::Infinite => write!(f, "infinite"),
+        }
+    }
+}
+
+/// Verification result of a single property of multiple verified in one run.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PropertyResult {
+    /// The name of the property, or the property itself if it was not named.
+    pub name: String,
+    /// The verification result of the property.
+    pub result: Result<KnownConclusion, ExecError>,
+    /// Statistics of the verification of the property.
+    ///
+    /// The refinements and generated states and transitions are the ones
+    /// performed for the property, the final states and transitions are the ones
+    /// in the state space after its verification.
+    pub stats: ExecStats,
+    /// Trace explaining the verification result of the property.
+    pub trace: Option<Trace>,
+    /// Classes of parameter values deciding the verification result of the property.
+    pub param_classes: Option<Vec<ParamClass>>,
+    /// Results of the displayed subproperties of the property in the initial states.
+    ///
+    /// Present if requested.
+    pub subproperty_results: Option<Vec<SubpropertyResult>>,
+}
+
+/// Result of a displayed subproperty in the initial states.
+///
+/// The subproperties that are not displayed are skipped,
+/// their displayed subproperties are the children of the nearest displayed ancestor.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubpropertyResult {
+    /// The subproperty as displayed.
+    pub name: String,
+    /// The valuation of the subproperty in the initial states.
+    pub result: Result<ParamValuation, ExecError>,
+    /// Results of the displayed subproperties of the subproperty.
+    pub children: Vec<SubpropertyResult>,
 }
 
 /// Execution statistics.
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct ExecStats {
     /// Total number of refinements performed.
     pub num_refinements: usize,
@@ -8,10 +8,16 @@ use serde::{Deserialize, Serialize};
 
 mod atomic;
 mod closed_form;
+mod diagnostics;
 mod parser;
+mod simplify;
 mod transition_depth;
 
-pub use atomic::{AtomicProperty, ComparisonType, ValueExpression};
+pub use atomic::{
+    AtomicProperty, BitSlice, ComparisonType, FieldIndex, FieldReference, FieldSource,
+    IndexQuantifier, OperationType, ValueExpression, ValueOperation,
+};
+pub use diagnostics::{PropertyDiagnostic, PropertyDiagnostics, PropertySpan};
 
 /// A Computation Tree Logic property.
 #[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -60,6 +66,17 @@ impl Property {
     pub fn num_subproperties(&self) -> usize {
         self.arena.len()
     }
+
+    /// Returns whether some atomic property references a field from the given source.
+    pub fn references_source(&self, source: FieldSource) -> bool {
+        self.arena.iter().any(|entry| match &entry.ty {
+            PropertyType::Atomic(atomic_property) => atomic_property
+                .fields()
+                .iter()
+                .any(|field| field.source() == source),
+            _ => false,
+        })
+    }
 }
 
 impl Debug for Property {
@@ -74,6 +91,27 @@ impl Debug for Property {
     }
 }
 
+/// Names of the fields and panic messages of a system that can be referenced by properties.
+///
+/// If given when parsing, a reference to an unknown field is an error
+/// suggesting the closest known field. Panic messages can only be
+/// referenced if the names are given.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub struct SystemNames {
+    pub state: Vec<String>,
+    pub input: Vec<String>,
+    pub param: Vec<String>,
+    /// The panic messages in the order of their panic ids, starting from 1.
+    pub panic_messages: Vec<String>,
+}
+
+/// A property named in a property file.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct NamedProperty {
+    pub name: String,
+    pub property: Property,
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct Subproperty {
     property: Property,
@@ -140,10 +178,70 @@ pub struct FixedPointOperator {
 }
 
 impl Property {
+    /// The maximum number of steps of a bounded operator.
+    pub const MAX_BOUND: u64 = parser::MAX_BOUND;
+
     pub fn parse(prop_str: &str) -> Result<Property, ExecError> {
         parser::parse(prop_str)
     }
 
+    /// Parses a property with the path quantifiers restricted to fair paths.
+    ///
+    /// A path is fair if each of the fairness constraints holds infinitely often on it.
+    /// The fairness applies to the CTL operators, including the ones within
+    /// fixed-point operators, but not to the fixed-point operators themselves.
+    ///
+    /// If the system names are given, the referenced fields and panic messages must be known.
+    pub fn parse_fair(
+        prop_str: &str,
+        fairness: &[String],
+        names: Option<&SystemNames>,
+    ) -> Result<Property, ExecError> {
+        parser::parse_fair(prop_str, fairness, names)
+    }
+
+    /// Parses a Linear Temporal Logic property, translating it to mu-calculus.
+    ///
+    /// The property holds if all paths from the initial states satisfy it. If fairness
+    /// constraints are given, only the paths on which each of them holds infinitely
+    /// often are considered.
+    ///
+    /// If the system names are given, the referenced fields and panic messages must be known.
+    pub fn parse_ltl(
+        prop_str: &str,
+        fairness: &[String],
+        names: Option<&SystemNames>,
+    ) -> Result<Property, ExecError> {
+        parser::parse_ltl(prop_str, fairness, names)
+    }
+
+    /// Parses a query of the number of steps until a formula is reached.
+    ///
+    /// The resulting property is `AF![formula]` if universal or `EF![formula]` otherwise.
+    ///
+    /// If the system names are given, the referenced fields and panic messages must be known.
+    pub fn parse_reachability(
+        formula: &str,
+        is_universal: bool,
+        names: Option<&SystemNames>,
+    ) -> Result<Property, ExecError> {
+        parser::parse_reachability(formula, is_universal, names)
+    }
+
+    /// Parses a property file with named properties and reusable definitions.
+    ///
+    /// The properties are returned in the order of appearance. If fairness constraints
+    /// are given, each of the properties is restricted to fair paths.
+    ///
+    /// If the system names are given, the referenced fields and panic messages must be known.
+    pub fn parse_file(
+        input: &str,
+        fairness: &[String],
+        names: Option<&SystemNames>,
+    ) -> Result<Vec<NamedProperty>, ExecError> {
+        parser::parse_file(input, fairness, names)
+    }
+
     fn get_by_index(&self, index: usize) -> &SubpropertyEntry {
         self.arena
             .get(index)
@@ -4,21 +4,43 @@ use serde::{Deserialize, Serialize};
 
 use crate::Signedness;
 
-/// A field name, potentially with indexing and forced signedness.
+/// A field name, potentially with indexing, bit slicing and forced signedness.
 #[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
-pub struct ValueExpression {
+pub struct FieldReference {
+    pub(crate) source: FieldSource,
     pub(crate) name: String,
-    pub(crate) index: Option<u64>,
+    pub(crate) index: Option<FieldIndex>,
+    pub(crate) slice: Option<BitSlice>,
     pub(crate) forced_signedness: Signedness,
 }
 
-impl ValueExpression {
+impl FieldReference {
+    pub fn source(&self) -> FieldSource {
+        self.source
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Returns the index if it is a number.
     pub fn index(&self) -> Option<u64> {
-        self.index
+        match self.index {
+            Some(FieldIndex::Number(index)) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the quantified variable if the field is indexed by it.
+    pub fn index_variable(&self) -> Option<&str> {
+        match &self.index {
+            Some(FieldIndex::Variable(variable)) => Some(variable),
+            _ => None,
+        }
+    }
+
+    pub fn slice(&self) -> Option<BitSlice> {
+        self.slice
     }
 
     pub fn forced_signedness(&self) -> Signedness {
@@ -26,29 +48,217 @@ impl ValueExpression {
     }
 }
 
+/// An index of an array field.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum FieldIndex {
+    Number(u64),
+    /// The variable of the quantifier of the atomic property.
+    Variable(String),
+}
+
+/// Where the referenced field is taken from.
+///
+/// The input and parameter fields are the ones of the step entering the state,
+/// i.e. the ones the state was generated with.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum FieldSource {
+    State,
+    Input,
+    Param,
+    /// The panic id of the state, which is zero if the system has not panicked.
+    ///
+    /// The panic id is referenced as `panic_id`.
+    Panic,
+    /// The state field in the initial states.
+    ///
+    /// A comparison with the initial fields is evaluated for each initial state separately,
+    /// taking all of them from the same state, and holds if it holds for some of them.
+    Init,
+}
+
+/// Bits sliced from a field, from the highest to the lowest, inclusive.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct BitSlice {
+    pub(crate) highest_bit: u32,
+    pub(crate) lowest_bit: u32,
+}
+
+impl BitSlice {
+    pub fn highest_bit(&self) -> u32 {
+        self.highest_bit
+    }
+
+    pub fn lowest_bit(&self) -> u32 {
+        self.lowest_bit
+    }
+}
+
+/// A value expression compared in an atomic property.
+///
+/// The expressions are evaluated on mathematical integers, i.e. without wrapping.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum ValueExpression {
+    Field(FieldReference),
+    Constant(i64),
+    Operation(ValueOperation),
+}
+
+/// A binary operation on value expressions.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct ValueOperation {
+    pub(crate) ty: OperationType,
+    pub(crate) a: Box<ValueExpression>,
+    pub(crate) b: Box<ValueExpression>,
+}
+
+impl ValueOperation {
+    pub fn ty(&self) -> OperationType {
+        self.ty
+    }
+
+    pub fn a(&self) -> &ValueExpression {
+        &self.a
+    }
+
+    pub fn b(&self) -> &ValueExpression {
+        &self.b
+    }
+}
+
+/// A type of binary operation on value expressions.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum OperationType {
+    Add,
+    Sub,
+    Mul,
+    BitAnd,
+    BitOr,
+    BitXor,
+}
+
+impl OperationType {
+    pub(crate) const MAX_PRECEDENCE: u8 = 4;
+
+    /// Binding strength of the operation, following Rust precedence.
+    pub(crate) fn precedence(&self) -> u8 {
+        match self {
+            OperationType::Mul => 4,
+            OperationType::Add | OperationType::Sub => 3,
+            OperationType::BitAnd => 2,
+            OperationType::BitXor => 1,
+            OperationType::BitOr => 0,
+        }
+    }
+}
+
+impl ValueExpression {
+    /// Returns the fields referenced in the expression, from left to right.
+    pub fn fields(&self) -> Vec<&FieldReference> {
+        let mut result = Vec::new();
+        self.collect_fields(&mut result);
+        result
+    }
+
+    fn instantiate(&mut self, variable: &str, index: u64) {
+        match self {
+            ValueExpression::Field(field) => {
+                if field.index_variable() == Some(variable) {
+                    field.index = Some(FieldIndex::Number(index));
+                }
+            }
+            ValueExpression::Constant(_) => {}
+            ValueExpression::Operation(operation) => {
+                operation.a.instantiate(variable, index);
+                operation.b.instantiate(variable, index);
+            }
+        }
+    }
+
+    fn collect_fields<'a>(&'a self, result: &mut Vec<&'a FieldReference>) {
+        match self {
+            ValueExpression::Field(field) => result.push(field),
+            ValueExpression::Constant(_) => {}
+            ValueExpression::Operation(operation) => {
+                operation.a.collect_fields(result);
+                operation.b.collect_fields(result);
+            }
+        }
+    }
+
+    fn precedence(&self) -> Option<u8> {
+        match self {
+            ValueExpression::Operation(operation) => Some(operation.ty.precedence()),
+            _ => None,
+        }
+    }
+}
+
 /// An atomic property of Computation Tree Logic.
 ///
-/// In our case, this is usually a field compared to a number.
+/// In our case, this is a comparison of two value expressions,
+/// usually a field compared to a number. The comparison can be quantified
+/// over the indices of array fields.
 #[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct AtomicProperty {
+    pub(crate) quantifier: Option<IndexQuantifier>,
     pub(crate) left: ValueExpression,
     pub(crate) comparison_type: ComparisonType,
-    pub(crate) right_number: i64,
+    pub(crate) right: ValueExpression,
+    /// Further unquantified comparisons that must hold together with this one.
+    ///
+    /// Used for the proposition `init`, so that all fields are compared with the same initial state.
+    pub(crate) conjoined: Vec<AtomicProperty>,
+}
+
+/// A quantification of the comparison over a range of indices,
+/// e.g. `forall i in 0..32: R[i] != 255`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct IndexQuantifier {
+    pub(crate) is_universal: bool,
+    pub(crate) variable: String,
+    pub(crate) start: u64,
+    pub(crate) end: u64,
+}
+
+impl IndexQuantifier {
+    pub fn is_universal(&self) -> bool {
+        self.is_universal
+    }
+
+    pub fn variable(&self) -> &str {
+        &self.variable
+    }
+
+    /// The first index in the range.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// The index after the last one in the range.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
 }
 
 impl AtomicProperty {
     pub fn new(
         left: ValueExpression,
         comparison_type: ComparisonType,
-        right_number: i64,
+        right: ValueExpression,
     ) -> AtomicProperty {
         AtomicProperty {
+            quantifier: None,
             left,
             comparison_type,
-            right_number,
+            right,
+            conjoined: Vec::new(),
         }
     }
 
+    pub fn quantifier(&self) -> Option<&IndexQuantifier> {
+        self.quantifier.as_ref()
+    }
+
     pub fn left(&self) -> &ValueExpression {
         &self.left
     }
@@ -57,12 +267,56 @@ impl AtomicProperty {
         &self.comparison_type
     }
 
-    pub fn right_number_unsigned(&self) -> u64 {
-        self.right_number as u64
+    pub fn right(&self) -> &ValueExpression {
+        &self.right
+    }
+
+    pub fn conjoined(&self) -> &[AtomicProperty] {
+        &self.conjoined
+    }
+
+    /// Returns the fields referenced on both sides of the comparison and the conjoined ones.
+    pub fn fields(&self) -> Vec<&FieldReference> {
+        let mut result = self.left.fields();
+        result.extend(self.right.fields());
+        for conjoined in &self.conjoined {
+            result.extend(conjoined.fields());
+        }
+        result
     }
 
-    pub fn right_number_signed(&self) -> i64 {
-        self.right_number
+    /// Returns whether the comparison holds trivially when the state is compared with itself
+    /// as the initial state, e.g. `value == init.value`.
+    ///
+    /// The conjoined comparisons are not considered.
+    pub fn is_reflexive_in_init(&self) -> bool {
+        let (ValueExpression::Field(left), ValueExpression::Field(right)) =
+            (&self.left, &self.right)
+        else {
+            return false;
+        };
+        matches!(
+            self.comparison_type,
+            ComparisonType::Eq | ComparisonType::Le | ComparisonType::Ge
+        ) && left.source == FieldSource::State
+            && right.source == FieldSource::Init
+            && *left
+                == FieldReference {
+                    source: FieldSource::State,
+                    ..right.clone()
+                }
+    }
+
+    /// Returns the unquantified comparison for the given index of the quantified variable.
+    ///
+    /// The property is returned unchanged if it is not quantified.
+    pub fn instantiate(&self, index: u64) -> AtomicProperty {
+        let mut result = self.clone();
+        if let Some(quantifier) = result.quantifier.take() {
+            result.left.instantiate(&quantifier.variable, index);
+            result.right.instantiate(&quantifier.variable, index);
+        }
+        result
     }
 }
 
@@ -79,15 +333,64 @@ pub enum ComparisonType {
 
 impl Display for AtomicProperty {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(quantifier) = &self.quantifier {
+            write!(f, "{}: ", quantifier)?;
+        }
+        write!(f, "{} {} {}", self.left, self.comparison_type, self.right)?;
+        for conjoined in &self.conjoined {
+            write!(f, " && {}", conjoined)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for IndexQuantifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let quantifier = if self.is_universal {
+            "forall"
+        } else {
+            "exists"
+        };
         write!(
             f,
-            "{} {} {}",
-            self.left, self.comparison_type, self.right_number
+            "{} {} in {}..{}",
+            quantifier, self.variable, self.start, self.end
         )
     }
 }
 
 impl Display for ValueExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueExpression::Field(field) => write!(f, "{}", field),
+            ValueExpression::Constant(constant) => write!(f, "{}", constant),
+            ValueExpression::Operation(operation) => write!(f, "{}", operation),
+        }
+    }
+}
+
+impl Display for ValueOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the operations are left-associative, parenthesize only where needed
+        let precedence = self.ty.precedence();
+        let a_parenthesized = self.a.precedence().is_some_and(|a| a < precedence);
+        let b_parenthesized = self.b.precedence().is_some_and(|b| b <= precedence);
+
+        if a_parenthesized {
+            write!(f, "({})", self.a)?;
+        } else {
+            write!(f, "{}", self.a)?;
+        }
+        write!(f, " {} ", self.ty)?;
+        if b_parenthesized {
+            write!(f, "({})", self.b)
+        } else {
+            write!(f, "{}", self.b)
+        }
+    }
+}
+
+impl Display for FieldReference {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let add_closing_parenthesis = match self.forced_signedness {
             Signedness::Unsigned => {
@@ -101,10 +404,21 @@ impl Display for ValueExpression {
             Signedness::None => false,
         };
 
+        match self.source {
+            FieldSource::State | FieldSource::Panic => {}
+            FieldSource::Input => write!(f, "input.")?,
+            FieldSource::Param => write!(f, "param.")?,
+            FieldSource::Init => write!(f, "init.")?,
+        }
         write!(f, "{}", self.name)?;
 
-        if let Some(index) = self.index {
-            write!(f, "[{}]", index)?;
+        match &self.index {
+            Some(FieldIndex::Number(index)) => write!(f, "[{}]", index)?,
+            Some(FieldIndex::Variable(variable)) => write!(f, "[{}]", variable)?,
+            None => {}
+        }
+        if let Some(slice) = self.slice {
+            write!(f, "[{}:{}]", slice.highest_bit, slice.lowest_bit)?;
         }
         if add_closing_parenthesis {
             write!(f, ")")?;
@@ -113,6 +427,21 @@ impl Display for ValueExpression {
     }
 }
 
+impl Display for OperationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            OperationType::Add => "+",
+            OperationType::Sub => "-",
+            OperationType::Mul => "*",
+            OperationType::BitAnd => "&",
+            OperationType::BitOr => "|",
+            OperationType::BitXor => "^",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
 impl Display for ComparisonType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match self {
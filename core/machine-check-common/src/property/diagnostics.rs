@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Diagnostics of a property that could not be parsed.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct PropertyDiagnostics {
+    /// The property string.
+    pub input: String,
+    /// The errors found in the property string, in the order of their positions.
+    pub errors: Vec<PropertyDiagnostic>,
+}
+
+/// A single error found in a property string.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct PropertyDiagnostic {
+    /// The offending span, if the error concerns a specific part of the property string.
+    ///
+    /// Errors beyond the end of input point just after the last character.
+    pub span: Option<PropertySpan>,
+    /// The description of the error.
+    pub message: String,
+    /// The suggested fix, if any.
+    pub help: Option<String>,
+}
+
+/// A span of characters in a property string.
+///
+/// Both the start and the end are character positions, the end is inclusive.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct PropertySpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl PropertyDiagnostic {
+    pub fn new(span: Option<PropertySpan>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+            help: None,
+        }
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
+
+impl Display for PropertyDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                write!(f, "\n\n")?;
+            }
+            self.write_error(f, error)?;
+        }
+        Ok(())
+    }
+}
+
+impl PropertyDiagnostics {
+    /// Writes the error with the line of the property string, marking the span by carets.
+    fn write_error(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        error: &PropertyDiagnostic,
+    ) -> std::fmt::Result {
+        write!(f, "error: {}", error.message)?;
+        let Some(span) = error.span else {
+            if let Some(help) = &error.help {
+                write!(f, "\n  = help: {}", help)?;
+            }
+            return Ok(());
+        };
+        writeln!(f)?;
+
+        // find the line containing the start of the span
+        let mut line_start = 0;
+        let mut line_number = 1;
+        let mut line = "";
+        for (index, input_line) in self.input.split('\n').enumerate() {
+            let line_len = input_line.chars().count();
+            line = input_line;
+            line_number = index + 1;
+            if span.start <= line_start + line_len {
+                break;
+            }
+            line_start += line_len + 1;
+        }
+
+        let line_len = line.chars().count();
+        let column = span.start - line_start;
+        // the carets are limited to the line
+        let num_carets = (span.end.min(line_start + line_len.max(column + 1) - 1) + 1)
+            .saturating_sub(span.start)
+            .max(1);
+
+        let number_str = line_number.to_string();
+        let gutter = " ".repeat(number_str.len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number_str, line.trim_end())?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(column),
+            "^".repeat(num_carets)
+        )?;
+        if let Some(help) = &error.help {
+            write!(f, "\n{} = help: {}", gutter, help)?;
+        }
+        Ok(())
+    }
+}
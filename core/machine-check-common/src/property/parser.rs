@@ -1,20 +1,29 @@
-use std::collections::VecDeque;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
 
 use crate::{
     property::{
         parser::original::{
-            BiLogicOperator, CtlOperator, FixedPointOperator, OperatorF, OperatorG, OperatorR,
-            OperatorU, Property, TemporalOperator,
+            BiLogicOperator, BoundedCtlOperator, CtlOperator, Definition, DerivedLogicOperator,
+            DerivedLogicType, FixedPointOperator, OperatorF, OperatorG, OperatorR, OperatorU,
+            Property, TemporalOperator, PANIC_ID_NAME,
         },
-        AtomicProperty, ValueExpression,
+        AtomicProperty, BitSlice, ComparisonType, FieldIndex, FieldReference, FieldSource,
+        IndexQuantifier, OperationType, PropertyDiagnostic, PropertyDiagnostics, PropertySpan,
+        SystemNames, ValueExpression, ValueOperation,
     },
     ExecError, Signedness,
 };
 
 use lexer::{Bracket, Token, TokenType};
 
+mod fair;
+mod file;
 mod fold;
 mod lexer;
+mod ltl;
 mod original;
 
 /// Parses a verification property.
@@ -25,13 +34,126 @@ pub fn parse(input: &str) -> Result<super::Property, ExecError> {
     fold::fold(original)
 }
 
+/// Parses a verification property with path quantifiers restricted to fair paths.
+///
+/// Each of the fairness constraints must hold infinitely often on a fair path.
+/// Returns an error if the property or a constraint was not parsed successfully.
+pub fn parse_fair(
+    input: &str,
+    fairness: &[String],
+    names: Option<&SystemNames>,
+) -> Result<super::Property, ExecError> {
+    let original = parse_with_definitions(input, BTreeMap::new(), names)?;
+    let constraints = parse_constraints(fairness, names)?;
+    fold_fair(original, &constraints)
+}
+
+/// Parses a property file with named properties and definitions.
+///
+/// Each of the named properties is restricted to fair paths as in [`parse_fair`].
+/// Returns an error if the file or a constraint was not parsed successfully.
+pub fn parse_file(
+    input: &str,
+    fairness: &[String],
+    names: Option<&SystemNames>,
+) -> Result<Vec<super::NamedProperty>, ExecError> {
+    let constraints = parse_constraints(fairness, names)?;
+    file::parse(input, names)?
+        .into_iter()
+        .map(|(name, original)| {
+            Ok(super::NamedProperty {
+                name,
+                property: fold_fair(original, &constraints)?,
+            })
+        })
+        .collect()
+}
+
 pub fn parse_inner(input: &str) -> Result<Property, ExecError> {
-    let parser = PropertyParser {
-        input: String::from(input),
-        lex_items: lexer::lex(input)?,
-        variables: Vec::new(),
-    };
-    parser.parse()
+    parse_with_definitions(input, BTreeMap::new(), None)
+}
+
+/// Parses a property which can reference the given definitions by their names.
+fn parse_with_definitions(
+    input: &str,
+    definitions: BTreeMap<String, Property>,
+    names: Option<&SystemNames>,
+) -> Result<Property, ExecError> {
+    PropertyParser::new(input, definitions, names).parse()
+}
+
+fn parse_constraints(
+    fairness: &[String],
+    names: Option<&SystemNames>,
+) -> Result<Vec<Property>, ExecError> {
+    fairness
+        .iter()
+        .map(|constraint| parse_with_definitions(constraint, BTreeMap::new(), names))
+        .collect()
+}
+
+/// Folds the property with path quantifiers restricted to fair paths.
+///
+/// The property is folded directly if there are no fairness constraints.
+fn fold_fair(original: Property, constraints: &[Property]) -> Result<super::Property, ExecError> {
+    if constraints.is_empty() {
+        return fold::fold(original);
+    }
+    let display_string = original.to_string();
+    let folded = fold::fold(fair::apply(original, constraints))?;
+    Ok(show_only_root(folded, display_string))
+}
+
+/// Parses a Linear Temporal Logic property.
+///
+/// The property is translated to mu-calculus. If fairness constraints are given,
+/// only the paths on which each of them holds infinitely often are considered.
+/// Returns an error if it was not parsed or translated successfully.
+pub fn parse_ltl(
+    input: &str,
+    fairness: &[String],
+    names: Option<&SystemNames>,
+) -> Result<super::Property, ExecError> {
+    let ltl = PropertyParser::new(input, BTreeMap::new(), names).parse_ltl()?;
+    let constraints = parse_constraints(fairness, names)?;
+    let translated = ltl::translate(input, &ltl, &constraints)?;
+    let folded = fold::fold(translated)?;
+    Ok(show_only_root(folded, ltl.to_string()))
+}
+
+/// Parses a query of the number of steps until the formula is reached.
+///
+/// The formula is wrapped in the F operator.
+pub fn parse_reachability(
+    input: &str,
+    is_universal: bool,
+    names: Option<&SystemNames>,
+) -> Result<super::Property, ExecError> {
+    let formula = parse_with_definitions(input, BTreeMap::new(), names)?;
+    let original = Property::Ctl(CtlOperator {
+        is_universal,
+        temporal: TemporalOperator::F(OperatorF(Box::new(formula))),
+    });
+    check_expanded_size(input, &original)?;
+    fold::fold(original)
+}
+
+/// Hides the subproperties of a translated property, showing the original in the root.
+///
+/// The translated subproperties are not meaningful to the user.
+fn show_only_root(folded: super::Property, display_string: String) -> super::Property {
+    let mut arena = Vec::clone(&folded.arena);
+    for (index, entry) in arena.iter_mut().enumerate() {
+        if index == 0 {
+            entry.display_string = Some(display_string.clone());
+        } else {
+            entry.display_string = None;
+            entry.visible = false;
+        }
+    }
+    super::Property {
+        arena: Arc::new(arena),
+    }
 }
 
 pub fn inherent() -> super::Property {
@@ -39,74 +161,285 @@ pub fn inherent() -> super::Property {
         .expect("Folding the inherent property should succeed")
 }
 
+/// The maximum number of steps of a bounded operator.
+///
+/// Each step is expanded separately, so the property grows with the bound.
+pub const MAX_BOUND: u64 = 256;
+
+/// The maximum number of subproperties after the bounded operators are expanded.
+///
+/// Nested bounded operators grow exponentially with the nesting depth when expanded,
+/// so the size is bounded to fail gracefully.
+const MAX_EXPANDED_SIZE: usize = 1 << 16;
+
+/// Returns an error if the property is too large after the bounded operators are expanded.
+fn check_expanded_size(input: &str, property: &Property) -> Result<(), ExecError> {
+    if property.expanded_size() > MAX_EXPANDED_SIZE {
+        return Err(ExecError::PropertyNotParseable(PropertyDiagnostics {
+            input: String::from(input),
+            errors: vec![PropertyDiagnostic::new(
+                None,
+                "The property is too large after expanding the bounded operators",
+            )],
+        }));
+    }
+    Ok(())
+}
+
 struct PropertyParser {
     input: String,
     lex_items: VecDeque<Token>,
     variables: Vec<String>,
+    index_variable: Option<String>,
+    definitions: BTreeMap<String, Property>,
+    names: Option<SystemNames>,
+    // the errors that do not prevent continuing the parsing
+    errors: Vec<PropertyDiagnostic>,
 }
 
 impl PropertyParser {
+    fn new(
+        input: &str,
+        definitions: BTreeMap<String, Property>,
+        names: Option<&SystemNames>,
+    ) -> Self {
+        let (lex_items, errors) = lexer::lex(input);
+        PropertyParser {
+            input: String::from(input),
+            lex_items,
+            variables: Vec::new(),
+            index_variable: None,
+            definitions,
+            names: names.cloned(),
+            errors,
+        }
+    }
+
     fn parse(mut self) -> Result<Property, ExecError> {
         assert!(self.variables.is_empty());
         let result = self.parse_property()?;
-        if !self.lex_items.is_empty() {
-            return Err(self.not_parseable(None, "Extraneous tokens"));
-        }
+        self.expect_end()?;
         assert!(self.variables.is_empty());
+        check_expanded_size(&self.input, &result)?;
         Ok(result)
     }
 
+    /// Ensures that all of the tokens were parsed and no errors were found.
+    fn expect_end(&mut self) -> Result<(), ExecError> {
+        if let Some(token) = self.lex_items.pop_front() {
+            return Err(self.not_parseable(
+                Some(token),
+                "Expected the end of the property after a complete property",
+            ));
+        }
+        if !self.errors.is_empty() {
+            return Err(self.error(None));
+        }
+        Ok(())
+    }
+
     fn peek_type(&self) -> Option<&TokenType> {
         self.lex_items.front().map(|e| &e.ty)
     }
 
+    /// Parses a property, including the logic operators.
+    ///
+    /// Equivalences have the lowest precedence, followed by implications, which are
+    /// right-associative. Conjunctions, disjunctions and exclusive ors have the highest
+    /// precedence, but cannot be mixed with each other without parentheses.
     fn parse_property(&mut self) -> Result<Property, ExecError> {
-        let mut expr = self.parse_property_expr()?;
+        let mut expr = self.parse_implication()?;
+        while let Some(TokenType::LogicEquivalence) = self.peek_type() {
+            self.lex_items.pop_front();
+            expr = Property::DerivedLogic(DerivedLogicOperator {
+                ty: DerivedLogicType::Equivalence,
+                a: Box::new(expr),
+                b: Box::new(self.parse_implication()?),
+            });
+        }
+        Ok(expr)
+    }
 
-        if let Some(TokenType::LogicAnd) = self.peek_type() {
-            loop {
-                self.lex_items.pop_front();
-                expr = Property::BiLogic(BiLogicOperator {
-                    is_and: true,
-                    a: Box::new(expr),
-                    b: Box::new(self.parse_property_expr()?),
-                });
-                let Some(TokenType::LogicAnd) = self.peek_type() else {
-                    break;
-                };
+    fn parse_implication(&mut self) -> Result<Property, ExecError> {
+        let premise = self.parse_junction()?;
+        let Some(TokenType::LogicImplication) = self.peek_type() else {
+            return Ok(premise);
+        };
+        self.lex_items.pop_front();
+        Ok(Property::DerivedLogic(DerivedLogicOperator {
+            ty: DerivedLogicType::Implication,
+            a: Box::new(premise),
+            b: Box::new(self.parse_implication()?),
+        }))
+    }
+
+    fn parse_junction(&mut self) -> Result<Property, ExecError> {
+        let mut expr = self.parse_operand(Self::parse_property_expr, Property::Const(true))?;
+
+        let token_type = match self.peek_type() {
+            Some(token_type @ (TokenType::LogicAnd | TokenType::LogicOr | TokenType::BitXor)) => {
+                token_type.clone()
             }
-        } else if let Some(TokenType::LogicOr) = self.peek_type() {
-            loop {
-                self.lex_items.pop_front();
-                expr = Property::BiLogic(BiLogicOperator {
+            _ => return Ok(expr),
+        };
+
+        // do not mix conjunctions, disjunctions and exclusive ors without parentheses
+        while self.peek_type() == Some(&token_type) {
+            self.lex_items.pop_front();
+            let a = Box::new(expr);
+            let b = Box::new(self.parse_operand(Self::parse_property_expr, Property::Const(true))?);
+            expr = match token_type {
+                TokenType::LogicAnd => Property::BiLogic(BiLogicOperator { is_and: true, a, b }),
+                TokenType::LogicOr => Property::BiLogic(BiLogicOperator {
                     is_and: false,
-                    a: Box::new(expr),
-                    b: Box::new(self.parse_property_expr()?),
-                });
-                let Some(TokenType::LogicOr) = self.peek_type() else {
-                    break;
-                };
-            }
+                    a,
+                    b,
+                }),
+                _ => Property::DerivedLogic(DerivedLogicOperator {
+                    ty: DerivedLogicType::ExclusiveOr,
+                    a,
+                    b,
+                }),
+            };
         }
 
         Ok(expr)
     }
 
+    /// Parses an operand of the logic operators, recovering from its syntax errors.
+    ///
+    /// The errors are kept and the tokens are skipped until the next logic operator, comma
+    /// or closing bracket that does not belong to the operand, so that the errors in the
+    /// following operands are reported as well. The placeholder is returned instead of
+    /// the operand, the parsing fails at the end due to the kept errors.
+    fn parse_operand<T>(
+        &mut self,
+        parse: fn(&mut Self) -> Result<T, ExecError>,
+        placeholder: T,
+    ) -> Result<T, ExecError> {
+        let saved_lex_items = self.lex_items.clone();
+        let num_variables = self.variables.len();
+        let index_variable = self.index_variable.clone();
+
+        let diagnostics = match parse(self) {
+            Err(ExecError::PropertyNotParseable(diagnostics)) => diagnostics,
+            result => return result,
+        };
+        let num_consumed = saved_lex_items.len() - self.lex_items.len();
+        let mut consumed: Vec<Token> = saved_lex_items.into_iter().take(num_consumed).collect();
+        // the unexpected token was consumed, but it may end the operand
+        match consumed.last() {
+            Some(token) if ends_operand(&token.ty) => {
+                self.lex_items.push_front(token.clone());
+                consumed.pop();
+            }
+            // nothing is left to find further errors in
+            _ if self.lex_items.is_empty() => {
+                return Err(ExecError::PropertyNotParseable(diagnostics))
+            }
+            _ => {}
+        }
+
+        self.errors = diagnostics.errors;
+        self.variables.truncate(num_variables);
+        self.index_variable = index_variable;
+
+        // skip the rest of the operand, including its unclosed brackets
+        let mut depth = consumed.iter().fold(0usize, |depth, token| match token.ty {
+            TokenType::OpeningBracket(_) => depth + 1,
+            TokenType::ClosingBracket(_) => depth.saturating_sub(1),
+            _ => depth,
+        });
+        while let Some(token) = self.lex_items.front() {
+            match token.ty {
+                TokenType::OpeningBracket(_) => depth += 1,
+                TokenType::ClosingBracket(_) if depth > 0 => depth -= 1,
+                ref ty if depth == 0 && ends_operand(ty) => break,
+                _ => {}
+            }
+            self.lex_items.pop_front();
+        }
+        Ok(placeholder)
+    }
+
     fn parse_property_expr(&mut self) -> Result<Property, ExecError> {
         let first_token = self.lex_items.pop_front();
         Ok(match first_token {
             Some(Token {
                 ty: TokenType::Ident(ident),
                 ..
-            }) => {
-                // look if it is a fixed-point variable first
-                if self.variables.contains(&ident) {
-                    Property::FixedVariable(ident)
-                } else {
-                    // parse as an atomic property
-                    Property::Atomic(self.parse_atomic_property(ident)?)
-                }
+            }) if self.variables.contains(&ident) => {
+                // a fixed-point variable
+                Property::FixedVariable(ident)
+            }
+            Some(Token {
+                ty: TokenType::Ident(ident),
+                ..
+            }) if self.definitions.contains_key(&ident) && !self.continues_field() => {
+                // a definition from a property file
+                let inner = Box::new(self.definitions[&ident].clone());
+                Property::Definition(Definition { name: ident, inner })
+            }
+            Some(Token {
+                ty: TokenType::Ident(ref ident),
+                ..
+            }) if (ident == "true" || ident == "false") && !self.continues_field() => {
+                // a constant property
+                Property::Const(ident == "true")
             }
+            Some(Token {
+                ty: TokenType::Ident(ref ident),
+                ..
+            }) if ident == "panic"
+                && self.peek_type() == Some(&TokenType::OpeningBracket(Bracket::Parenthesis)) =>
+            {
+                self.parse_panic_proposition()?
+            }
+            Some(Token {
+                ty: TokenType::Ident(ref ident),
+                ..
+            }) if ident == "state_eq_init"
+                && self.peek_type() == Some(&TokenType::OpeningBracket(Bracket::Parenthesis)) =>
+            {
+                self.parse_state_eq_init()?
+            }
+            Some(Token {
+                ty: TokenType::Ident(ref ident),
+                span,
+            }) if ident == "init" && !self.continues_field() => self.init_proposition(span),
+            Some(Token {
+                ty: TokenType::Ident(ref ident),
+                ..
+            }) if self.starts_bound() => {
+                // a bounded CTL operator, e.g. AF[<=20]![a == 1]
+                let ident = ident.clone();
+                let bound = self.parse_bound()?;
+                let temporal = match ident.as_str() {
+                    "AF" | "EF" => self.parse_f()?,
+                    "AG" | "EG" => self.parse_g()?,
+                    "AU" | "EU" => self.parse_bi_operator(true)?,
+                    "AR" | "ER" => self.parse_bi_operator(false)?,
+                    _ => {
+                        return Err(self.not_parseable(
+                            first_token,
+                            "Unexpected bounded operator when parsing a property",
+                        ))
+                    }
+                };
+                Property::BoundedCtl(BoundedCtlOperator {
+                    bound,
+                    ctl: CtlOperator {
+                        is_universal: ident.starts_with('A'),
+                        temporal,
+                    },
+                })
+            }
+            Some(
+                token @ Token {
+                    ty: TokenType::Ident(_) | TokenType::Number(_) | TokenType::Minus,
+                    ..
+                },
+            ) => Property::Atomic(self.parse_atomic_or_operator(token, CTL_OPERATORS)?),
             Some(Token {
                 ty: TokenType::MacroInvocation(ref ident),
                 ..
@@ -150,18 +483,12 @@ impl PropertyParser {
                 )?;
                 result
             }
-            Some(Token {
-                ty: TokenType::OpeningBracket(Bracket::Parenthesis),
-                ..
-            }) => {
-                let result = self.parse_property()?;
-                // extraneous parentheses, remove them
-                self.expect(
-                    TokenType::ClosingBracket(Bracket::Parenthesis),
-                    "inside an opened parenthesis",
-                )?;
-                result
-            }
+            Some(
+                token @ Token {
+                    ty: TokenType::OpeningBracket(Bracket::Parenthesis),
+                    ..
+                },
+            ) => self.parse_parenthesized(token, Self::parse_property, Property::Atomic)?,
 
             token => {
                 return Err(self.not_parseable(
@@ -172,6 +499,24 @@ impl PropertyParser {
         })
     }
 
+    /// Returns whether the identifier just taken continues as a field,
+    /// e.g. `a[1]`, `a.b`, `a(b)` or `a == 1`.
+    ///
+    /// Used to keep fields accessible even if a definition has the same name.
+    fn continues_field(&self) -> bool {
+        match self.peek_type() {
+            Some(
+                TokenType::Comparison(_)
+                | TokenType::Dot
+                | TokenType::OpeningBracket(Bracket::Square | Bracket::Parenthesis),
+            ) => true,
+            // an exclusive or of the defined property
+            Some(TokenType::BitXor) => false,
+            Some(token_type) => operation_type(token_type).is_some(),
+            None => false,
+        }
+    }
+
     fn parse_x(&mut self) -> Result<TemporalOperator, ExecError> {
         Ok(TemporalOperator::X(Box::new(self.parse_uni_operator()?)))
     }
@@ -188,6 +533,40 @@ impl PropertyParser {
         ))))
     }
 
+    /// Returns whether a bound of a CTL operator follows, e.g. `[<=20]`.
+    ///
+    /// The bound cannot be confused with a field index, as the index is just a number.
+    fn starts_bound(&self) -> bool {
+        matches!(
+            self.lex_items.front().map(|token| &token.ty),
+            Some(TokenType::OpeningBracket(Bracket::Square))
+        ) && matches!(
+            self.lex_items.get(1).map(|token| &token.ty),
+            Some(TokenType::Comparison(ComparisonType::Le))
+        )
+    }
+
+    fn parse_bound(&mut self) -> Result<u64, ExecError> {
+        const WHEN_PARSING: &str = "an operator bound";
+
+        self.expect(TokenType::OpeningBracket(Bracket::Square), WHEN_PARSING)?;
+        self.expect(TokenType::Comparison(ComparisonType::Le), WHEN_PARSING)?;
+        let bound_token = self.lex_items.pop_front();
+        let Some(Token {
+            ty: TokenType::Number(bound),
+            ..
+        }) = bound_token
+        else {
+            return Err(self.not_parseable(bound_token, "Expected the number of steps"));
+        };
+        if bound > MAX_BOUND {
+            return Err(self.not_parseable(bound_token, "The number of steps is too high"));
+        }
+        self.expect(TokenType::ClosingBracket(Bracket::Square), WHEN_PARSING)?;
+        self.expect(TokenType::ExclamationMark, WHEN_PARSING)?;
+        Ok(bound)
+    }
+
     fn parse_uni_operator(&mut self) -> Result<Property, ExecError> {
         self.expect(
             TokenType::OpeningBracket(Bracket::Square),
@@ -247,37 +626,365 @@ impl PropertyParser {
         })
     }
 
-    fn parse_atomic_property(&mut self, first_ident: String) -> Result<AtomicProperty, ExecError> {
-        let left = self.parse_value_expression(first_ident)?;
+    /// Parses a property starting with an opening parenthesis.
+    ///
+    /// The parenthesis can enclose either a property or the start of a value expression
+    /// of an atomic property, e.g. `(a + b) == c`. The property is tried first and
+    /// the tokens are reparsed as an atomic property if that is not successful.
+    fn parse_parenthesized<T>(
+        &mut self,
+        opening_token: Token,
+        parse_property: fn(&mut Self) -> Result<T, ExecError>,
+        from_atomic: fn(AtomicProperty) -> T,
+    ) -> Result<T, ExecError> {
+        let saved_lex_items = self.lex_items.clone();
+        let saved_variables = self.variables.clone();
+        let saved_errors = self.errors.clone();
+
+        let property_result = parse_property(self).and_then(|result| {
+            // extraneous parentheses, remove them
+            self.expect(
+                TokenType::ClosingBracket(Bracket::Parenthesis),
+                "inside an opened parenthesis",
+            )?;
+            Ok(result)
+        });
+        let continues_value_expression = match self.peek_type() {
+            Some(TokenType::Comparison(_)) => true,
+            // a property cannot be a value, so this is an exclusive or of properties
+            Some(TokenType::BitXor) => false,
+            Some(token_type) => operation_type(token_type).is_some(),
+            None => false,
+        };
+        match property_result {
+            Ok(result) if !continues_value_expression => return Ok(result),
+            _ => {}
+        }
+
+        self.lex_items = saved_lex_items;
+        self.variables = saved_variables;
+        self.errors = saved_errors;
+        self.lex_items.push_front(opening_token);
+        match self.parse_atomic_property() {
+            Ok(atomic) => Ok(from_atomic(atomic)),
+            // prefer the error of parsing as a property if there is one
+            Err(atomic_err) => Err(property_result.err().unwrap_or(atomic_err)),
+        }
+    }
+
+    /// Parses an atomic property starting with the given token.
+    ///
+    /// If the token is an identifier naming one of the operators and it cannot be parsed
+    /// as an atomic property, the operator was likely intended.
+    fn parse_atomic_or_operator(
+        &mut self,
+        token: Token,
+        operators: &[&str],
+    ) -> Result<AtomicProperty, ExecError> {
+        let operator = match &token.ty {
+            TokenType::Ident(ident)
+                if operators.contains(&ident.as_str())
+                    && matches!(
+                        self.peek_type(),
+                        Some(TokenType::OpeningBracket(
+                            Bracket::Parenthesis | Bracket::Square
+                        ))
+                    ) =>
+            {
+                Some((ident.clone(), token.span))
+            }
+            _ => None,
+        };
+        self.lex_items.push_front(token);
+        let num_errors = self.errors.len();
+        let result = self.parse_atomic_property();
+
+        match (result, operator) {
+            (Err(_), Some((operator, span))) => {
+                // the errors of parsing as an atomic property are not relevant
+                self.errors.truncate(num_errors);
+                Err(self.error(Some(
+                    PropertyDiagnostic::new(
+                        Some(span),
+                        format!("Expected '!' after the operator '{}'", operator),
+                    )
+                    .with_help(format!(
+                        "operators are used as macros, e.g. '{}![...]'",
+                        operator
+                    )),
+                )))
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn parse_atomic_property(&mut self) -> Result<AtomicProperty, ExecError> {
+        if self.starts_quantifier() {
+            return self.parse_quantified_atomic_property();
+        }
+
+        let left = self.parse_value_expression(0)?;
 
         let comparison_token = self.lex_items.pop_front();
         let comparison_type = match comparison_token.as_ref().map(|token| &token.ty) {
-            Some(TokenType::Comparison(comparison_type)) => comparison_type,
+            Some(TokenType::Comparison(comparison_type)) => *comparison_type,
             _ => return Err(self.not_parseable(comparison_token, "Expected a comparison operator")),
         };
 
-        let right_number = match self.lex_items.pop_front() {
-            Some(Token {
-                ty: TokenType::Number(right_number),
-                ..
-            }) => right_number,
-            token => return Err(self.not_parseable(token, "Expected a number in comparison")),
+        let right = self.parse_value_expression(0)?;
+
+        Ok(AtomicProperty::new(left, comparison_type, right))
+    }
+
+    /// Parses a panic proposition, e.g. `panic("Division by zero")`, which holds
+    /// in the states where the system panicked with the given message.
+    ///
+    /// The proposition is resolved to a comparison of the panic id.
+    fn parse_panic_proposition(&mut self) -> Result<Property, ExecError> {
+        const WHEN_PARSING: &str = "a panic proposition";
+        self.expect(
+            TokenType::OpeningBracket(Bracket::Parenthesis),
+            WHEN_PARSING,
+        )?;
+        let message_token = self.lex_items.pop_front();
+        let Some(Token {
+            ty: TokenType::Str(message),
+            span,
+        }) = message_token
+        else {
+            return Err(self.not_parseable(
+                message_token,
+                "Expected a panic message string when parsing a panic proposition",
+            ));
         };
+        self.expect(
+            TokenType::ClosingBracket(Bracket::Parenthesis),
+            WHEN_PARSING,
+        )?;
 
-        // the numbers are stored as i64 currently since it is more likely it will be signed when the highest bit is set
-        let right_number = right_number as i64;
+        // the same message can be used at multiple panic sites, each with its own panic id
+        let panic_ids = self.resolve_panic_message(&message, span);
+        let inner = panic_ids
+            .into_iter()
+            .map(|panic_id| {
+                let panic_id_reference = FieldReference {
+                    source: FieldSource::Panic,
+                    name: String::from(PANIC_ID_NAME),
+                    index: None,
+                    slice: None,
+                    forced_signedness: Signedness::None,
+                };
+                Property::Atomic(AtomicProperty::new(
+                    ValueExpression::Field(panic_id_reference),
+                    ComparisonType::Eq,
+                    ValueExpression::Constant(panic_id as i64),
+                ))
+            })
+            .reduce(|a, b| {
+                Property::BiLogic(BiLogicOperator {
+                    is_and: false,
+                    a: Box::new(a),
+                    b: Box::new(b),
+                })
+            })
+            .expect("Panic message should resolve to some panic id");
+        Ok(Property::Definition(Definition {
+            name: format!("panic({:?})", message),
+            inner: Box::new(inner),
+        }))
+    }
 
-        Ok(AtomicProperty::new(left, *comparison_type, right_number))
+    /// Parses a comparison of a state field with its value in the initial states,
+    /// e.g. `state_eq_init(R[2])`.
+    fn parse_state_eq_init(&mut self) -> Result<Property, ExecError> {
+        const WHEN_PARSING: &str = "a comparison with the initial value";
+        self.expect(
+            TokenType::OpeningBracket(Bracket::Parenthesis),
+            WHEN_PARSING,
+        )?;
+        let (field_token, field_ident) = self.expect_ident(WHEN_PARSING)?;
+        let field = self.parse_bit_or_field_reference(
+            field_token.as_ref().map(|token| token.span),
+            field_ident,
+            Signedness::None,
+        )?;
+        if field.source != FieldSource::State {
+            return Err(self.not_parseable(
+                field_token,
+                "Only the state fields can be compared with their initial values",
+            ));
+        }
+        self.expect(
+            TokenType::ClosingBracket(Bracket::Parenthesis),
+            WHEN_PARSING,
+        )?;
+
+        Ok(Property::Definition(Definition {
+            name: format!("state_eq_init({})", field),
+            inner: Box::new(Property::Atomic(state_eq_init(field))),
+        }))
     }
 
-    fn parse_value_expression(
+    /// Returns the proposition holding in the states equal to some initial state,
+    /// i.e. the comparisons of all state fields with their values in the same initial state.
+    fn init_proposition(&mut self, span: PropertySpan) -> Property {
+        let Some(names) = &self.names else {
+            // continue with a placeholder
+            self.errors.push(PropertyDiagnostic::new(
+                Some(span),
+                "The initial states cannot be referenced without knowing the system",
+            ));
+            return Property::Const(true);
+        };
+
+        let mut comparisons = names.state.iter().map(|name| {
+            state_eq_init(FieldReference {
+                source: FieldSource::State,
+                name: name.clone(),
+                index: None,
+                slice: None,
+                forced_signedness: Signedness::None,
+            })
+        });
+        let inner = match comparisons.next() {
+            Some(first) => Property::Atomic(AtomicProperty {
+                conjoined: comparisons.collect(),
+                ..first
+            }),
+            None => Property::Const(true),
+        };
+        Property::Definition(Definition {
+            name: String::from("init"),
+            inner: Box::new(inner),
+        })
+    }
+
+    /// Returns whether an index quantifier follows, e.g. `forall i in 0..32:`.
+    ///
+    /// The quantifier cannot be confused with a field, as a field is never followed by an identifier.
+    fn starts_quantifier(&self) -> bool {
+        let ident = |index: usize| match self.lex_items.get(index).map(|token| &token.ty) {
+            Some(TokenType::Ident(ident)) => Some(ident.as_str()),
+            _ => None,
+        };
+        matches!(ident(0), Some("forall" | "exists"))
+            && ident(1).is_some()
+            && ident(2) == Some("in")
+    }
+
+    /// Parses an atomic property quantified over a range of indices,
+    /// e.g. `forall i in 0..32: R[i] != 255`.
+    fn parse_quantified_atomic_property(&mut self) -> Result<AtomicProperty, ExecError> {
+        const WHEN_PARSING: &str = "an index quantifier";
+
+        let (quantifier_token, quantifier) = self.expect_ident(WHEN_PARSING)?;
+        if self.index_variable.is_some() {
+            return Err(self.not_parseable(quantifier_token, "Quantifiers cannot be nested"));
+        }
+        let is_universal = quantifier == "forall";
+        let (_variable_token, variable) = self.expect_ident(WHEN_PARSING)?;
+        self.expect(TokenType::Ident(String::from("in")), WHEN_PARSING)?;
+        let (start_token, start) = self.expect_number(WHEN_PARSING)?;
+        self.expect(TokenType::Dot, WHEN_PARSING)?;
+        self.expect(TokenType::Dot, WHEN_PARSING)?;
+        let (_end_token, end) = self.expect_number(WHEN_PARSING)?;
+        if start >= end {
+            return Err(self.not_parseable(start_token, "The quantified range must not be empty"));
+        }
+        self.expect(TokenType::Colon, WHEN_PARSING)?;
+
+        // the variable can be used as an index in the quantified comparison
+        self.index_variable = Some(variable.clone());
+        let result = self.parse_atomic_property();
+        self.index_variable = None;
+
+        let mut result = result?;
+        result.quantifier = Some(IndexQuantifier {
+            is_universal,
+            variable,
+            start,
+            end,
+        });
+        Ok(result)
+    }
+
+    /// Parses a value expression containing operations with the given precedence or higher.
+    ///
+    /// The operations are left-associative and follow Rust precedence.
+    fn parse_value_expression(&mut self, precedence: u8) -> Result<ValueExpression, ExecError> {
+        if precedence > OperationType::MAX_PRECEDENCE {
+            return self.parse_value_operand();
+        }
+
+        let mut expr = self.parse_value_expression(precedence + 1)?;
+        while let Some(ty) = self
+            .peek_type()
+            .and_then(operation_type)
+            .filter(|ty| ty.precedence() == precedence)
+        {
+            self.lex_items.pop_front();
+            let b = self.parse_value_expression(precedence + 1)?;
+            expr = ValueExpression::Operation(ValueOperation {
+                ty,
+                a: Box::new(expr),
+                b: Box::new(b),
+            });
+        }
+        Ok(expr)
+    }
+
+    fn parse_value_operand(&mut self) -> Result<ValueExpression, ExecError> {
+        // the numbers are stored as i64 currently since it is more likely it will be signed when the highest bit is set
+        match self.lex_items.pop_front() {
+            Some(Token {
+                ty: TokenType::Number(number),
+                ..
+            }) => Ok(ValueExpression::Constant(number as i64)),
+            Some(Token {
+                ty: TokenType::Minus,
+                ..
+            }) => match self.lex_items.pop_front() {
+                Some(Token {
+                    ty: TokenType::Number(number),
+                    ..
+                }) => Ok(ValueExpression::Constant((number as i64).wrapping_neg())),
+                token => Err(self.not_parseable(token, "Expected a number after a minus sign")),
+            },
+            Some(Token {
+                ty: TokenType::Ident(ident),
+                span,
+            }) => Ok(ValueExpression::Field(
+                self.parse_field_reference(Some(span), ident)?,
+            )),
+            Some(Token {
+                ty: TokenType::OpeningBracket(Bracket::Parenthesis),
+                ..
+            }) => {
+                let result = self.parse_value_expression(0)?;
+                self.expect(
+                    TokenType::ClosingBracket(Bracket::Parenthesis),
+                    "a parenthesized value expression",
+                )?;
+                Ok(result)
+            }
+            token => Err(self.not_parseable(
+                token,
+                "Expected a field, a number, or a parenthesized value expression",
+            )),
+        }
+    }
+
+    fn parse_field_reference(
         &mut self,
+        first_span: Option<PropertySpan>,
         first_ident: String,
-    ) -> Result<ValueExpression, ExecError> {
+    ) -> Result<FieldReference, ExecError> {
         let forced_signedness = match first_ident.as_str() {
             "as_unsigned" => Signedness::Unsigned,
             "as_signed" => Signedness::Signed,
-            _ => return self.parse_value_expression_inner(first_ident, Signedness::None),
+            _ => {
+                return self.parse_bit_or_field_reference(first_span, first_ident, Signedness::None)
+            }
         };
 
         // there should be parentheses around the inner expression
@@ -285,8 +992,12 @@ impl PropertyParser {
             TokenType::OpeningBracket(Bracket::Parenthesis),
             "inside forced signedness",
         )?;
-        let (_first_token, first_ident) = self.expect_ident("inside forced signedness")?;
-        let result = self.parse_value_expression_inner(first_ident, forced_signedness);
+        let (first_token, first_ident) = self.expect_ident("inside forced signedness")?;
+        let result = self.parse_bit_or_field_reference(
+            first_token.map(|token| token.span),
+            first_ident,
+            forced_signedness,
+        );
         self.expect(
             TokenType::ClosingBracket(Bracket::Parenthesis),
             "inside forced signedness",
@@ -294,43 +1005,194 @@ impl PropertyParser {
         result
     }
 
-    fn parse_value_expression_inner(
+    fn parse_bit_or_field_reference(
         &mut self,
+        first_span: Option<PropertySpan>,
         first_ident: String,
         forced_signedness: Signedness,
-    ) -> Result<ValueExpression, ExecError> {
-        let index = match self.lex_items.front() {
-            Some(Token {
-                ty: TokenType::OpeningBracket(Bracket::Square),
-                ..
-            }) => {
+    ) -> Result<FieldReference, ExecError> {
+        // a field can be also named bit, require the parenthesis
+        if first_ident != "bit"
+            || self.peek_type() != Some(&TokenType::OpeningBracket(Bracket::Parenthesis))
+        {
+            return self.parse_field_reference_inner(first_span, first_ident, forced_signedness);
+        }
+
+        // bit(field, position) is a shorthand for field[position:position]
+        const WHEN_PARSING: &str = "a bit extraction";
+        self.expect(
+            TokenType::OpeningBracket(Bracket::Parenthesis),
+            WHEN_PARSING,
+        )?;
+        let (first_token, first_ident) = self.expect_ident(WHEN_PARSING)?;
+        let mut result = self.parse_field_reference_inner(
+            first_token.map(|token| token.span),
+            first_ident,
+            forced_signedness,
+        )?;
+        self.expect(TokenType::Comma, WHEN_PARSING)?;
+        let (position_token, position) = self.expect_bit_position(WHEN_PARSING)?;
+        if result.slice.is_some() {
+            return Err(
+                self.not_parseable(position_token, "Cannot extract a bit from a sliced field")
+            );
+        }
+        self.expect(
+            TokenType::ClosingBracket(Bracket::Parenthesis),
+            WHEN_PARSING,
+        )?;
+        result.slice = Some(BitSlice {
+            highest_bit: position,
+            lowest_bit: position,
+        });
+        Ok(result)
+    }
+
+    fn parse_field_reference_inner(
+        &mut self,
+        first_span: Option<PropertySpan>,
+        first_ident: String,
+        forced_signedness: Signedness,
+    ) -> Result<FieldReference, ExecError> {
+        // input, parameter and initial fields are prefixed, e.g. input.PIND
+        // the panic id is not a field of the system, but can be referenced as one
+        let source = match first_ident.as_str() {
+            "input" => FieldSource::Input,
+            "param" => FieldSource::Param,
+            "init" => FieldSource::Init,
+            PANIC_ID_NAME => FieldSource::Panic,
+            _ => FieldSource::State,
+        };
+        let (source, first_ident) = match source {
+            FieldSource::Input | FieldSource::Param | FieldSource::Init
+                if self.peek_type() == Some(&TokenType::Dot) =>
+            {
                 self.lex_items.pop_front();
-                let index = match self.lex_items.pop_front() {
-                    Some(Token {
-                        ty: TokenType::Number(index),
-                        ..
-                    }) => index,
-                    token => {
+                let (name_token, name) = self.expect_ident("a field reference")?;
+                self.check_field(source, &name, name_token.map(|token| token.span));
+                (source, name)
+            }
+            FieldSource::Panic => (source, first_ident),
+            _ => {
+                self.check_field(FieldSource::State, &first_ident, first_span);
+                (FieldSource::State, first_ident)
+            }
+        };
+
+        // the field can be indexed first and then sliced, e.g. R[16][7:4]
+        let mut index = None;
+        let mut slice = None;
+        while let Some(TokenType::OpeningBracket(Bracket::Square)) = self.peek_type() {
+            let opening_token = self.lex_items.pop_front();
+            if slice.is_some() {
+                return Err(
+                    self.not_parseable(opening_token, "Cannot index or slice a sliced field")
+                );
+            }
+            let first_token = self.lex_items.pop_front();
+            let first_number = match first_token {
+                Some(Token {
+                    ty: TokenType::Number(first_number),
+                    ..
+                }) => first_number,
+                Some(Token {
+                    ty: TokenType::Ident(ref ident),
+                    ..
+                }) if self.index_variable.as_ref() == Some(ident) => {
+                    // indexed by the quantified variable
+                    if index.is_some() {
                         return Err(
-                            self.not_parseable(token, "Expected a number to use as an index")
-                        )
+                            self.not_parseable(first_token, "Only a single index can be used")
+                        );
                     }
-                };
+                    self.expect(
+                        TokenType::ClosingBracket(Bracket::Square),
+                        "an inner value expression",
+                    )?;
+                    index = Some(FieldIndex::Variable(ident.clone()));
+                    continue;
+                }
+                _ => {
+                    return Err(self.not_parseable(
+                        first_token,
+                        "Expected a number or a quantified variable to use as an index or a bit position",
+                    ))
+                }
+            };
+
+            if let Some(TokenType::Colon) = self.peek_type() {
+                self.lex_items.pop_front();
+                let highest_bit = self.bit_position(first_token.clone(), first_number)?;
+                let (_lowest_token, lowest_bit) = self.expect_bit_position("a bit slice")?;
+                if lowest_bit > highest_bit {
+                    return Err(self.not_parseable(
+                        first_token,
+                        "The highest bit of a slice must not be lower than the lowest bit",
+                    ));
+                }
+                self.expect(TokenType::ClosingBracket(Bracket::Square), "a bit slice")?;
+                slice = Some(BitSlice {
+                    highest_bit,
+                    lowest_bit,
+                });
+            } else {
+                if index.is_some() {
+                    return Err(self.not_parseable(first_token, "Only a single index can be used"));
+                }
                 self.expect(
                     TokenType::ClosingBracket(Bracket::Square),
                     "an inner value expression",
                 )?;
-                Some(index)
+                index = Some(FieldIndex::Number(first_number));
             }
-            _ => None,
-        };
-        Ok(ValueExpression {
+        }
+        Ok(FieldReference {
+            source,
             name: first_ident,
             index,
+            slice,
             forced_signedness,
         })
     }
 
+    fn expect_number(&mut self, when_parsing: &str) -> Result<(Option<Token>, u64), ExecError> {
+        let token = self.lex_items.pop_front();
+        match token {
+            Some(Token {
+                ty: TokenType::Number(number),
+                ..
+            }) => Ok((token, number)),
+            token => Err(self.not_parseable(
+                token,
+                &format!("Expected a number when parsing {}", when_parsing),
+            )),
+        }
+    }
+
+    fn expect_bit_position(
+        &mut self,
+        when_parsing: &str,
+    ) -> Result<(Option<Token>, u32), ExecError> {
+        let token = self.lex_items.pop_front();
+        match token {
+            Some(Token {
+                ty: TokenType::Number(number),
+                ..
+            }) => {
+                let position = self.bit_position(token.clone(), number)?;
+                Ok((token, position))
+            }
+            token => Err(self.not_parseable(
+                token,
+                &format!("Expected a bit position when parsing {}", when_parsing),
+            )),
+        }
+    }
+
+    fn bit_position(&self, token: Option<Token>, number: u64) -> Result<u32, ExecError> {
+        u32::try_from(number).map_err(|_| self.not_parseable(token, "The bit position is too high"))
+    }
+
     fn expect(&mut self, expected: TokenType, when_parsing: &str) -> Result<(), ExecError> {
         let token = self.lex_items.pop_front();
         if token.as_ref().map(|token| &token.ty) == Some(&expected) {
@@ -338,7 +1200,7 @@ impl PropertyParser {
         } else {
             Err(self.not_parseable(
                 token,
-                &format!("Expected {:?} when parsing {}", expected, when_parsing),
+                &format!("Expected {} when parsing {}", expected, when_parsing),
             ))
         }
     }
@@ -361,16 +1223,189 @@ impl PropertyParser {
     }
 
     fn not_parseable(&self, token: Option<Token>, reason: &str) -> ExecError {
-        let reason = if let Some(token) = token {
-            format!(
-                "At {}..={}: {} (have {:?})",
-                token.span.start, token.span.end, reason, token.ty
-            )
+        let error = if let Some(token) = token {
+            PropertyDiagnostic::new(Some(token.span), format!("{}, found {}", reason, token.ty))
         } else {
-            format!("Beyond the end of input: {}", reason)
+            // point just after the end of input
+            let end = self.input.chars().count();
+            PropertyDiagnostic::new(
+                Some(PropertySpan { start: end, end }),
+                format!("{}, found the end of input", reason),
+            )
         };
-        ExecError::PropertyNotParseable(self.input.clone(), reason)
+        self.error(Some(error))
     }
+
+    /// Returns the parsing error with the errors found so far and the given one.
+    fn error(&self, error: Option<PropertyDiagnostic>) -> ExecError {
+        let mut errors = self.errors.clone();
+        errors.extend(error);
+        // keep the errors in the order of their positions
+        errors.sort_by_key(|error| error.span.map(|span| span.start));
+        ExecError::PropertyNotParseable(PropertyDiagnostics {
+            input: self.input.clone(),
+            errors,
+        })
+    }
+
+    /// Checks that the referenced field is among the known fields, if they are given.
+    ///
+    /// An unknown field does not prevent continuing the parsing.
+    fn check_field(&mut self, source: FieldSource, name: &str, span: Option<PropertySpan>) {
+        let Some(names) = &self.names else {
+            return;
+        };
+        let names_of = |source: FieldSource| match source {
+            FieldSource::State | FieldSource::Init => names.state.as_slice(),
+            FieldSource::Input => names.input.as_slice(),
+            FieldSource::Param => names.param.as_slice(),
+            FieldSource::Panic => &[],
+        };
+        if names_of(source).iter().any(|known| known == name) {
+            return;
+        }
+
+        let reference = |source: FieldSource, name: &str| match source {
+            FieldSource::State | FieldSource::Panic => String::from(name),
+            FieldSource::Input => format!("input.{}", name),
+            FieldSource::Param => format!("param.{}", name),
+            FieldSource::Init => format!("init.{}", name),
+        };
+        let mut error =
+            PropertyDiagnostic::new(span, format!("Unknown field '{}'", reference(source, name)));
+
+        // suggest the field of another source with the same name or the closest one
+        let other_source = [FieldSource::State, FieldSource::Input, FieldSource::Param]
+            .into_iter()
+            .find(|other| names_of(*other).iter().any(|known| known == name));
+        if let Some(other_source) = other_source {
+            error = error.with_help(format!(
+                "the field is referenced as '{}'",
+                reference(other_source, name)
+            ));
+        } else if let Some(closest) = closest_name(name, names_of(source)) {
+            error = error.with_help(format!(
+                "a field with a similar name exists: '{}'",
+                reference(source, closest)
+            ));
+        }
+        self.errors.push(error);
+    }
+
+    /// Resolves the panic message to the panic ids of all panic sites with the message,
+    /// using the known panic messages.
+    ///
+    /// An unknown panic message does not prevent continuing the parsing,
+    /// the only returned panic id is zero in that case.
+    fn resolve_panic_message(&mut self, message: &str, span: PropertySpan) -> Vec<u64> {
+        let error = match &self.names {
+            Some(names) => {
+                // the panic ids start from 1
+                let panic_ids: Vec<u64> = names
+                    .panic_messages
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, known)| *known == message)
+                    .map(|(index, _)| index as u64 + 1)
+                    .collect();
+                if !panic_ids.is_empty() {
+                    return panic_ids;
+                }
+                let error = PropertyDiagnostic::new(
+                    Some(span),
+                    format!("Unknown panic message {:?}", message),
+                );
+                match closest_name(message, &names.panic_messages) {
+                    Some(closest) => error.with_help(format!(
+                        "a panic message with a similar text exists: {:?}",
+                        closest
+                    )),
+                    None if names.panic_messages.is_empty() => {
+                        error.with_help("the system has no panic messages")
+                    }
+                    None => error,
+                }
+            }
+            None => PropertyDiagnostic::new(
+                Some(span),
+                "Panic messages cannot be referenced without knowing the system",
+            ),
+        };
+        self.errors.push(error);
+        vec![0]
+    }
+}
+
+/// Returns the equality of the state field with the same field in the initial states.
+fn state_eq_init(field: FieldReference) -> AtomicProperty {
+    let init_field = FieldReference {
+        source: FieldSource::Init,
+        ..field.clone()
+    };
+    AtomicProperty::new(
+        ValueExpression::Field(field),
+        ComparisonType::Eq,
+        ValueExpression::Field(init_field),
+    )
+}
+
+/// The names of the CTL and fixed-point operators, used as macros.
+const CTL_OPERATORS: &[&str] = &[
+    "AX", "AF", "AG", "AU", "AR", "EX", "EF", "EG", "EU", "ER", "lfp", "gfp",
+];
+
+/// Returns the known name closest to the given one, if it is close enough to be a typo.
+///
+/// The closeness is measured by the edit distance.
+fn closest_name<'a>(name: &str, known: &'a [String]) -> Option<&'a str> {
+    let name: Vec<char> = name.chars().collect();
+    let max_distance = (name.len() + 2) / 3;
+
+    known
+        .iter()
+        .map(|known_name| {
+            // compute the Levenshtein distance row by row
+            let known_chars: Vec<char> = known_name.chars().collect();
+            let mut previous: Vec<usize> = (0..=known_chars.len()).collect();
+            for (i, name_char) in name.iter().enumerate() {
+                let mut current = vec![i + 1];
+                for (j, known_char) in known_chars.iter().enumerate() {
+                    let substitution = previous[j] + usize::from(name_char != known_char);
+                    current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+                }
+                previous = current;
+            }
+            (previous[known_chars.len()], known_name.as_str())
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known_name)| known_name)
+}
+
+/// Returns the operation denoted by the token type, if any.
+fn operation_type(token_type: &TokenType) -> Option<OperationType> {
+    Some(match token_type {
+        TokenType::Plus => OperationType::Add,
+        TokenType::Minus => OperationType::Sub,
+        TokenType::Star => OperationType::Mul,
+        TokenType::BitAnd => OperationType::BitAnd,
+        TokenType::BitOr => OperationType::BitOr,
+        TokenType::BitXor => OperationType::BitXor,
+        _ => return None,
+    })
+}
+
+/// Returns whether the token ends an operand of the logic operators when not in brackets.
+fn ends_operand(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::LogicAnd
+            | TokenType::LogicOr
+            | TokenType::LogicImplication
+            | TokenType::LogicEquivalence
+            | TokenType::Comma
+            | TokenType::ClosingBracket(_)
+    )
 }
 
 fn existential_op(temporal: TemporalOperator) -> Property {
@@ -393,25 +1428,33 @@ fn test_parse() {
         let parsed = parse_inner(str).unwrap();
         let ag = universal_op(TemporalOperator::G(OperatorG(Box::new(Property::Atomic(
             AtomicProperty {
-                left: ValueExpression {
+                quantifier: None,
+                left: ValueExpression::Field(FieldReference {
+                    source: FieldSource::State,
                     name: String::from("a"),
                     index: None,
+                    slice: None,
                     forced_signedness: Signedness::None,
-                },
+                }),
                 comparison_type: crate::property::ComparisonType::Eq,
-                right_number: 0,
+                right: ValueExpression::Constant(0),
+                conjoined: Vec::new(),
             },
         )))));
 
         let ef = existential_op(TemporalOperator::F(OperatorF(Box::new(Property::Atomic(
             AtomicProperty {
-                left: ValueExpression {
+                quantifier: None,
+                left: ValueExpression::Field(FieldReference {
+                    source: FieldSource::State,
                     name: String::from("b"),
-                    index: Some(32),
+                    index: Some(FieldIndex::Number(32)),
+                    slice: None,
                     forced_signedness: Signedness::Signed,
-                },
+                }),
                 comparison_type: crate::property::ComparisonType::Ne,
-                right_number: 3,
+                right: ValueExpression::Constant(3),
+                conjoined: Vec::new(),
             },
         )))));
 
@@ -433,36 +1476,48 @@ fn test_parse() {
         let until = Property::BiLogic(BiLogicOperator {
             is_and: false,
             a: Box::new(Property::Atomic(AtomicProperty {
-                left: ValueExpression {
+                quantifier: None,
+                left: ValueExpression::Field(FieldReference {
+                    source: FieldSource::State,
                     name: String::from("ALREADY_UNSIGNED"),
                     index: None,
+                    slice: None,
                     forced_signedness: Signedness::None,
-                },
+                }),
                 comparison_type: crate::property::ComparisonType::Le,
-                right_number: 0x5E,
+                right: ValueExpression::Constant(0x5E),
+                conjoined: Vec::new(),
             })),
             b: Box::new(Property::Negation(Box::new(Property::Atomic(
                 AtomicProperty {
-                    left: ValueExpression {
+                    quantifier: None,
+                    left: ValueExpression::Field(FieldReference {
+                        source: FieldSource::State,
                         name: String::from("abc"),
                         index: None,
+                        slice: None,
                         forced_signedness: Signedness::None,
-                    },
+                    }),
                     comparison_type: crate::property::ComparisonType::Ge,
-                    right_number: -3,
+                    right: ValueExpression::Constant(-3),
+                    conjoined: Vec::new(),
                 },
             )))),
         });
 
         let created = existential_op(TemporalOperator::U(OperatorU {
             hold: Box::new(Property::Atomic(AtomicProperty {
-                left: ValueExpression {
+                quantifier: None,
+                left: ValueExpression::Field(FieldReference {
+                    source: FieldSource::State,
                     name: String::from("prOpeRty"),
                     index: None,
+                    slice: None,
                     forced_signedness: Signedness::Signed,
-                },
+                }),
                 comparison_type: crate::property::ComparisonType::Gt,
-                right_number: 37,
+                right: ValueExpression::Constant(37),
+                conjoined: Vec::new(),
             })),
             until: Box::new(until),
         }));
@@ -474,5 +1529,515 @@ fn test_parse() {
             "EU![as_signed(prOpeRty) > 37, ALREADY_UNSIGNED <= 94 || !(abc >= -3)]"
         );
     }
+    {
+        let parsed = parse_inner("AG![as_unsigned(lo) <= as_unsigned(hi)]").unwrap();
+        let created = universal_op(TemporalOperator::G(OperatorG(Box::new(Property::Atomic(
+            AtomicProperty {
+                quantifier: None,
+                left: ValueExpression::Field(FieldReference {
+                    source: FieldSource::State,
+                    name: String::from("lo"),
+                    index: None,
+                    slice: None,
+                    forced_signedness: Signedness::Unsigned,
+                }),
+                comparison_type: crate::property::ComparisonType::Le,
+                right: ValueExpression::Field(FieldReference {
+                    source: FieldSource::State,
+                    name: String::from("hi"),
+                    index: None,
+                    slice: None,
+                    forced_signedness: Signedness::Unsigned,
+                }),
+                conjoined: Vec::new(),
+            },
+        )))));
+        assert_eq!(parsed, created);
+    }
+    {
+        let str = "(a + b) * 2 == c & 0xF0 | d[1] ^ 1 && ((e - (f - 1))) != -2";
+        let parsed = parse_inner(str).unwrap();
+        assert_eq!(
+            &parsed.to_string(),
+            "(a + b) * 2 == c & 240 | d[1] ^ 1 && e - (f - 1) != -2"
+        );
+    }
     assert!(parse("property > 3 token_after_end").is_err());
+    assert!(parse("(a + b) && c == 1").is_err());
+
+    {
+        let parsed = parse_inner("bit(PORTB, 5) == 1 && as_signed(R[16][7:4]) < 0").unwrap();
+        assert_eq!(
+            &parsed.to_string(),
+            "PORTB[5:5] == 1 && as_signed(R[16][7:4]) < 0"
+        );
+        let bit = parse_inner("bit == 1").unwrap();
+        assert_eq!(&bit.to_string(), "bit == 1");
+    }
+    assert!(parse("SREG[3:4] == 0").is_err());
+    assert!(parse("SREG[7:7][1] == 0").is_err());
+    assert!(parse("bit(SREG[7:6], 1) == 0").is_err());
+}
+
+#[test]
+fn test_parse_ltl() {
+    let str = "G![!(req == 1) || F![ack == 1]] && U![a == 0, X![b[2] != 1]]";
+    let parsed = PropertyParser::new(str, BTreeMap::new(), None).parse_ltl();
+    assert_eq!(&parsed.unwrap().to_string(), str);
+
+    let translated = parse_ltl(str, &[], None).unwrap();
+    assert_eq!(translated.root_subproperty().display_str(), Some(str));
+    assert!(parse_ltl("G![a == 0] AG![a == 0]", &[], None).is_err());
+    assert!(parse_ltl("AG![a == 0]", &[], None).is_err());
+}
+
+#[test]
+fn test_parse_fair() {
+    let str = "AF![a == 1]";
+    let fair = parse_fair(str, &[String::from("b == 1")], None).unwrap();
+    assert_eq!(fair.root_subproperty().display_str(), Some(str));
+    assert_eq!(parse_fair(str, &[], None).unwrap(), parse(str).unwrap());
+    assert!(parse_fair(str, &[String::from("b ==")], None).is_err());
+    assert!(parse_ltl("F![a == 1]", &[String::from("b == 1")], None).is_ok());
+    assert!(parse_ltl("F![a == 1]", &[String::from("AF[<=2]![b == 1]")], None).is_ok());
+}
+
+#[test]
+fn test_parse_bounded() {
+    let str = "AF[<=20]![a == 1] && EU[<=3]![b == 0, EG[<=0]![c == 1]]";
+    let parsed = parse_inner(str).unwrap();
+    assert_eq!(&parsed.to_string(), str);
+
+    let folded = parse(str).unwrap();
+    assert_eq!(folded.root_subproperty().display_str(), Some(str));
+    assert_eq!(folded.transition_depth(), 20);
+    assert_eq!(parse("EG[<=0]![c == 1]").unwrap().transition_depth(), 0);
+    assert_eq!(
+        parse("AR[<=2]![a == 1, AX![b == 1]]")
+            .unwrap()
+            .transition_depth(),
+        3
+    );
+
+    // a field can still be indexed
+    assert!(parse("AF[2] == 1").is_ok());
+    assert!(parse("AX[<=2]![a == 1]").is_err());
+    assert!(parse("AF[<2]![a == 1]").is_err());
+    assert!(parse("AF[<=a]![a == 1]").is_err());
+    assert!(parse(&format!("AF[<={}]![a == 1]", MAX_BOUND + 1)).is_err());
+
+    // the expanded size is computed without expanding
+    for str in ["AF[<=20]![a == 1]", "EU[<=3]![b == 0, EG[<=2]![c == 1]]"] {
+        assert_eq!(
+            parse(str).unwrap().arena.len(),
+            parse_inner(str).unwrap().expanded_size()
+        );
+    }
+    // nested bounded operators grow exponentially when expanded
+    assert!(parse("AF[<=16]![AG[<=16]![a == 1]]").is_ok());
+    assert!(parse("AF[<=256]![AG[<=256]![a == 1]]").is_err());
+    assert!(parse("AF[<=256]![AG[<=256]![AF[<=256]![AG[<=256]![a == 1]]]]").is_err());
+    assert!(parse_reachability("AF[<=256]![AG[<=256]![a == 1]]", true, None).is_err());
+}
+
+#[test]
+fn test_parse_reachability() {
+    let universal = parse_reachability("a == 1 && b == 0", true, None).unwrap();
+    assert_eq!(
+        universal.root_subproperty().display_str(),
+        Some("AF![a == 1 && b == 0]")
+    );
+    let existential = parse_reachability("a == 1", false, None).unwrap();
+    assert_eq!(
+        existential.root_subproperty().display_str(),
+        Some("EF![a == 1]")
+    );
+    assert!(parse_reachability("a ==", true, None).is_err());
+}
+
+#[test]
+fn test_parse_input_param() {
+    let str = "AG![input.PIND[3:2] == param.max_value || as_signed(input.data) < 0]";
+    let parsed = parse(str).unwrap();
+    assert_eq!(parsed.root_subproperty().display_str(), Some(str));
+    assert!(parsed.references_source(FieldSource::Input));
+    assert!(parsed.references_source(FieldSource::Param));
+
+    // fields can still be named input or param
+    let state_field = parse("AG![input == 1 && bit(param, 2) == 0]").unwrap();
+    assert!(!state_field.references_source(FieldSource::Input));
+    assert!(!state_field.references_source(FieldSource::Param));
+
+    assert!(parse("AG![input. == 1]").is_err());
+    assert!(parse("AG![state.value == 1]").is_err());
+}
+
+#[test]
+fn test_parse_quantified() {
+    let str = "AG![forall i in 0..32: R[i] != 255] && EF![exists j in 4..8: R[j][3:0] == R[0] + 1]";
+    let parsed = parse_inner(str).unwrap();
+    assert_eq!(&parsed.to_string(), str);
+
+    let Property::BiLogic(op) = parsed else {
+        panic!("Expected a conjunction");
+    };
+    let Property::Ctl(CtlOperator {
+        temporal: TemporalOperator::G(OperatorG(inner)),
+        ..
+    }) = *op.a
+    else {
+        panic!("Expected a globally operator");
+    };
+    let Property::Atomic(atomic) = *inner else {
+        panic!("Expected an atomic property");
+    };
+    let quantifier = atomic.quantifier().unwrap();
+    assert!(quantifier.is_universal());
+    assert_eq!((quantifier.start(), quantifier.end()), (0, 32));
+    assert_eq!(atomic.fields()[0].index_variable(), Some("i"));
+    let instantiated = atomic.instantiate(5);
+    assert!(instantiated.quantifier().is_none());
+    assert_eq!(&instantiated.to_string(), "R[5] != 255");
+
+    // fields can still be named forall or exists
+    assert!(parse("forall == 1 && exists[2] == 0").is_ok());
+    assert!(parse_ltl("G![forall i in 0..4: R[i] == 0]", &[], None).is_ok());
+
+    assert!(parse("forall i in 4..4: R[i] == 0").is_err());
+    assert!(parse("forall i in 0..4: R[j] == 0").is_err());
+    assert!(parse("forall i in 0..4: exists j in 0..4: R[i] == R[j]").is_err());
+    assert!(parse("forall i in 0..4: R[i][i] == 0").is_err());
+    assert!(parse("R[i] == 0").is_err());
+}
+
+#[test]
+fn test_parse_derived_logic() {
+    let str = "AG![a == 1 -> AF![b == 1]] && EF![(c == 0) ^ !(d == 2)]";
+    let parsed = parse_inner(str).unwrap();
+    assert_eq!(&parsed.to_string(), str);
+
+    // implications are right-associative and bind more tightly than equivalences
+    let parsed = parse_inner("a == 1 && b == 1 -> c == 1 -> d == 1 <-> e<-1").unwrap();
+    assert_eq!(
+        &parsed.to_string(),
+        "((a == 1 && b == 1) -> (c == 1 -> d == 1)) <-> e < -1"
+    );
+    // the exclusive or is still a bitwise operation within value expressions
+    let parsed = parse_inner("(a ^ 1 == 0) ^ (b == 1) ^ AX![c == 1]").unwrap();
+    assert_eq!(
+        &parsed.to_string(),
+        "((a ^ 1 == 0) ^ (b == 1)) ^ AX![c == 1]"
+    );
+
+    // the lowered property is displayed as written, with the operands as its children
+    let str = "a == 1 -> b == 1";
+    let folded = parse(str).unwrap();
+    let root = folded.root_subproperty();
+    assert_eq!(root.display_str(), Some(str));
+    let children = root.displayed_children();
+    assert_eq!(children[0].display_str(), None);
+    assert_eq!(
+        children[0].displayed_children()[0].display_str(),
+        Some("a == 1")
+    );
+    assert_eq!(children[1].display_str(), Some("b == 1"));
+
+    assert!(parse("a == 1 && b == 1 || c == 1").is_err());
+    assert!(parse("AX![a == 1] ^ AX![b == 1] && c == 1").is_err());
+    assert!(parse("a == 1 -> ").is_err());
+    assert!(parse("lfp![Z, a == 1 -> AX![Z]]").is_ok());
+    assert!(parse("lfp![Z, AX![Z] -> a == 1]").is_err());
+    assert!(parse("gfp![Z, AX![Z] <-> a == 1]").is_err());
+}
+
+#[test]
+fn test_parse_file() {
+    let input = "// shared building blocks
+let in_isr = SREG[7:7] == 0;
+let stack_ok = as_unsigned(SP) >= 0x100; // the stack is above registers
+let led_on = led_on == 1 && in_isr;
+
+property stack: AG![stack_ok];
+property isr_led:
+    AG![in_isr -> AF![led_on]];
+";
+    let named = parse_file(input, &[], None).unwrap();
+    let names: Vec<&str> = named.iter().map(|named| named.name.as_str()).collect();
+    assert_eq!(names, ["stack", "isr_led"]);
+
+    // the definitions are displayed by their names
+    let isr_led = named[1].property.root_subproperty();
+    assert_eq!(isr_led.display_str(), Some("AG![in_isr -> AF![led_on]]"));
+    let stack = parse_file("let a = b == 1; property p: a;", &[], None).unwrap();
+    let root = stack[0].property.root_subproperty();
+    assert_eq!(root.display_str(), Some("a"));
+    assert!(root.displayed_children().is_empty());
+
+    // fields can still have the same names as definitions
+    assert!(parse_file("let a = a == 1; property p: a && a[3:0] == 2;", &[], None).is_ok());
+    assert!(parse_file(input, &[String::from("in_isr == 1")], None).is_ok());
+
+    let errors = |input: &str| match parse_file(input, &[], None) {
+        Err(ExecError::PropertyFileNotParseable(diagnostics)) => {
+            assert_eq!(diagnostics.input, input);
+            diagnostics.errors
+        }
+        result => panic!("Expected a property file error, have {:?}", result),
+    };
+    let error_line = |input: &str| {
+        errors(input)[0].span.map(|span| {
+            input
+                .chars()
+                .take(span.start)
+                .filter(|c| *c == '\n')
+                .count()
+                + 1
+        })
+    };
+    assert_eq!(error_line("let a = b == 1;\nproperty p: a"), Some(2));
+    assert_eq!(error_line("let a = b == 1;\n\nproperty p: c;"), Some(3));
+    assert_eq!(
+        error_line("let a = b == 1; let a = c == 1; property p: a;"),
+        Some(1)
+    );
+    assert_eq!(
+        error_line("property p: a == 1;\nproperty p: a == 2;"),
+        Some(2)
+    );
+    assert_eq!(error_line("property p: a == 1;\n;"), Some(2));
+    assert_eq!(error_line("property 1p: a == 1;"), Some(1));
+    assert_eq!(error_line("prop p: a == 1;"), Some(1));
+    assert_eq!(error_line("let a = b == 1;\n// no properties"), None);
+
+    // the errors of the properties point to the file
+    let span = |start, end| Some(PropertySpan { start, end });
+    let input = "let a = b == 1; // \"x\";\nproperty p: AG![a && c = 1 && d == ];";
+    let found = errors(input);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].span, span(47, 47));
+    assert_eq!(found[1].span, span(59, 59));
+    assert_eq!(
+        errors("property p: a == 1;\nlet  b =")[0].span,
+        span(28, 28)
+    );
+    assert_eq!(
+        errors("let b = c == 1;\nlet  b = c == 2;")[0].span,
+        span(21, 21)
+    );
+    let rendered = parse_file("let a = b == 1;\nproperty p: a = 1;", &[], None)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        rendered,
+        "property file could not be parsed\nerror: A single '=' cannot be used\n  |\n2 | property p: a = 1;\n  |               ^\n  = help: use '==' to compare for equality"
+    );
+}
+
+#[test]
+fn test_parse_diagnostics() {
+    let names = SystemNames {
+        state: vec![String::from("value"), String::from("R")],
+        input: vec![String::from("increment")],
+        param: Vec::new(),
+        panic_messages: Vec::new(),
+    };
+    let errors = |input: &str| match parse_fair(input, &[], Some(&names)) {
+        Err(ExecError::PropertyNotParseable(diagnostics)) => {
+            assert_eq!(diagnostics.input, input);
+            diagnostics.errors
+        }
+        result => panic!("Expected a property error, have {:?}", result),
+    };
+    let span = |start, end| Some(PropertySpan { start, end });
+
+    // several errors are reported at once
+    let found = errors("AG![valeu = 3 && increment == 1]");
+    assert_eq!(found.len(), 3);
+    assert_eq!(found[0].span, span(4, 8));
+    assert_eq!(
+        found[0].help.as_deref(),
+        Some("a field with a similar name exists: 'value'")
+    );
+    assert_eq!(found[1].span, span(10, 10));
+    assert_eq!(
+        found[1].help.as_deref(),
+        Some("use '==' to compare for equality")
+    );
+    assert_eq!(found[2].span, span(17, 25));
+    assert_eq!(
+        found[2].help.as_deref(),
+        Some("the field is referenced as 'input.increment'")
+    );
+
+    // the parsing recovers at the ends of the operands
+    let found = errors("AG![value == && value + == 1] && EF![value > ]");
+    assert_eq!(found.len(), 3);
+    assert_eq!(found[0].span, span(13, 14));
+    assert_eq!(found[1].span, span(24, 25));
+    assert_eq!(found[2].span, span(45, 45));
+    let found = errors("lfp![X, (value = 1 || EX![X]) && (== 2)]");
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].span, span(15, 15));
+    assert_eq!(found[1].span, span(34, 35));
+    match parse_ltl("G![value == || F![value + == 1]]", &[], Some(&names)) {
+        Err(ExecError::PropertyNotParseable(diagnostics)) => {
+            assert_eq!(diagnostics.errors.len(), 2)
+        }
+        result => panic!("Expected a property error, have {:?}", result),
+    }
+
+    let found = errors("AG[value == 3]");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].span, span(0, 1));
+    assert!(found[0].message.contains("Expected '!'"));
+
+    let found = errors("AG![value == 3 @] ");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].message, "Unknown character '@'");
+
+    let found = errors("AG![value ==");
+    assert_eq!(found[0].span, span(12, 12));
+    assert!(found[0].message.ends_with("found the end of input"));
+
+    // the fields are only checked if given
+    assert!(parse_fair("AG![valeu == 3]", &[], None).is_ok());
+    assert!(parse_fair("AG![forall i in 0..4: R[i] == 0]", &[], Some(&names)).is_ok());
+
+    let rendered = ExecError::PropertyNotParseable(PropertyDiagnostics {
+        input: String::from("AG![value = 3]"),
+        errors: errors("AG![value = 3]"),
+    })
+    .to_string();
+    assert_eq!(
+        rendered,
+        "property could not be parsed\nerror: A single '=' cannot be used\n  |\n1 | AG![value = 3]\n  |           ^\n  = help: use '==' to compare for equality"
+    );
+}
+
+#[test]
+fn test_parse_panic() {
+    let names = SystemNames {
+        state: vec![String::from("value")],
+        panic_messages: vec![
+            String::from("attempt to divide by zero"),
+            String::from("Reserved opcode; halting"),
+        ],
+        ..Default::default()
+    };
+    let parse = |input: &str| parse_fair(input, &[], Some(&names));
+
+    // the panic propositions are displayed as written and resolved to the panic ids
+    let property = parse(r#"AG![!(panic("Reserved opcode; halting"))]"#).unwrap();
+    let (index, atomic) = (0..property.num_subproperties())
+        .find_map(|index| match &property.subproperty_entry(index).ty {
+            super::PropertyType::Atomic(atomic) => Some((index, atomic.clone())),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(atomic.to_string(), "panic_id == 2");
+    assert_eq!(atomic.fields()[0].source(), FieldSource::Panic);
+    let proposition = super::Subproperty { property, index };
+    assert_eq!(
+        proposition.display_str(),
+        Some(r#"panic("Reserved opcode; halting")"#)
+    );
+
+    // a message shared by multiple panic sites is resolved to all of their panic ids
+    {
+        let names = SystemNames {
+            panic_messages: vec![
+                String::from("Reserved opcode"),
+                String::from("attempt to divide by zero"),
+                String::from("Reserved opcode"),
+            ],
+            ..Default::default()
+        };
+        let parse = |input: &str| parse_fair(input, &[], Some(&names));
+        let property = parse(r#"AG![!(panic("Reserved opcode"))]"#).unwrap();
+        let comparisons: Vec<String> = (0..property.num_subproperties())
+            .filter_map(|index| match &property.subproperty_entry(index).ty {
+                super::PropertyType::Atomic(atomic) => Some(atomic.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(comparisons, ["panic_id == 1", "panic_id == 3"]);
+    }
+
+    // the panic id can be referenced directly
+    assert!(parse("AG![panic_id == 0 || value == 1]").is_ok());
+    assert!(parse(r#"AG![panic("attempt to divide by zero") -> panic_id != 2]"#).is_ok());
+
+    let errors = |input: &str| match parse(input) {
+        Err(ExecError::PropertyNotParseable(diagnostics)) => diagnostics.errors,
+        result => panic!("Expected a property error, have {:?}", result),
+    };
+    let found = errors(r#"AG![!(panic("attempt to divide by zeor"))]"#);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].span, Some(PropertySpan { start: 12, end: 38 }));
+    assert_eq!(
+        found[0].help.as_deref(),
+        Some(r#"a panic message with a similar text exists: "attempt to divide by zero""#)
+    );
+    assert!(errors("AG![panic(value)]")[0]
+        .message
+        .starts_with("Expected a panic message string"));
+    assert_eq!(
+        errors(r#"AG![panic("a)]"#)[0].message,
+        "Unterminated string"
+    );
+
+    // the panic messages are only known with the system
+    assert!(parse_fair(r#"AG![panic("attempt to divide by zero")]"#, &[], None).is_err());
+
+    let named = parse_file(
+        "// a semicolon in a message\nproperty reserved: AG![!(panic(\"Reserved opcode; halting\"))]; // done",
+        &[],
+        Some(&names),
+    )
+    .unwrap();
+    assert_eq!(named[0].name, "reserved");
+}
+
+#[test]
+fn test_parse_init() {
+    let names = SystemNames {
+        state: vec![String::from("value"), String::from("R")],
+        ..Default::default()
+    };
+    let parse = |input: &str| parse_fair(input, &[], Some(&names));
+
+    // the initial state proposition compares all of the state fields with the same initial state
+    let property = parse("AG![EF![init]]").unwrap();
+    let comparisons: Vec<String> = (0..property.num_subproperties())
+        .filter_map(|index| match &property.subproperty_entry(index).ty {
+            super::PropertyType::Atomic(atomic) => Some(atomic.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(comparisons, ["value == init.value && R == init.R"]);
+
+    let parsed = parse_inner("AG![state_eq_init(R[2][3:0]) || value != init.value]").unwrap();
+    assert_eq!(
+        parsed.to_string(),
+        "AG![state_eq_init(R[2][3:0]) || value != init.value]"
+    );
+    assert!(parse("AG![state_eq_init(input.value)]").is_err());
+
+    // fields can still be named init
+    assert!(parse_inner("AG![init == 1 && init.init == 1]").is_ok());
+
+    let errors = |input: &str| match parse(input) {
+        Err(ExecError::PropertyNotParseable(diagnostics)) => diagnostics.errors,
+        result => panic!("Expected a property error, have {:?}", result),
+    };
+    assert_eq!(
+        errors("AG![state_eq_init(valeu)]")[0].help.as_deref(),
+        Some("a field with a similar name exists: 'value'")
+    );
+    assert_eq!(
+        errors("AG![init.valeu == 0]")[0].help.as_deref(),
+        Some("a field with a similar name exists: 'init.value'")
+    );
+    // the initial states are only known with the system
+    assert!(parse_fair("AG![EF![init]]", &[], None).is_err());
 }
@@ -0,0 +1,198 @@
+use super::original::{
+    BiLogicOperator, CtlOperator, Definition, DerivedLogicOperator, FixedPointOperator, Property,
+    TemporalOperator,
+};
+
+/// Restricts the CTL path quantifiers of the property to fair paths.
+///
+/// A path is fair if each of the constraints holds infinitely often on it.
+/// The fair existential operators are translated to mu-calculus using
+/// the Emerson-Lei characterisation of fair EG, the universal operators
+/// are translated via their existential duals. Fixed-point operators given
+/// directly by the user are kept, but the CTL operators inside them are made fair.
+pub fn apply(property: Property, constraints: &[Property]) -> Property {
+    assert!(!constraints.is_empty());
+    let mut applier = FairnessApplier {
+        constraints,
+        num_variables: 0,
+    };
+    applier.apply(property)
+}
+
+struct FairnessApplier<'a> {
+    constraints: &'a [Property],
+    num_variables: usize,
+}
+
+impl FairnessApplier<'_> {
+    fn apply(&mut self, property: Property) -> Property {
+        match property {
+            Property::Const(_) | Property::Atomic(_) | Property::FixedVariable(_) => property,
+            Property::Negation(inner) => not(self.apply(*inner)),
+            Property::BiLogic(op) => Property::BiLogic(BiLogicOperator {
+                is_and: op.is_and,
+                a: Box::new(self.apply(*op.a)),
+                b: Box::new(self.apply(*op.b)),
+            }),
+            Property::DerivedLogic(op) => Property::DerivedLogic(DerivedLogicOperator {
+                ty: op.ty,
+                a: Box::new(self.apply(*op.a)),
+                b: Box::new(self.apply(*op.b)),
+            }),
+            Property::FixedPoint(op) => Property::FixedPoint(FixedPointOperator {
+                is_greatest: op.is_greatest,
+                variable: op.variable,
+                inner: Box::new(self.apply(*op.inner)),
+            }),
+            Property::Ctl(op) => self.apply_ctl(op),
+            // the expanded steps are made fair separately
+            Property::BoundedCtl(op) => self.apply(op.expand()),
+            Property::Definition(definition) => Property::Definition(Definition {
+                name: definition.name,
+                inner: Box::new(self.apply(*definition.inner)),
+            }),
+        }
+    }
+
+    fn apply_ctl(&mut self, op: CtlOperator) -> Property {
+        match (op.is_universal, op.temporal) {
+            (false, TemporalOperator::X(inner)) => {
+                // EX_f p = EX(p && fair)
+                let inner = self.apply(*inner);
+                let fair = self.fair();
+                ex(and(inner, fair))
+            }
+            (true, TemporalOperator::X(inner)) => {
+                // AX_f p = !EX(!p && fair)
+                let inner = self.apply(*inner);
+                let fair = self.fair();
+                not(ex(and(not(inner), fair)))
+            }
+            (false, TemporalOperator::F(inner)) => {
+                // EF_f p = E[true U (p && fair)]
+                let inner = self.apply(*inner.0);
+                let fair = self.fair();
+                self.eu(Property::Const(true), and(inner, fair))
+            }
+            (true, TemporalOperator::F(inner)) => {
+                // AF_f p = !EG_f !p
+                let inner = self.apply(*inner.0);
+                not(self.eg(not(inner)))
+            }
+            (false, TemporalOperator::G(inner)) => {
+                let inner = self.apply(*inner.0);
+                self.eg(inner)
+            }
+            (true, TemporalOperator::G(inner)) => {
+                // AG_f p = !EF_f !p
+                let inner = self.apply(*inner.0);
+                let fair = self.fair();
+                not(self.eu(Property::Const(true), and(not(inner), fair)))
+            }
+            (false, TemporalOperator::U(inner)) => {
+                // E_f[p U q] = E[p U (q && fair)]
+                let hold = self.apply(*inner.hold);
+                let until = self.apply(*inner.until);
+                let fair = self.fair();
+                self.eu(hold, and(until, fair))
+            }
+            (true, TemporalOperator::U(inner)) => {
+                // A_f[p U q] = !E_f[!q U (!p && !q)] && !EG_f !q
+                let hold = self.apply(*inner.hold);
+                let until = self.apply(*inner.until);
+                let fair = self.fair();
+                let never_until = self.eu(
+                    not(until.clone()),
+                    and(and(not(hold), not(until.clone())), fair),
+                );
+                let globally_not_until = self.eg(not(until));
+                and(not(never_until), not(globally_not_until))
+            }
+            (false, TemporalOperator::R(inner)) => {
+                // E_f[p R q] = E_f[q U (p && q)] || EG_f q
+                let releaser = self.apply(*inner.releaser);
+                let releasee = self.apply(*inner.releasee);
+                let fair = self.fair();
+                let released =
+                    self.eu(releasee.clone(), and(and(releaser, releasee.clone()), fair));
+                let globally_releasee = self.eg(releasee);
+                or(released, globally_releasee)
+            }
+            (true, TemporalOperator::R(inner)) => {
+                // A_f[p R q] = !E_f[!p U !q]
+                let releaser = self.apply(*inner.releaser);
+                let releasee = self.apply(*inner.releasee);
+                let fair = self.fair();
+                not(self.eu(not(releaser), and(not(releasee), fair)))
+            }
+        }
+    }
+
+    /// Holds in states from which a fair path starts, i.e. EG_f true.
+    fn fair(&mut self) -> Property {
+        self.eg(Property::Const(true))
+    }
+
+    /// Fair EG as gfp Z . p && EX(E[p U (Z && c_1)]) && ... && EX(E[p U (Z && c_n)]).
+    fn eg(&mut self, inner: Property) -> Property {
+        let variable = self.new_variable();
+        let mut result = inner.clone();
+        for constraint in self.constraints {
+            let reached = and(
+                Property::FixedVariable(variable.clone()),
+                constraint.clone(),
+            );
+            let until = self.eu(inner.clone(), reached);
+            result = and(result, ex(until));
+        }
+        Property::FixedPoint(FixedPointOperator {
+            is_greatest: true,
+            variable,
+            inner: Box::new(result),
+        })
+    }
+
+    /// Non-fair E[p U q] as lfp Y . q || (p && EX(Y)).
+    fn eu(&mut self, hold: Property, until: Property) -> Property {
+        let variable = self.new_variable();
+        let next = ex(Property::FixedVariable(variable.clone()));
+        Property::FixedPoint(FixedPointOperator {
+            is_greatest: false,
+            variable,
+            inner: Box::new(or(until, and(hold, next))),
+        })
+    }
+
+    fn new_variable(&mut self) -> String {
+        let variable = format!("__fair_{}", self.num_variables);
+        self.num_variables += 1;
+        variable
+    }
+}
+
+fn ex(inner: Property) -> Property {
+    Property::Ctl(CtlOperator {
+        is_universal: false,
+        temporal: TemporalOperator::X(Box::new(inner)),
+    })
+}
+
+fn not(inner: Property) -> Property {
+    Property::Negation(Box::new(inner))
+}
+
+fn and(a: Property, b: Property) -> Property {
+    Property::BiLogic(BiLogicOperator {
+        is_and: true,
+        a: Box::new(a),
+        b: Box::new(b),
+    })
+}
+
+fn or(a: Property, b: Property) -> Property {
+    Property::BiLogic(BiLogicOperator {
+        is_and: false,
+        a: Box::new(a),
+        b: Box::new(b),
+    })
+}
@@ -0,0 +1,279 @@
+//! Property files with named properties and reusable definitions.
+//!
+//! A property file consists of statements terminated by semicolons. The definition
+//! `let name = <property>;` can be referenced by its name in the following statements,
+//! the named property `property name: <property>;` is to be verified. Comments start
+//! with `//` and continue to the end of the line.
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{original::Property, parse_with_definitions};
+use crate::{
+    property::{PropertyDiagnostic, PropertyDiagnostics, PropertySpan, SystemNames},
+    ExecError,
+};
+
+/// Parses the property file to the named properties, in the order of appearance.
+///
+/// The definitions are resolved in the returned properties. The spans of the errors
+/// are the character positions in the whole file.
+pub fn parse(
+    input: &str,
+    names: Option<&SystemNames>,
+) -> Result<Vec<(String, Property)>, ExecError> {
+    let not_parseable = |errors: Vec<PropertyDiagnostic>| {
+        ExecError::PropertyFileNotParseable(PropertyDiagnostics {
+            input: String::from(input),
+            errors,
+        })
+    };
+    let mut definitions = BTreeMap::new();
+    let mut property_names = BTreeSet::new();
+    let mut result = Vec::new();
+
+    for statement in statements(input).map_err(|error| not_parseable(vec![error]))? {
+        let (keyword, rest) = statement.split_once_whitespace();
+        let is_definition = match keyword.text {
+            "let" => true,
+            "property" => false,
+            _ => {
+                return Err(not_parseable(vec![PropertyDiagnostic::new(
+                    Some(keyword.span()),
+                    format!(
+                        "Expected 'let' or 'property' at the start of a statement, have '{}'",
+                        keyword.text
+                    ),
+                )]))
+            }
+        };
+        let separator = if is_definition { '=' } else { ':' };
+        let (name, body) =
+            split_statement(rest, separator).map_err(|error| not_parseable(vec![error]))?;
+
+        let property = parse_with_definitions(body.text, definitions.clone(), names)
+            .map_err(|err| not_parseable(body.errors(err)))?;
+
+        let is_new = if is_definition {
+            definitions
+                .insert(String::from(name.text), property)
+                .is_none()
+        } else {
+            result.push((String::from(name.text), property));
+            property_names.insert(String::from(name.text))
+        };
+        if !is_new {
+            return Err(not_parseable(vec![PropertyDiagnostic::new(
+                Some(name.span()),
+                format!(
+                    "The name '{}' is already used by another {}",
+                    name.text, keyword.text
+                ),
+            )]));
+        }
+    }
+
+    if result.is_empty() {
+        return Err(not_parseable(vec![PropertyDiagnostic::new(
+            None,
+            "There are no named properties to verify",
+        )]));
+    }
+    Ok(result)
+}
+
+/// A part of the property file, starting at the given character position.
+#[derive(Clone, Copy)]
+struct Part<'a> {
+    start: usize,
+    text: &'a str,
+}
+
+impl<'a> Part<'a> {
+    /// Returns the span of the text without the surrounding whitespace.
+    ///
+    /// An empty text points just after its start.
+    fn span(&self) -> PropertySpan {
+        let trimmed_start = self.text.len() - self.text.trim_start().len();
+        let start = self.start + self.text[..trimmed_start].chars().count();
+        let len = self.text.trim().chars().count();
+        PropertySpan {
+            start,
+            end: start + len.max(1) - 1,
+        }
+    }
+
+    /// Returns the part after the given byte index.
+    fn after(&self, index: usize) -> Part<'a> {
+        Part {
+            start: self.start + self.text[..index].chars().count(),
+            text: &self.text[index..],
+        }
+    }
+
+    /// Splits the part at the first whitespace character, which is not a part of either.
+    fn split_once_whitespace(&self) -> (Part<'a>, Part<'a>) {
+        match self.text.find(char::is_whitespace) {
+            Some(index) => {
+                let whitespace_len = self.text[index..].chars().next().map_or(0, char::len_utf8);
+                (
+                    Part {
+                        start: self.start,
+                        text: &self.text[..index],
+                    },
+                    self.after(index + whitespace_len),
+                )
+            }
+            None => (*self, self.after(self.text.len())),
+        }
+    }
+
+    /// Returns the errors of parsing the part as a property, moved to the positions in the file.
+    fn errors(&self, err: ExecError) -> Vec<PropertyDiagnostic> {
+        match err {
+            ExecError::PropertyNotParseable(diagnostics) => diagnostics
+                .errors
+                .into_iter()
+                .map(|mut error| {
+                    error.span = Some(match error.span {
+                        Some(span) => PropertySpan {
+                            start: self.start + span.start,
+                            end: self.start + span.end,
+                        },
+                        None => self.span(),
+                    });
+                    error
+                })
+                .collect(),
+            err => vec![PropertyDiagnostic::new(Some(self.span()), err.to_string())],
+        }
+    }
+}
+
+/// A statement of the property file, without the terminating semicolon.
+///
+/// The comments are replaced by spaces, so that the characters stay at their positions.
+struct Statement {
+    start: usize,
+    text: String,
+}
+
+impl Statement {
+    fn split_once_whitespace(&self) -> (Part<'_>, Part<'_>) {
+        Part {
+            start: self.start,
+            text: &self.text,
+        }
+        .split_once_whitespace()
+    }
+}
+
+/// Splits the input to the statements terminated by semicolons, without the comments.
+///
+/// The semicolons and comment starts inside strings, e.g. panic messages, are ignored.
+/// Each statement starts with its first non-whitespace character.
+fn statements(input: &str) -> Result<Vec<Statement>, PropertyDiagnostic> {
+    let mut result = Vec::new();
+    let mut statement: Option<Statement> = None;
+    let mut in_string = false;
+    let mut in_comment = false;
+    let mut chars = input.chars().enumerate().peekable();
+    while let Some((position, c)) = chars.next() {
+        if c == '\n' {
+            in_string = false;
+            in_comment = false;
+        }
+        let c = match c {
+            _ if in_comment => ' ',
+            '\\' if in_string => {
+                // keep the escaped character
+                if let Some(statement) = &mut statement {
+                    statement.text.push(c);
+                }
+                match chars.next() {
+                    Some((_, escaped)) => escaped,
+                    None => break,
+                }
+            }
+            '"' => {
+                in_string = !in_string;
+                c
+            }
+            ';' if !in_string => {
+                let Some(statement) = statement.take() else {
+                    return Err(PropertyDiagnostic::new(
+                        Some(PropertySpan {
+                            start: position,
+                            end: position,
+                        }),
+                        "Empty statement",
+                    ));
+                };
+                result.push(statement);
+                continue;
+            }
+            '/' if !in_string && chars.peek().is_some_and(|(_, next)| *next == '/') => {
+                in_comment = true;
+                ' '
+            }
+            _ => c,
+        };
+        match &mut statement {
+            Some(statement) => statement.text.push(c),
+            None if !c.is_whitespace() => {
+                statement = Some(Statement {
+                    start: position,
+                    text: String::from(c),
+                })
+            }
+            None => {}
+        }
+    }
+
+    if let Some(statement) = statement {
+        // point just after the statement
+        let end = statement.start + statement.text.trim_end().chars().count();
+        return Err(PropertyDiagnostic::new(
+            Some(PropertySpan { start: end, end }),
+            "Expected a semicolon after the statement",
+        ));
+    }
+    Ok(result)
+}
+
+/// Splits the statement after the keyword to the name and the property.
+fn split_statement(
+    rest: Part<'_>,
+    separator: char,
+) -> Result<(Part<'_>, Part<'_>), PropertyDiagnostic> {
+    let Some(index) = rest.text.find(separator) else {
+        return Err(PropertyDiagnostic::new(
+            Some(rest.span()),
+            format!("Expected '{}' after the name", separator),
+        ));
+    };
+    let name = Part {
+        start: rest.start,
+        text: &rest.text[..index],
+    };
+    let body = rest.after(index + separator.len_utf8());
+    let mut chars = name.text.trim().chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(PropertyDiagnostic::new(
+            Some(name.span()),
+            format!(
+                "Expected an identifier as the name, have '{}'",
+                name.text.trim()
+            ),
+        ));
+    }
+    Ok((
+        Part {
+            start: name.span().start,
+            text: name.text.trim(),
+        },
+        body,
+    ))
+}
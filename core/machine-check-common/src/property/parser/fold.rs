@@ -40,6 +40,26 @@ impl Folder {
     fn fold_inner(&mut self, original: original::Property) -> Result<usize, ExecError> {
         let display_string = original.to_string();
 
+        if let original::Property::BoundedCtl(op) = original {
+            // expand the first step, the expansion takes the place of the bounded operator
+            let property_index = self.fold_inner(op.expand())?;
+            let entry = self.arena[property_index]
+                .as_mut()
+                .expect("Expanded subproperty in arena should be filled");
+            entry.display_string = Some(display_string);
+            return Ok(property_index);
+        }
+
+        if let original::Property::Definition(definition) = original {
+            // the definition is displayed by its name
+            let property_index = self.fold_inner(*definition.inner)?;
+            let entry = self.arena[property_index]
+                .as_mut()
+                .expect("Defined subproperty in arena should be filled");
+            entry.display_string = Some(definition.name);
+            return Ok(property_index);
+        }
+
         let property_index = self.arena.len();
         self.arena.push(None);
 
@@ -47,19 +67,7 @@ impl Folder {
             original::Property::Const(value) => folded::PropertyType::Const(value),
             original::Property::Atomic(atomic) => folded::PropertyType::Atomic(atomic),
             original::Property::Negation(inner) => {
-                // negate the fixed-point negations
-                for negation in self.fixed_point_negations.values_mut() {
-                    *negation = !*negation;
-                }
-
-                let ty = folded::PropertyType::Negation(self.fold_inner(*inner)?);
-
-                // negate afterward to counteract
-                for negation in self.fixed_point_negations.values_mut() {
-                    *negation = !*negation;
-                }
-
-                ty
+                folded::PropertyType::Negation(self.fold_negated(*inner)?)
             }
             original::Property::BiLogic(op) => {
                 folded::PropertyType::BiLogic(folded::BiLogicOperator {
@@ -69,6 +77,7 @@ impl Folder {
                     reverse_display: false,
                 })
             }
+            original::Property::DerivedLogic(op) => self.derived_logic(op)?,
             original::Property::Ctl(op) => match op.temporal {
                 original::TemporalOperator::X(inner) => {
                     folded::PropertyType::Next(folded::NextOperator {
@@ -109,6 +118,12 @@ impl Folder {
                     true,
                 )?,
             },
+            original::Property::BoundedCtl(_) => {
+                unreachable!("Bounded operator should be expanded")
+            }
+            original::Property::Definition(_) => {
+                unreachable!("Definition should be folded as its inner property")
+            }
             original::Property::FixedPoint(fixed_point) => {
                 self.variable_indices
                     .push((fixed_point.variable.clone(), property_index));
@@ -160,6 +175,91 @@ impl Folder {
         Ok(property_index)
     }
 
+    /// Folds the inner property of a negation.
+    fn fold_negated(&mut self, inner: original::Property) -> Result<usize, ExecError> {
+        // negate the fixed-point negations
+        for negation in self.fixed_point_negations.values_mut() {
+            *negation = !*negation;
+        }
+
+        let result = self.fold_inner(inner);
+
+        // negate afterward to counteract
+        for negation in self.fixed_point_negations.values_mut() {
+            *negation = !*negation;
+        }
+
+        result
+    }
+
+    fn derived_logic(
+        &mut self,
+        op: original::DerivedLogicOperator,
+    ) -> Result<folded::PropertyType, ExecError> {
+        // lower to a disjunction with the inner conjunctions and negations not displayed,
+        // so that only the operator as written and its operands are shown; the operands
+        // of the equivalence and exclusive or are used both negated and not negated,
+        // so a fixed-point variable within them makes the property non-monotone
+        let (a, b) = match op.ty {
+            original::DerivedLogicType::Implication => {
+                // a -> b is !a || b
+                let not_a = self.fold_polarity(*op.a, false)?;
+                (not_a, self.fold_inner(*op.b)?)
+            }
+            original::DerivedLogicType::Equivalence => {
+                // a <-> b is (a && b) || (!a && !b)
+                let both = self.conjunction(&op, true, true)?;
+                (both, self.conjunction(&op, false, false)?)
+            }
+            original::DerivedLogicType::ExclusiveOr => {
+                // a ^ b is (a && !b) || (!a && b)
+                let only_a = self.conjunction(&op, true, false)?;
+                (only_a, self.conjunction(&op, false, true)?)
+            }
+        };
+        Ok(folded::PropertyType::BiLogic(BiLogicOperator {
+            is_and: false,
+            a,
+            b,
+            reverse_display: false,
+        }))
+    }
+
+    /// Folds the conjunction of the operands with the given polarities.
+    fn conjunction(
+        &mut self,
+        op: &original::DerivedLogicOperator,
+        a_polarity: bool,
+        b_polarity: bool,
+    ) -> Result<usize, ExecError> {
+        let a = self.fold_polarity((*op.a).clone(), a_polarity)?;
+        let b = self.fold_polarity((*op.b).clone(), b_polarity)?;
+        Ok(self.arena_push(
+            folded::PropertyType::BiLogic(BiLogicOperator {
+                is_and: true,
+                a,
+                b,
+                reverse_display: false,
+            }),
+            true,
+        ))
+    }
+
+    /// Folds the property, negating it if the polarity is false.
+    ///
+    /// The negation is not displayed.
+    fn fold_polarity(
+        &mut self,
+        property: original::Property,
+        polarity: bool,
+    ) -> Result<usize, ExecError> {
+        if polarity {
+            return self.fold_inner(property);
+        }
+        let inner = self.fold_negated(property)?;
+        Ok(self.arena_push(folded::PropertyType::Negation(inner), true))
+    }
+
     fn fixed_point(
         &mut self,
         property_index: usize,
@@ -1,6 +1,6 @@
-use std::{collections::VecDeque, iter::Peekable};
+use std::{collections::VecDeque, fmt::Display, iter::Peekable};
 
-use crate::{property::ComparisonType, ExecError};
+use crate::property::{ComparisonType, PropertyDiagnostic, PropertySpan};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Bracket {
@@ -12,35 +12,44 @@ pub enum Bracket {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TokenType {
     Comma,
+    Colon,
+    Dot,
     ExclamationMark,
     LogicAnd,
     LogicOr,
+    LogicImplication,
+    LogicEquivalence,
+    Plus,
+    Minus,
+    Star,
+    BitAnd,
+    BitOr,
+    BitXor,
     OpeningBracket(Bracket),
     ClosingBracket(Bracket),
     Ident(String),
     Number(u64),
+    Str(String),
     Comparison(ComparisonType),
     MacroInvocation(String),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct TokenSpan {
-    pub start: usize,
-    pub end: usize,
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Token {
     pub ty: TokenType,
-    pub span: TokenSpan,
+    pub span: PropertySpan,
 }
 
 /// Lexes a property string to a sequence of tokens.
-pub fn lex(input: &str) -> Result<VecDeque<Token>, ExecError> {
+///
+/// The lexing continues after an error, skipping the offending characters
+/// or substituting the likely intended token, so that the errors are returned
+/// together with the tokens.
+pub fn lex(input: &str) -> (VecDeque<Token>, Vec<PropertyDiagnostic>) {
     fn add_token(tokens: &mut VecDeque<Token>, start: usize, end: usize, ty: TokenType) {
         tokens.push_back(Token {
             ty,
-            span: TokenSpan { start, end },
+            span: PropertySpan { start, end },
         });
     }
 
@@ -62,28 +71,8 @@ pub fn lex(input: &str) -> Result<VecDeque<Token>, ExecError> {
         add_token(tokens, start, start, else_ty);
     }
 
-    fn add_token_with_require(
-        input: &str,
-        tokens: &mut VecDeque<Token>,
-        it: &mut Peekable<impl Iterator<Item = (usize, char)>>,
-        start: usize,
-        require_char: char,
-        then_ty: TokenType,
-        else_msg: &str,
-    ) -> Result<(), ExecError> {
-        if let Some((end, c)) = it.next() {
-            if c == require_char {
-                add_token(tokens, start, end, then_ty);
-                return Ok(());
-            }
-        }
-        Err(ExecError::PropertyNotLexable(
-            String::from(input),
-            format!("In position {}, {}", start, else_msg),
-        ))
-    }
-
     let mut result = VecDeque::new();
+    let mut errors = Vec::new();
 
     let mut it = input.chars().enumerate().peekable();
     while let Some((start, c)) = it.next() {
@@ -92,12 +81,17 @@ pub fn lex(input: &str) -> Result<VecDeque<Token>, ExecError> {
         }
         let simple_token = match c {
             ',' => Some(TokenType::Comma),
+            ':' => Some(TokenType::Colon),
+            '.' => Some(TokenType::Dot),
             '(' => Some(TokenType::OpeningBracket(Bracket::Parenthesis)),
             '[' => Some(TokenType::OpeningBracket(Bracket::Square)),
             '{' => Some(TokenType::OpeningBracket(Bracket::Curly)),
             ')' => Some(TokenType::ClosingBracket(Bracket::Parenthesis)),
             ']' => Some(TokenType::ClosingBracket(Bracket::Square)),
             '}' => Some(TokenType::ClosingBracket(Bracket::Curly)),
+            '+' => Some(TokenType::Plus),
+            '*' => Some(TokenType::Star),
+            '^' => Some(TokenType::BitXor),
             _ => None,
         };
         if let Some(simple_token) = simple_token {
@@ -106,15 +100,32 @@ pub fn lex(input: &str) -> Result<VecDeque<Token>, ExecError> {
         }
 
         match c {
-            '=' => add_token_with_require(
-                input,
-                &mut result,
-                &mut it,
-                start,
-                '=',
-                TokenType::Comparison(ComparisonType::Eq),
-                "a single '=' cannot be used",
-            )?,
+            '=' => {
+                if let Some((end, '=')) = it.peek().copied() {
+                    it.next();
+                    add_token(
+                        &mut result,
+                        start,
+                        end,
+                        TokenType::Comparison(ComparisonType::Eq),
+                    );
+                } else {
+                    // continue as if the equality comparison was intended
+                    errors.push(
+                        PropertyDiagnostic::new(
+                            Some(PropertySpan { start, end: start }),
+                            "A single '=' cannot be used",
+                        )
+                        .with_help("use '==' to compare for equality"),
+                    );
+                    add_token(
+                        &mut result,
+                        start,
+                        start,
+                        TokenType::Comparison(ComparisonType::Eq),
+                    );
+                }
+            }
             '!' => add_token_with_peek(
                 &mut result,
                 &mut it,
@@ -123,40 +134,55 @@ pub fn lex(input: &str) -> Result<VecDeque<Token>, ExecError> {
                 TokenType::Comparison(ComparisonType::Ne),
                 TokenType::ExclamationMark,
             ),
-            '<' => add_token_with_peek(
+            '<' => {
+                // look two characters ahead as a less-than can be followed by a minus sign
+                let mut lookahead = it.clone();
+                if let (Some((_, '-')), Some((end, '>'))) = (lookahead.next(), lookahead.next()) {
+                    it = lookahead;
+                    add_token(&mut result, start, end, TokenType::LogicEquivalence);
+                } else {
+                    add_token_with_peek(
+                        &mut result,
+                        &mut it,
+                        start,
+                        '=',
+                        TokenType::Comparison(ComparisonType::Le),
+                        TokenType::Comparison(ComparisonType::Lt),
+                    )
+                }
+            }
+            '>' => add_token_with_peek(
                 &mut result,
                 &mut it,
                 start,
                 '=',
-                TokenType::Comparison(ComparisonType::Le),
-                TokenType::Comparison(ComparisonType::Lt),
+                TokenType::Comparison(ComparisonType::Ge),
+                TokenType::Comparison(ComparisonType::Gt),
             ),
-            '>' => add_token_with_peek(
+            '-' => add_token_with_peek(
                 &mut result,
                 &mut it,
                 start,
-                '=',
-                TokenType::Comparison(ComparisonType::Ge),
-                TokenType::Comparison(ComparisonType::Gt),
+                '>',
+                TokenType::LogicImplication,
+                TokenType::Minus,
             ),
-            '&' => add_token_with_require(
-                input,
+            '&' => add_token_with_peek(
                 &mut result,
                 &mut it,
                 start,
                 '&',
                 TokenType::LogicAnd,
-                "a single '&' cannot be used",
-            )?,
-            '|' => add_token_with_require(
-                input,
+                TokenType::BitAnd,
+            ),
+            '|' => add_token_with_peek(
                 &mut result,
                 &mut it,
                 start,
                 '|',
                 TokenType::LogicOr,
-                "a single '|' cannot be used",
-            )?,
+                TokenType::BitOr,
+            ),
             'A'..='Z' | 'a'..='z' | '_' => {
                 let mut ident = String::from(c);
                 let mut end_index = start;
@@ -181,7 +207,8 @@ pub fn lex(input: &str) -> Result<VecDeque<Token>, ExecError> {
 
                 add_token(&mut result, start, end_index, token_type);
             }
-            '0'..='9' | '-' | '+' => {
+            '0'..='9' => {
+                // the sign is lexed separately and applied by the parser
                 let mut str_val = String::new();
                 let hexadecimal = if let Some((_, 'x')) = it.peek() {
                     it.next();
@@ -211,39 +238,107 @@ pub fn lex(input: &str) -> Result<VecDeque<Token>, ExecError> {
                     end_index = index;
                 }
 
-                let unsigned_val: Result<u64, _> =
+                let val: Result<u64, _> =
                     u64::from_str_radix(&str_val, if hexadecimal { 16 } else { 10 });
-                let val = if let Ok(unsigned_val) = unsigned_val {
-                    Some(unsigned_val)
-                } else if !hexadecimal {
-                    let signed_val: Result<i64, _> = str_val.parse();
-                    if let Ok(signed_val) = signed_val {
-                        Some(signed_val as u64)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
 
-                let Some(val) = val else {
-                    return Err(ExecError::PropertyNotLexable(
-                        String::from(input),
-                        format!(
-                            "Unparsable number at {}..={}: '{}'",
-                            start, end_index, str_val
-                        ),
+                let val = val.unwrap_or_else(|_| {
+                    // continue with a placeholder number
+                    errors.push(PropertyDiagnostic::new(
+                        Some(PropertySpan {
+                            start,
+                            end: end_index,
+                        }),
+                        format!("Unparsable number '{}'", str_val),
                     ));
-                };
+                    0
+                });
                 add_token(&mut result, start, end_index, TokenType::Number(val));
             }
+            '"' => {
+                // backslash escapes a quote or another backslash
+                let mut str_val = String::new();
+                let mut end_index = None;
+                while let Some((index, c)) = it.next() {
+                    match c {
+                        '"' => {
+                            end_index = Some(index);
+                            break;
+                        }
+                        '\\' => match it.next() {
+                            Some((_, escaped @ ('"' | '\\'))) => str_val.push(escaped),
+                            Some((index, escaped)) => {
+                                errors.push(PropertyDiagnostic::new(
+                                    Some(PropertySpan {
+                                        start: index - 1,
+                                        end: index,
+                                    }),
+                                    format!("Unknown escape sequence '\\{}'", escaped),
+                                ));
+                            }
+                            None => {}
+                        },
+                        _ => str_val.push(c),
+                    }
+                }
+                let end_index = end_index.unwrap_or_else(|| {
+                    // continue as if the string was terminated at the end of input
+                    let end_index = input.chars().count() - 1;
+                    errors.push(PropertyDiagnostic::new(
+                        Some(PropertySpan {
+                            start,
+                            end: end_index,
+                        }),
+                        "Unterminated string",
+                    ));
+                    end_index
+                });
+                add_token(&mut result, start, end_index, TokenType::Str(str_val));
+            }
             _ => {
-                return Err(ExecError::PropertyNotLexable(
-                    String::from(input),
-                    format!("Unknown character in position {}: '{}'", start, c),
-                ))
+                // skip the character
+                errors.push(PropertyDiagnostic::new(
+                    Some(PropertySpan { start, end: start }),
+                    format!("Unknown character '{}'", c),
+                ));
             }
         }
     }
-    Ok(result)
+    (result, errors)
+}
+
+impl Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            TokenType::Comma => ",",
+            TokenType::Colon => ":",
+            TokenType::Dot => ".",
+            TokenType::ExclamationMark => "!",
+            TokenType::LogicAnd => "&&",
+            TokenType::LogicOr => "||",
+            TokenType::LogicImplication => "->",
+            TokenType::LogicEquivalence => "<->",
+            TokenType::Plus => "+",
+            TokenType::Minus => "-",
+            TokenType::Star => "*",
+            TokenType::BitAnd => "&",
+            TokenType::BitOr => "|",
+            TokenType::BitXor => "^",
+            TokenType::OpeningBracket(bracket) => match bracket {
+                Bracket::Parenthesis => "(",
+                Bracket::Square => "[",
+                Bracket::Curly => "{",
+            },
+            TokenType::ClosingBracket(bracket) => match bracket {
+                Bracket::Parenthesis => ")",
+                Bracket::Square => "]",
+                Bracket::Curly => "}",
+            },
+            TokenType::Ident(ident) => return write!(f, "'{}'", ident),
+            TokenType::Number(number) => return write!(f, "'{}'", number),
+            TokenType::Str(str) => return write!(f, "{:?}", str),
+            TokenType::Comparison(comparison_type) => return write!(f, "'{}'", comparison_type),
+            TokenType::MacroInvocation(ident) => return write!(f, "'{}!'", ident),
+        };
+        write!(f, "'{}'", str)
+    }
 }
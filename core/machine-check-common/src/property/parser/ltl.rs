@@ -0,0 +1,728 @@
+//! Linear Temporal Logic front-end.
+//!
+//! An LTL property holds if all paths from the initial states satisfy it.
+//! It is translated to mu-calculus by constructing a generalized Büchi automaton
+//! for the negated property via the tableau construction, degeneralizing it,
+//! and expressing the existence of an accepting run of the automaton along
+//! some path of the system as a hierarchical system of fixed-point equations.
+//! The equation system is then solved symbolically by Gaussian elimination.
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+
+use crate::{
+    property::{
+        parser::{
+            lexer::{Bracket, Token, TokenType},
+            original::{self, CtlOperator, FixedPointOperator, TemporalOperator},
+            PropertyParser,
+        },
+        AtomicProperty, ComparisonType, PropertyDiagnostic, PropertyDiagnostics, ValueExpression,
+    },
+    ExecError,
+};
+
+/// Maximum number of subproperties of the translated property.
+///
+/// The translation is exponential in the worst case, so it is bounded to fail gracefully.
+const MAX_TRANSLATED_SIZE: usize = 1 << 12;
+
+/// A Linear Temporal Logic property as written by the user.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum LtlProperty {
+    Atomic(AtomicProperty),
+    Negation(Box<LtlProperty>),
+    BiLogic {
+        is_and: bool,
+        a: Box<LtlProperty>,
+        b: Box<LtlProperty>,
+    },
+    X(Box<LtlProperty>),
+    F(Box<LtlProperty>),
+    G(Box<LtlProperty>),
+    U {
+        hold: Box<LtlProperty>,
+        until: Box<LtlProperty>,
+    },
+    R {
+        releaser: Box<LtlProperty>,
+        releasee: Box<LtlProperty>,
+    },
+}
+
+/// The names of the LTL operators, used as macros.
+const LTL_OPERATORS: &[&str] = &["X", "F", "G", "U", "R"];
+
+impl PropertyParser {
+    pub(super) fn parse_ltl(mut self) -> Result<LtlProperty, ExecError> {
+        let result = self.parse_ltl_property()?;
+        self.expect_end()?;
+        Ok(result)
+    }
+
+    fn parse_ltl_property(&mut self) -> Result<LtlProperty, ExecError> {
+        let mut expr = self.parse_ltl_operand()?;
+
+        let (token_type, is_and) = match self.peek_type() {
+            Some(TokenType::LogicAnd) => (TokenType::LogicAnd, true),
+            Some(TokenType::LogicOr) => (TokenType::LogicOr, false),
+            _ => return Ok(expr),
+        };
+
+        // do not mix conjunctions and disjunctions without parentheses
+        while self.peek_type() == Some(&token_type) {
+            self.lex_items.pop_front();
+            expr = LtlProperty::BiLogic {
+                is_and,
+                a: Box::new(expr),
+                b: Box::new(self.parse_ltl_operand()?),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn parse_ltl_operand(&mut self) -> Result<LtlProperty, ExecError> {
+        // the placeholder is never used as the parsing fails
+        let placeholder = LtlProperty::Atomic(AtomicProperty::new(
+            ValueExpression::Constant(0),
+            ComparisonType::Eq,
+            ValueExpression::Constant(0),
+        ));
+        self.parse_operand(Self::parse_ltl_property_expr, placeholder)
+    }
+
+    fn parse_ltl_property_expr(&mut self) -> Result<LtlProperty, ExecError> {
+        let first_token = self.lex_items.pop_front();
+        Ok(match first_token {
+            Some(
+                token @ Token {
+                    ty: TokenType::Ident(_) | TokenType::Number(_) | TokenType::Minus,
+                    ..
+                },
+            ) => LtlProperty::Atomic(self.parse_atomic_or_operator(token, LTL_OPERATORS)?),
+            Some(Token {
+                ty: TokenType::MacroInvocation(ref ident),
+                ..
+            }) => match ident.as_str() {
+                "X" => LtlProperty::X(Box::new(self.parse_ltl_uni_operator()?)),
+                "F" => LtlProperty::F(Box::new(self.parse_ltl_uni_operator()?)),
+                "G" => LtlProperty::G(Box::new(self.parse_ltl_uni_operator()?)),
+                "U" => {
+                    let (hold, until) = self.parse_ltl_bi_operator()?;
+                    LtlProperty::U { hold, until }
+                }
+                "R" => {
+                    let (releaser, releasee) = self.parse_ltl_bi_operator()?;
+                    LtlProperty::R { releaser, releasee }
+                }
+                _ => {
+                    return Err(self.not_parseable(
+                        first_token,
+                        "Unexpected macro invocation when parsing an LTL property",
+                    ))
+                }
+            },
+            Some(Token {
+                ty: TokenType::ExclamationMark,
+                ..
+            }) => {
+                // negate the property, require parentheses
+                self.expect(
+                    TokenType::OpeningBracket(Bracket::Parenthesis),
+                    "inside a negation",
+                )?;
+                let result = LtlProperty::Negation(Box::new(self.parse_ltl_property()?));
+                self.expect(
+                    TokenType::ClosingBracket(Bracket::Parenthesis),
+                    "inside a negation",
+                )?;
+                result
+            }
+            Some(
+                token @ Token {
+                    ty: TokenType::OpeningBracket(Bracket::Parenthesis),
+                    ..
+                },
+            ) => self.parse_parenthesized(token, Self::parse_ltl_property, LtlProperty::Atomic)?,
+            token => {
+                return Err(self.not_parseable(
+                    token,
+                    "Expected an identifier or a macro invocation when parsing an LTL property",
+                ))
+            }
+        })
+    }
+
+    fn parse_ltl_uni_operator(&mut self) -> Result<LtlProperty, ExecError> {
+        self.expect(
+            TokenType::OpeningBracket(Bracket::Square),
+            "a unary operator",
+        )?;
+        let result = self.parse_ltl_property()?;
+        self.expect(
+            TokenType::ClosingBracket(Bracket::Square),
+            "a unary operator",
+        )?;
+        Ok(result)
+    }
+
+    fn parse_ltl_bi_operator(&mut self) -> Result<(Box<LtlProperty>, Box<LtlProperty>), ExecError> {
+        const WHEN_PARSING: &str = "a binary operator";
+
+        self.expect(TokenType::OpeningBracket(Bracket::Square), WHEN_PARSING)?;
+        let a = Box::new(self.parse_ltl_property()?);
+        self.expect(TokenType::Comma, WHEN_PARSING)?;
+        let b = Box::new(self.parse_ltl_property()?);
+        self.expect(TokenType::ClosingBracket(Bracket::Square), WHEN_PARSING)?;
+        Ok((a, b))
+    }
+}
+
+impl Display for LtlProperty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LtlProperty::Atomic(atomic) => write!(f, "{}", atomic),
+            LtlProperty::Negation(inner) => write!(f, "!({})", inner),
+            LtlProperty::BiLogic { is_and, a, b } => {
+                let op_str = if *is_and { "&&" } else { "||" };
+                // make sure the inner and / or properties are in parentheses so the display is unambiguous
+                let write_inner = |f: &mut std::fmt::Formatter<'_>, prop: &LtlProperty| {
+                    if matches!(prop, LtlProperty::BiLogic { .. }) {
+                        write!(f, "({})", prop)
+                    } else {
+                        write!(f, "{}", prop)
+                    }
+                };
+                write_inner(f, a)?;
+                write!(f, " {} ", op_str)?;
+                write_inner(f, b)
+            }
+            LtlProperty::X(inner) => write!(f, "X![{}]", inner),
+            LtlProperty::F(inner) => write!(f, "F![{}]", inner),
+            LtlProperty::G(inner) => write!(f, "G![{}]", inner),
+            LtlProperty::U { hold, until } => write!(f, "U![{}, {}]", hold, until),
+            LtlProperty::R { releaser, releasee } => write!(f, "R![{}, {}]", releaser, releasee),
+        }
+    }
+}
+
+/// Translates the LTL property to an equivalent mu-calculus property.
+///
+/// Only the paths on which each of the fairness constraints holds infinitely often
+/// are considered. The constraints are added as further acceptance sets of the automaton.
+pub fn translate(
+    input: &str,
+    ltl: &LtlProperty,
+    fairness: &[original::Property],
+) -> Result<original::Property, ExecError> {
+    // the property holds iff there is no path satisfying its negation
+    let mut formulas = NnfFormulas::default();
+    let negated = formulas.normalize(ltl, true);
+
+    let automaton = Automaton::construct(&formulas, negated);
+    let Some(accepting_path_exists) = automaton.accepting_path_property(&formulas, fairness) else {
+        return Err(ExecError::PropertyNotParseable(PropertyDiagnostics {
+            input: String::from(input),
+            errors: vec![PropertyDiagnostic::new(
+                None,
+                "The LTL property is too complex to translate",
+            )],
+        }));
+    };
+
+    Ok(original::Property::Negation(Box::new(
+        accepting_path_exists,
+    )))
+}
+
+/// A formula in negation normal form, with subformulas referenced by index.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum NnfFormula {
+    Const(bool),
+    Literal {
+        atomic: AtomicProperty,
+        negated: bool,
+    },
+    And(usize, usize),
+    Or(usize, usize),
+    Next(usize),
+    Until(usize, usize),
+    Release(usize, usize),
+}
+
+/// Interned formulas in negation normal form.
+#[derive(Default)]
+struct NnfFormulas {
+    formulas: Vec<NnfFormula>,
+    indices: HashMap<NnfFormula, usize>,
+}
+
+impl NnfFormulas {
+    fn intern(&mut self, formula: NnfFormula) -> usize {
+        if let Some(index) = self.indices.get(&formula) {
+            return *index;
+        }
+        let index = self.formulas.len();
+        self.formulas.push(formula.clone());
+        self.indices.insert(formula, index);
+        index
+    }
+
+    /// Converts the property, possibly negated, to negation normal form.
+    fn normalize(&mut self, property: &LtlProperty, negate: bool) -> usize {
+        let formula = match property {
+            LtlProperty::Atomic(atomic) => NnfFormula::Literal {
+                atomic: atomic.clone(),
+                negated: negate,
+            },
+            LtlProperty::Negation(inner) => return self.normalize(inner, !negate),
+            LtlProperty::BiLogic { is_and, a, b } => {
+                let a = self.normalize(a, negate);
+                let b = self.normalize(b, negate);
+                // De Morgan's laws
+                if *is_and != negate {
+                    NnfFormula::And(a, b)
+                } else {
+                    NnfFormula::Or(a, b)
+                }
+            }
+            LtlProperty::X(inner) => NnfFormula::Next(self.normalize(inner, negate)),
+            LtlProperty::F(inner) => {
+                // F a = true U a, !F a = false R !a
+                let inner = self.normalize(inner, negate);
+                let constant = self.intern(NnfFormula::Const(!negate));
+                if negate {
+                    NnfFormula::Release(constant, inner)
+                } else {
+                    NnfFormula::Until(constant, inner)
+                }
+            }
+            LtlProperty::G(inner) => {
+                // G a = false R a, !G a = true U !a
+                let inner = self.normalize(inner, negate);
+                let constant = self.intern(NnfFormula::Const(negate));
+                if negate {
+                    NnfFormula::Until(constant, inner)
+                } else {
+                    NnfFormula::Release(constant, inner)
+                }
+            }
+            LtlProperty::U { hold, until } => {
+                // !(a U b) = !a R !b
+                let hold = self.normalize(hold, negate);
+                let until = self.normalize(until, negate);
+                if negate {
+                    NnfFormula::Release(hold, until)
+                } else {
+                    NnfFormula::Until(hold, until)
+                }
+            }
+            LtlProperty::R { releaser, releasee } => {
+                // !(a R b) = !a U !b
+                let releaser = self.normalize(releaser, negate);
+                let releasee = self.normalize(releasee, negate);
+                if negate {
+                    NnfFormula::Until(releaser, releasee)
+                } else {
+                    NnfFormula::Release(releaser, releasee)
+                }
+            }
+        };
+        self.intern(formula)
+    }
+}
+
+/// A node of the tableau, i.e. a state of the generalized Büchi automaton.
+#[derive(Clone, Default)]
+struct TableauNode {
+    /// Predecessor nodes, none stands for the initial pseudo-node.
+    incoming: BTreeSet<Option<usize>>,
+    new: BTreeSet<usize>,
+    old: BTreeSet<usize>,
+    next: BTreeSet<usize>,
+}
+
+/// A generalized Büchi automaton constructed by the tableau construction.
+struct Automaton {
+    nodes: Vec<TableauNode>,
+    /// For each acceptance set, the nodes that are within it.
+    acceptance_sets: Vec<BTreeSet<usize>>,
+}
+
+impl Automaton {
+    fn construct(formulas: &NnfFormulas, root: usize) -> Self {
+        let mut automaton = Automaton {
+            nodes: Vec::new(),
+            acceptance_sets: Vec::new(),
+        };
+        automaton.expand(
+            formulas,
+            TableauNode {
+                incoming: BTreeSet::from([None]),
+                new: BTreeSet::from([root]),
+                ..Default::default()
+            },
+        );
+
+        // each until formula a U b must be eventually fulfilled, i.e. b must hold
+        for (index, formula) in formulas.formulas.iter().enumerate() {
+            if let NnfFormula::Until(_, until) = formula {
+                let acceptance_set = (0..automaton.nodes.len())
+                    .filter(|node_index| {
+                        let old = &automaton.nodes[*node_index].old;
+                        !old.contains(&index) || old.contains(until)
+                    })
+                    .collect();
+                automaton.acceptance_sets.push(acceptance_set);
+            }
+        }
+
+        automaton
+    }
+
+    fn expand(&mut self, formulas: &NnfFormulas, mut node: TableauNode) {
+        loop {
+            let Some(formula_index) = node.new.pop_first() else {
+                // the node is fully expanded, look if there is an equivalent node already
+                if let Some(existing) = self
+                    .nodes
+                    .iter_mut()
+                    .find(|existing| existing.old == node.old && existing.next == node.next)
+                {
+                    existing.incoming.extend(node.incoming);
+                    return;
+                }
+                let node_index = self.nodes.len();
+                let successor = TableauNode {
+                    incoming: BTreeSet::from([Some(node_index)]),
+                    new: node.next.clone(),
+                    ..Default::default()
+                };
+                self.nodes.push(node);
+                self.expand(formulas, successor);
+                return;
+            };
+
+            if node.old.contains(&formula_index) {
+                continue;
+            }
+
+            match &formulas.formulas[formula_index] {
+                NnfFormula::Const(value) => {
+                    if !value {
+                        // contradiction, discard the node
+                        return;
+                    }
+                }
+                NnfFormula::Literal { atomic, negated } => {
+                    let opposite = NnfFormula::Literal {
+                        atomic: atomic.clone(),
+                        negated: !negated,
+                    };
+                    if let Some(opposite_index) = formulas.indices.get(&opposite) {
+                        if node.old.contains(opposite_index) {
+                            // contradiction, discard the node
+                            return;
+                        }
+                    }
+                }
+                NnfFormula::And(a, b) => {
+                    node.new
+                        .extend([*a, *b].into_iter().filter(|f| !node.old.contains(f)));
+                }
+                NnfFormula::Next(inner) => {
+                    node.next.insert(*inner);
+                }
+                NnfFormula::Or(a, b) | NnfFormula::Until(a, b) | NnfFormula::Release(a, b) => {
+                    // split the node
+                    let (first_new, first_next, second_new) = match &formulas.formulas
+                        [formula_index]
+                    {
+                        NnfFormula::Or(..) => (vec![*a], None, vec![*b]),
+                        // a U b = b || (a && X(a U b))
+                        NnfFormula::Until(..) => (vec![*a], Some(formula_index), vec![*b]),
+                        // a R b = (a && b) || (b && X(a R b))
+                        NnfFormula::Release(..) => (vec![*b], Some(formula_index), vec![*a, *b]),
+                        _ => unreachable!(),
+                    };
+
+                    let mut first = node.clone();
+                    first.old.insert(formula_index);
+                    first
+                        .new
+                        .extend(first_new.into_iter().filter(|f| !node.old.contains(f)));
+                    first.next.extend(first_next);
+                    self.expand(formulas, first);
+
+                    node.new
+                        .extend(second_new.into_iter().filter(|f| !node.old.contains(f)));
+                }
+            }
+            node.old.insert(formula_index);
+        }
+    }
+
+    /// Constructs the property that holds in states from which some path
+    /// has an accepting run of the automaton.
+    ///
+    /// The fairness constraints are further acceptance sets, containing the states
+    /// where they hold. Returns none if the property would be too large.
+    fn accepting_path_property(
+        &self,
+        formulas: &NnfFormulas,
+        fairness: &[original::Property],
+    ) -> Option<original::Property> {
+        // degeneralize by counting which acceptance set is awaited
+        let num_acceptance_sets = self.acceptance_sets.len();
+        let num_counters = (num_acceptance_sets + fairness.len()).max(1);
+        let num_states = self.nodes.len() * num_counters;
+        let state_index = |node_index: usize, counter: usize| node_index * num_counters + counter;
+
+        // the fairness acceptance sets depend on the system state, so the fulfilment is a property
+        let in_acceptance_set = |node_index: usize, counter: usize| {
+            if let Some(acceptance_set) = self.acceptance_sets.get(counter) {
+                original::Property::Const(acceptance_set.contains(&node_index))
+            } else if let Some(constraint) = fairness.get(counter - num_acceptance_sets) {
+                constraint.clone()
+            } else {
+                original::Property::Const(true)
+            }
+        };
+
+        // the equations are ordered from the outermost: the accepting path property,
+        // then the greatest fixed points Y for accepting visits and the least fixed points X
+        // for the states of the degeneralized automaton, i.e. nu Y . mu X . ...
+        let accepting_variable = |state: usize| 1 + state;
+        let state_variable = |state: usize| 1 + num_states + state;
+        let mut equations = Vec::new();
+
+        let initial_states = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.incoming.contains(&None))
+            .map(|(node_index, _)| variable(state_variable(state_index(node_index, 0))));
+        equations.push(Equation {
+            is_greatest: false,
+            rhs: disjunction(initial_states),
+        });
+
+        for state in 0..num_states {
+            equations.push(Equation {
+                is_greatest: true,
+                rhs: variable(state_variable(state)),
+            });
+        }
+
+        for node_index in 0..self.nodes.len() {
+            for counter in 0..num_counters {
+                let node = &self.nodes[node_index];
+                let next = |fulfilled: bool| {
+                    let accepting = fulfilled && counter == 0;
+                    let next_counter = if fulfilled {
+                        (counter + 1) % num_counters
+                    } else {
+                        counter
+                    };
+
+                    let successors = self
+                        .nodes
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, successor)| successor.incoming.contains(&Some(node_index)))
+                        .map(|(successor_index, _)| {
+                            let successor_state = state_index(successor_index, next_counter);
+                            if accepting {
+                                variable(accepting_variable(successor_state))
+                            } else {
+                                variable(state_variable(successor_state))
+                            }
+                        });
+                    original::Property::Ctl(CtlOperator {
+                        is_universal: false,
+                        temporal: TemporalOperator::X(Box::new(disjunction(successors))),
+                    })
+                };
+                let next = match in_acceptance_set(node_index, counter) {
+                    original::Property::Const(fulfilled) => next(fulfilled),
+                    // staying in the awaiting counter is always possible, it only delays acceptance
+                    fulfilled => disjunction(
+                        [
+                            conjunction([fulfilled, next(true)].into_iter()),
+                            next(false),
+                        ]
+                        .into_iter(),
+                    ),
+                };
+
+                let literals = node.old.iter().filter_map(|formula_index| {
+                    match &formulas.formulas[*formula_index] {
+                        NnfFormula::Literal { atomic, negated } => {
+                            let atomic = original::Property::Atomic(atomic.clone());
+                            Some(if *negated {
+                                original::Property::Negation(Box::new(atomic))
+                            } else {
+                                atomic
+                            })
+                        }
+                        _ => None,
+                    }
+                });
+
+                equations.push(Equation {
+                    is_greatest: false,
+                    rhs: conjunction(literals.chain([next])),
+                });
+            }
+        }
+
+        solve(equations)
+    }
+}
+
+struct Equation {
+    is_greatest: bool,
+    rhs: original::Property,
+}
+
+/// Solves the hierarchical equation system by Gaussian elimination,
+/// returning the closed property for the first equation variable.
+///
+/// The first equation variable must not be used in the equations.
+/// Returns none if some intermediate property would be too large.
+fn solve(mut equations: Vec<Equation>) -> Option<original::Property> {
+    while equations.len() > 1 {
+        let equation = equations.pop().expect("Equation should be present");
+        let index = equations.len();
+        let closed = if mentions_variable(&equation.rhs, &variable_name(index)) {
+            original::Property::FixedPoint(FixedPointOperator {
+                is_greatest: equation.is_greatest,
+                variable: variable_name(index),
+                inner: Box::new(equation.rhs),
+            })
+        } else {
+            // the fixed point would be vacuous
+            equation.rhs
+        };
+        for outer in equations.iter_mut() {
+            substitute(&mut outer.rhs, &variable_name(index), &closed);
+            if outer.rhs.expanded_size() > MAX_TRANSLATED_SIZE {
+                return None;
+            }
+        }
+    }
+    Some(equations.pop().expect("Equation should be present").rhs)
+}
+
+fn substitute(property: &mut original::Property, name: &str, replacement: &original::Property) {
+    match property {
+        original::Property::Const(_) | original::Property::Atomic(_) => {}
+        original::Property::Negation(inner) => substitute(inner, name, replacement),
+        original::Property::BiLogic(op) => {
+            substitute(&mut op.a, name, replacement);
+            substitute(&mut op.b, name, replacement);
+        }
+        original::Property::DerivedLogic(op) => {
+            substitute(&mut op.a, name, replacement);
+            substitute(&mut op.b, name, replacement);
+        }
+        original::Property::Ctl(op) => substitute_temporal(&mut op.temporal, name, replacement),
+        // bounded operators can be in the fairness constraints
+        original::Property::BoundedCtl(op) => {
+            substitute_temporal(&mut op.ctl.temporal, name, replacement)
+        }
+        original::Property::FixedPoint(op) => substitute(&mut op.inner, name, replacement),
+        original::Property::Definition(definition) => {
+            substitute(&mut definition.inner, name, replacement)
+        }
+        original::Property::FixedVariable(variable) => {
+            if variable == name {
+                *property = replacement.clone();
+            }
+        }
+    }
+}
+
+fn substitute_temporal(
+    temporal: &mut TemporalOperator,
+    name: &str,
+    replacement: &original::Property,
+) {
+    match temporal {
+        TemporalOperator::X(inner) => substitute(inner, name, replacement),
+        TemporalOperator::F(op) => substitute(&mut op.0, name, replacement),
+        TemporalOperator::G(op) => substitute(&mut op.0, name, replacement),
+        TemporalOperator::U(op) => {
+            substitute(&mut op.hold, name, replacement);
+            substitute(&mut op.until, name, replacement);
+        }
+        TemporalOperator::R(op) => {
+            substitute(&mut op.releaser, name, replacement);
+            substitute(&mut op.releasee, name, replacement);
+        }
+    }
+}
+
+fn mentions_variable(property: &original::Property, name: &str) -> bool {
+    match property {
+        original::Property::Const(_) | original::Property::Atomic(_) => false,
+        original::Property::Negation(inner) => mentions_variable(inner, name),
+        original::Property::BiLogic(op) => {
+            mentions_variable(&op.a, name) || mentions_variable(&op.b, name)
+        }
+        original::Property::DerivedLogic(op) => {
+            mentions_variable(&op.a, name) || mentions_variable(&op.b, name)
+        }
+        original::Property::Ctl(op) => temporal_mentions_variable(&op.temporal, name),
+        // bounded operators can be in the fairness constraints
+        original::Property::BoundedCtl(op) => temporal_mentions_variable(&op.ctl.temporal, name),
+        original::Property::FixedPoint(op) => mentions_variable(&op.inner, name),
+        original::Property::Definition(definition) => mentions_variable(&definition.inner, name),
+        original::Property::FixedVariable(variable) => variable == name,
+    }
+}
+
+fn temporal_mentions_variable(temporal: &TemporalOperator, name: &str) -> bool {
+    match temporal {
+        TemporalOperator::X(inner) => mentions_variable(inner, name),
+        TemporalOperator::F(op) => mentions_variable(&op.0, name),
+        TemporalOperator::G(op) => mentions_variable(&op.0, name),
+        TemporalOperator::U(op) => {
+            mentions_variable(&op.hold, name) || mentions_variable(&op.until, name)
+        }
+        TemporalOperator::R(op) => {
+            mentions_variable(&op.releaser, name) || mentions_variable(&op.releasee, name)
+        }
+    }
+}
+
+fn variable_name(index: usize) -> String {
+    format!("__ltl_{}", index)
+}
+
+fn variable(index: usize) -> original::Property {
+    original::Property::FixedVariable(variable_name(index))
+}
+
+fn conjunction(properties: impl Iterator<Item = original::Property>) -> original::Property {
+    combine(properties, true)
+}
+
+fn disjunction(properties: impl Iterator<Item = original::Property>) -> original::Property {
+    combine(properties, false)
+}
+
+fn combine(
+    properties: impl Iterator<Item = original::Property>,
+    is_and: bool,
+) -> original::Property {
+    properties
+        .reduce(|a, b| {
+            original::Property::BiLogic(original::BiLogicOperator {
+                is_and,
+                a: Box::new(a),
+                b: Box::new(b),
+            })
+        })
+        .unwrap_or(original::Property::Const(is_and))
+}
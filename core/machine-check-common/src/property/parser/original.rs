@@ -1,31 +1,39 @@
 use std::fmt::Display;
 
 use crate::{
-    property::{AtomicProperty, ValueExpression},
+    property::{AtomicProperty, FieldReference, FieldSource, ValueExpression},
     Signedness,
 };
 
+/// The name the panic id is referenced by.
+pub const PANIC_ID_NAME: &str = "panic_id";
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Property {
     Const(bool),
     Atomic(AtomicProperty),
     Negation(Box<Property>),
     BiLogic(BiLogicOperator),
+    DerivedLogic(DerivedLogicOperator),
     Ctl(CtlOperator),
+    BoundedCtl(BoundedCtlOperator),
     FixedPoint(FixedPointOperator),
     FixedVariable(String),
+    Definition(Definition),
 }
 
 impl Property {
     pub fn inherent() -> Property {
         let not_panicking = AtomicProperty::new(
-            ValueExpression {
-                name: String::from("__panic"),
+            ValueExpression::Field(FieldReference {
+                source: FieldSource::Panic,
+                name: String::from(PANIC_ID_NAME),
                 index: None,
+                slice: None,
                 forced_signedness: Signedness::None,
-            },
+            }),
             crate::property::ComparisonType::Eq,
-            0,
+            ValueExpression::Constant(0),
         );
         let not_panicking = Box::new(Property::Atomic(not_panicking));
 
@@ -35,6 +43,23 @@ impl Property {
             temporal: g_operator,
         })
     }
+
+    /// Returns the number of subproperties after the bounded operators are expanded.
+    ///
+    /// The size is computed without expanding, saturating on overflow.
+    pub fn expanded_size(&self) -> usize {
+        let inner_size = match self {
+            Property::Const(_) | Property::Atomic(_) | Property::FixedVariable(_) => 0,
+            Property::Negation(inner) => inner.expanded_size(),
+            Property::BiLogic(op) => op.a.expanded_size().saturating_add(op.b.expanded_size()),
+            Property::DerivedLogic(op) => op.a.expanded_size().saturating_add(op.b.expanded_size()),
+            Property::Ctl(op) => op.temporal.expanded_size(),
+            Property::BoundedCtl(op) => return op.expanded_size(),
+            Property::FixedPoint(op) => op.inner.expanded_size(),
+            Property::Definition(definition) => definition.inner.expanded_size(),
+        };
+        inner_size.saturating_add(1)
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -44,12 +69,130 @@ pub struct BiLogicOperator {
     pub b: Box<Property>,
 }
 
+/// An implication, equivalence or exclusive or.
+///
+/// These are lowered to conjunctions, disjunctions and negations when folding.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DerivedLogicOperator {
+    pub ty: DerivedLogicType,
+    pub a: Box<Property>,
+    pub b: Box<Property>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DerivedLogicType {
+    Implication,
+    Equivalence,
+    ExclusiveOr,
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct CtlOperator {
     pub is_universal: bool,
     pub temporal: TemporalOperator,
 }
 
+/// A CTL operator considering only the given number of steps.
+///
+/// The temporal operator must not be X.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BoundedCtlOperator {
+    pub bound: u64,
+    pub ctl: CtlOperator,
+}
+
+impl BoundedCtlOperator {
+    /// Expands the first step of the operator.
+    ///
+    /// The operator in the next step is bounded by one step less.
+    /// If the bound is zero, only the current state is considered.
+    pub fn expand(self) -> Property {
+        let is_universal = self.ctl.is_universal;
+        let bound = self.bound;
+        let next = |temporal: TemporalOperator| {
+            if bound == 0 {
+                return None;
+            }
+            Some(Property::Ctl(CtlOperator {
+                is_universal,
+                temporal: TemporalOperator::X(Box::new(Property::BoundedCtl(BoundedCtlOperator {
+                    bound: bound - 1,
+                    ctl: CtlOperator {
+                        is_universal,
+                        temporal,
+                    },
+                }))),
+            }))
+        };
+
+        // F: p || [A/E]X(F[<=k-1] p)
+        // G: p && [A/E]X(G[<=k-1] p)
+        // U: q || (p && [A/E]X(U[<=k-1](p, q)))
+        // R: q && (p || [A/E]X(R[<=k-1](p, q)))
+        let (is_release, permitting, sufficient, next) = match self.ctl.temporal {
+            TemporalOperator::X(_) => panic!("Bounded operator should not be X"),
+            TemporalOperator::F(op) => {
+                let next = next(TemporalOperator::F(op.clone()));
+                (false, None, *op.0, next)
+            }
+            TemporalOperator::G(op) => {
+                let next = next(TemporalOperator::G(op.clone()));
+                (true, None, *op.0, next)
+            }
+            TemporalOperator::U(op) => {
+                let next = next(TemporalOperator::U(op.clone()));
+                (false, Some(*op.hold), *op.until, next)
+            }
+            TemporalOperator::R(op) => {
+                let next = next(TemporalOperator::R(op.clone()));
+                (true, Some(*op.releaser), *op.releasee, next)
+            }
+        };
+
+        let Some(next) = next else {
+            return sufficient;
+        };
+        let next = match permitting {
+            Some(permitting) => Property::BiLogic(BiLogicOperator {
+                is_and: !is_release,
+                a: Box::new(permitting),
+                b: Box::new(next),
+            }),
+            None => next,
+        };
+        Property::BiLogic(BiLogicOperator {
+            is_and: is_release,
+            a: Box::new(sufficient),
+            b: Box::new(next),
+        })
+    }
+
+    /// Returns the number of subproperties of the fully expanded operator.
+    fn expanded_size(&self) -> usize {
+        let (permitting_size, sufficient_size) = match &self.ctl.temporal {
+            TemporalOperator::X(_) => panic!("Bounded operator should not be X"),
+            TemporalOperator::F(op) => (None, op.0.expanded_size()),
+            TemporalOperator::G(op) => (None, op.0.expanded_size()),
+            TemporalOperator::U(op) => (Some(op.hold.expanded_size()), op.until.expanded_size()),
+            TemporalOperator::R(op) => (
+                Some(op.releaser.expanded_size()),
+                op.releasee.expanded_size(),
+            ),
+        };
+        // each step adds the logic operator, the next-state operator and its operands
+        let step_size = match permitting_size {
+            Some(permitting_size) => permitting_size.saturating_add(1),
+            None => 0,
+        }
+        .saturating_add(sufficient_size)
+        .saturating_add(2);
+        let bound = usize::try_from(self.bound).unwrap_or(usize::MAX);
+        step_size
+            .saturating_mul(bound)
+            .saturating_add(sufficient_size)
+    }
+}
+
 /// A temporal operator within a CTL path quantifier.
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum TemporalOperator {
@@ -68,6 +211,15 @@ pub struct FixedPointOperator {
     pub inner: Box<Property>,
 }
 
+/// A property referenced by its name, displayed as the name.
+///
+/// Used for the definitions from a property file and the panic propositions.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Definition {
+    pub name: String,
+    pub inner: Box<Property>,
+}
+
 impl Display for Property {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -80,54 +232,120 @@ impl Display for Property {
             Property::Negation(prop_uni) => {
                 write!(f, "!({})", *prop_uni)
             }
-            Property::BiLogic(op) => write_logic_bi(f, op),
+            Property::BiLogic(op) => {
+                let op_str = if op.is_and { "&&" } else { "||" };
+                write_logic_bi(f, op_str, &op.a, &op.b, false)
+            }
+            Property::DerivedLogic(op) => {
+                let op_str = match op.ty {
+                    DerivedLogicType::Implication => "->",
+                    DerivedLogicType::Equivalence => "<->",
+                    DerivedLogicType::ExclusiveOr => "^",
+                };
+                // the atomic properties would take the exclusive or as a part of the value expression
+                let parenthesize_atomic = op.ty == DerivedLogicType::ExclusiveOr;
+                write_logic_bi(f, op_str, &op.a, &op.b, parenthesize_atomic)
+            }
             Property::Ctl(op) => {
                 let quantifier_letter = if op.is_universal { 'A' } else { 'E' };
                 write!(f, "{}{}", quantifier_letter, op.temporal)
             }
+            Property::BoundedCtl(op) => {
+                let quantifier_letter = if op.ctl.is_universal { 'A' } else { 'E' };
+                write!(f, "{}", quantifier_letter)?;
+                op.ctl.temporal.write_bounded(f, Some(op.bound))
+            }
             Property::FixedPoint(op) => {
                 let fixed_point_str = if op.is_greatest { "gfp" } else { "lfp" };
 
                 write!(f, "{}![{}, {}]", fixed_point_str, op.variable, op.inner)
             }
             Property::FixedVariable(var) => write!(f, "{}", var),
+            Property::Definition(definition) => write!(f, "{}", definition.name),
         }
     }
 }
 
-fn write_logic_bi(f: &mut std::fmt::Formatter<'_>, op: &BiLogicOperator) -> std::fmt::Result {
-    let op_str = if op.is_and { "&&" } else { "||" };
-    // Make sure the inner and / or properties are in parentheses so the display is unambiguous.
+fn write_logic_bi(
+    f: &mut std::fmt::Formatter<'_>,
+    op_str: &str,
+    a: &Property,
+    b: &Property,
+    parenthesize_atomic: bool,
+) -> std::fmt::Result {
+    // Make sure the inner logic properties are in parentheses so the display is unambiguous.
     let write_inner_prop = |f: &mut std::fmt::Formatter<'_>, prop: &Property| {
-        if matches!(prop, Property::BiLogic(..)) {
+        let parenthesize = match prop {
+            Property::BiLogic(..) | Property::DerivedLogic(..) => true,
+            Property::Atomic(..) => parenthesize_atomic,
+            _ => false,
+        };
+        if parenthesize {
             write!(f, "({})", prop)
         } else {
             write!(f, "{}", prop)
         }
     };
 
-    write_inner_prop(f, &op.a)?;
+    write_inner_prop(f, a)?;
     write!(f, " {} ", op_str)?;
-    write_inner_prop(f, &op.b)
+    write_inner_prop(f, b)
 }
 
 impl Display for TemporalOperator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_bounded(f, None)
+    }
+}
+
+impl TemporalOperator {
+    fn expanded_size(&self) -> usize {
+        match self {
+            TemporalOperator::X(inner) => inner.expanded_size(),
+            TemporalOperator::F(op) => op.0.expanded_size(),
+            TemporalOperator::G(op) => op.0.expanded_size(),
+            TemporalOperator::U(op) => op
+                .hold
+                .expanded_size()
+                .saturating_add(op.until.expanded_size()),
+            TemporalOperator::R(op) => op
+                .releaser
+                .expanded_size()
+                .saturating_add(op.releasee.expanded_size()),
+        }
+    }
+
+    fn write_bounded(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        bound: Option<u64>,
+    ) -> std::fmt::Result {
+        let letter = match self {
+            TemporalOperator::X(_) => 'X',
+            TemporalOperator::F(_) => 'F',
+            TemporalOperator::G(_) => 'G',
+            TemporalOperator::U(_) => 'U',
+            TemporalOperator::R(_) => 'R',
+        };
+        write!(f, "{}", letter)?;
+        if let Some(bound) = bound {
+            write!(f, "[<={}]", bound)?;
+        }
         match self {
             TemporalOperator::X(prop_uni) => {
-                write!(f, "X![{}]", *prop_uni)
+                write!(f, "![{}]", *prop_uni)
             }
             TemporalOperator::F(prop_f) => {
-                write!(f, "F![{}]", prop_f.0)
+                write!(f, "![{}]", prop_f.0)
             }
             TemporalOperator::G(prop_g) => {
-                write!(f, "G![{}]", prop_g.0)
+                write!(f, "![{}]", prop_g.0)
             }
             TemporalOperator::U(prop_u) => {
-                write!(f, "U![{}, {}]", prop_u.hold, prop_u.until)
+                write!(f, "![{}, {}]", prop_u.hold, prop_u.until)
             }
             TemporalOperator::R(prop_r) => {
-                write!(f, "R![{}, {}]", prop_r.releaser, prop_r.releasee)
+                write!(f, "![{}, {}]", prop_r.releaser, prop_r.releasee)
             }
         }
     }
@@ -0,0 +1,423 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use crate::property::{
+    BiLogicOperator, FixedPointOperator, NextOperator, Property, PropertyType, SubpropertyEntry,
+};
+
+impl Property {
+    /// Returns an equivalent property that is simpler to model-check.
+    ///
+    /// The negations are pushed inwards to the atomic properties, constant subproperties
+    /// are folded, fixed-point operators that do not use their variable are removed
+    /// and duplicate subproperties are shared. Subproperties containing fixed-point
+    /// operators are not shared, as each fixed-point operator is computed separately.
+    ///
+    /// The display strings are preserved: a subproperty that replaces others
+    /// is displayed as the outermost one it replaces.
+    pub fn simplify(&self) -> Property {
+        let mut simplifier = Simplifier {
+            property: self,
+            arena: Vec::new(),
+            fixed_point_free: Vec::new(),
+            shared: HashMap::new(),
+            fixed_points: BTreeMap::new(),
+        };
+        let root = simplifier.simplify(0, false);
+        simplifier.compact(root)
+    }
+}
+
+/// The simplified subproperty before its display string is decided.
+enum Outcome {
+    /// A new subproperty of the given type.
+    Built(PropertyType),
+    /// A fixed-point operator to be placed at its reserved index.
+    Reserved(usize, PropertyType),
+    /// An already simplified subproperty that takes the place of the original.
+    Collapsed(usize),
+}
+
+struct Simplifier<'a> {
+    property: &'a Property,
+    arena: Vec<Option<SubpropertyEntry>>,
+    fixed_point_free: Vec<bool>,
+    shared: HashMap<SubpropertyEntry, usize>,
+    // original fixed-point index to the simplified one and whether it is negated
+    fixed_points: BTreeMap<usize, (usize, bool)>,
+}
+
+impl Simplifier<'_> {
+    /// Simplifies the original subproperty, negating it if required.
+    ///
+    /// Returns the index of the simplified subproperty.
+    fn simplify(&mut self, index: usize, negated: bool) -> usize {
+        let entry = self.property.subproperty_entry(index);
+
+        let outcome = match &entry.ty {
+            PropertyType::Const(value) => Outcome::Built(PropertyType::Const(*value != negated)),
+            PropertyType::Atomic(atomic) => {
+                if negated {
+                    // the negation stays above the atomic property
+                    let atomic = self.push(SubpropertyEntry {
+                        ty: PropertyType::Atomic(atomic.clone()),
+                        display_string: entry.display_string.clone(),
+                        visible: entry.visible,
+                    });
+                    Outcome::Built(PropertyType::Negation(atomic))
+                } else {
+                    Outcome::Built(PropertyType::Atomic(atomic.clone()))
+                }
+            }
+            PropertyType::Negation(inner) => Outcome::Collapsed(self.simplify(*inner, !negated)),
+            PropertyType::BiLogic(op) => self.simplify_bi_logic(op, negated),
+            PropertyType::Next(op) => {
+                // by duality, !AX[a] is EX[!a]
+                let inner = self.simplify(op.inner, negated);
+                match self.entry(inner).ty {
+                    // there is always a successor, so the next state has the same constant value
+                    PropertyType::Const(value) => Outcome::Built(PropertyType::Const(value)),
+                    _ => Outcome::Built(PropertyType::Next(NextOperator {
+                        is_universal: op.is_universal != negated,
+                        inner,
+                    })),
+                }
+            }
+            PropertyType::FixedPoint(op) => self.simplify_fixed_point(index, op, negated),
+            PropertyType::FixedVariable(fixed_point_index) => {
+                let (variable_index, fixed_point_negated) = *self
+                    .fixed_points
+                    .get(fixed_point_index)
+                    .expect("Fixed-point variable should be within its fixed-point operator");
+                let variable = PropertyType::FixedVariable(variable_index);
+                // the variable of a negated fixed-point operator is negated as well,
+                // which cancels out in monotone properties
+                let variable = if negated != fixed_point_negated {
+                    PropertyType::Negation(self.push(SubpropertyEntry {
+                        ty: variable,
+                        display_string: entry.display_string.clone(),
+                        visible: entry.visible,
+                    }))
+                } else {
+                    variable
+                };
+                return self.finish(index, Outcome::Built(variable), false);
+            }
+        };
+
+        self.finish(index, outcome, negated)
+    }
+
+    fn simplify_bi_logic(&mut self, op: &BiLogicOperator, negated: bool) -> Outcome {
+        // by De Morgan's laws, !(a && b) is !a || !b
+        let is_and = op.is_and != negated;
+        let a = self.simplify(op.a, negated);
+        let b = self.simplify(op.b, negated);
+
+        for (constant, other) in [(a, b), (b, a)] {
+            if let PropertyType::Const(value) = self.entry(constant).ty {
+                return if value == is_and {
+                    // neutral, only the other operand matters
+                    Outcome::Collapsed(other)
+                } else {
+                    // absorbing
+                    Outcome::Built(PropertyType::Const(value))
+                };
+            }
+        }
+
+        if a == b {
+            // idempotent
+            return Outcome::Collapsed(a);
+        }
+
+        Outcome::Built(PropertyType::BiLogic(BiLogicOperator {
+            is_and,
+            a,
+            b,
+            reverse_display: op.reverse_display,
+        }))
+    }
+
+    fn simplify_fixed_point(
+        &mut self,
+        index: usize,
+        op: &FixedPointOperator,
+        negated: bool,
+    ) -> Outcome {
+        // by duality, the negation of the least fixed point of f(Z)
+        // is the greatest fixed point of !f(!Z)
+        let is_greatest = op.is_greatest != negated;
+
+        let fixed_point_index = self.arena.len();
+        self.arena.push(None);
+        self.fixed_point_free.push(false);
+
+        self.fixed_points
+            .insert(index, (fixed_point_index, negated));
+        let inner = self.simplify(op.inner, negated);
+        self.fixed_points.remove(&index);
+
+        if !self.references(inner, fixed_point_index) {
+            // the fixed point is reached immediately
+            return Outcome::Collapsed(inner);
+        }
+
+        // the fixed point of Z or [A/E]X(Z) is the starting value
+        let only_variable = match &self.entry(inner).ty {
+            PropertyType::FixedVariable(_) => true,
+            PropertyType::Next(next) => {
+                matches!(self.entry(next.inner).ty, PropertyType::FixedVariable(_))
+            }
+            _ => false,
+        };
+        if only_variable {
+            return Outcome::Built(PropertyType::Const(is_greatest));
+        }
+
+        Outcome::Reserved(
+            fixed_point_index,
+            PropertyType::FixedPoint(FixedPointOperator { is_greatest, inner }),
+        )
+    }
+
+    /// Decides the display of the simplified subproperty, returning its index.
+    ///
+    /// A subproperty that is not negated keeps the display string of the original,
+    /// a negated subproperty is only displayed by its parts.
+    fn finish(&mut self, index: usize, outcome: Outcome, negated: bool) -> usize {
+        let original = self.property.subproperty_entry(index);
+        let display_string = if negated {
+            None
+        } else {
+            original.display_string.clone()
+        };
+
+        match outcome {
+            Outcome::Built(ty) => self.push(SubpropertyEntry {
+                ty,
+                display_string,
+                visible: original.visible,
+            }),
+            Outcome::Reserved(reserved_index, ty) => {
+                self.arena[reserved_index] = Some(SubpropertyEntry {
+                    ty,
+                    display_string,
+                    visible: original.visible,
+                });
+                reserved_index
+            }
+            Outcome::Collapsed(collapsed_index) => {
+                let collapsed = self.entry(collapsed_index);
+                let (display_string, visible) = if negated {
+                    (
+                        collapsed.display_string.clone(),
+                        collapsed.visible && original.visible,
+                    )
+                } else {
+                    (
+                        display_string.or_else(|| collapsed.display_string.clone()),
+                        original.visible,
+                    )
+                };
+                self.relabel(collapsed_index, display_string, visible)
+            }
+        }
+    }
+
+    /// Changes the display of a simplified subproperty, returning its new index.
+    fn relabel(&mut self, index: usize, display_string: Option<String>, visible: bool) -> usize {
+        let entry = self.entry(index);
+        if entry.display_string == display_string && entry.visible == visible {
+            return index;
+        }
+        if self.fixed_point_free[index] {
+            // may be shared, push a relabelled copy
+            let mut entry = entry.clone();
+            entry.display_string = display_string;
+            entry.visible = visible;
+            return self.push(entry);
+        }
+        // not shared, the subproperty has no other parent yet
+        let entry = self.arena[index]
+            .as_mut()
+            .expect("Simplified subproperty should be filled");
+        entry.display_string = display_string;
+        entry.visible = visible;
+        index
+    }
+
+    /// Pushes the subproperty, sharing it with an equal one if possible.
+    fn push(&mut self, entry: SubpropertyEntry) -> usize {
+        let fixed_point_free = match &entry.ty {
+            PropertyType::Const(_) | PropertyType::Atomic(_) => true,
+            PropertyType::Negation(inner) => self.fixed_point_free[*inner],
+            PropertyType::BiLogic(op) => self.fixed_point_free[op.a] && self.fixed_point_free[op.b],
+            PropertyType::Next(op) => self.fixed_point_free[op.inner],
+            PropertyType::FixedPoint(_) | PropertyType::FixedVariable(_) => false,
+        };
+
+        if fixed_point_free {
+            if let Some(shared_index) = self.shared.get(&entry) {
+                return *shared_index;
+            }
+            self.shared.insert(entry.clone(), self.arena.len());
+        }
+
+        let index = self.arena.len();
+        self.arena.push(Some(entry));
+        self.fixed_point_free.push(fixed_point_free);
+        index
+    }
+
+    /// Returns whether the simplified subproperty references the fixed-point variable.
+    fn references(&self, index: usize, fixed_point_index: usize) -> bool {
+        match &self.entry(index).ty {
+            PropertyType::Const(_) | PropertyType::Atomic(_) => false,
+            PropertyType::Negation(inner) => self.references(*inner, fixed_point_index),
+            PropertyType::BiLogic(op) => {
+                self.references(op.a, fixed_point_index) || self.references(op.b, fixed_point_index)
+            }
+            PropertyType::Next(op) => self.references(op.inner, fixed_point_index),
+            PropertyType::FixedPoint(op) => self.references(op.inner, fixed_point_index),
+            PropertyType::FixedVariable(variable) => *variable == fixed_point_index,
+        }
+    }
+
+    fn entry(&self, index: usize) -> &SubpropertyEntry {
+        self.arena[index]
+            .as_ref()
+            .expect("Simplified subproperty should be filled")
+    }
+
+    /// Constructs the property from the subproperties reachable from the root.
+    ///
+    /// The root is placed at index zero and each subproperty is placed before its children.
+    fn compact(mut self, root: usize) -> Property {
+        let mut mapping = BTreeMap::new();
+        let mut order = Vec::new();
+        self.map_reachable(root, &mut mapping, &mut order);
+
+        let arena = order
+            .into_iter()
+            .map(|index| {
+                let mut entry = self.arena[index]
+                    .take()
+                    .expect("Reachable subproperty should be filled");
+                let map = |index: &mut usize| *index = mapping[&*index];
+                match &mut entry.ty {
+                    PropertyType::Const(_) | PropertyType::Atomic(_) => {}
+                    PropertyType::Negation(inner) => map(inner),
+                    PropertyType::BiLogic(op) => {
+                        map(&mut op.a);
+                        map(&mut op.b);
+                    }
+                    PropertyType::Next(op) => map(&mut op.inner),
+                    PropertyType::FixedPoint(op) => map(&mut op.inner),
+                    PropertyType::FixedVariable(fixed_point_index) => map(fixed_point_index),
+                }
+                entry
+            })
+            .collect();
+
+        Property {
+            arena: Arc::new(arena),
+        }
+    }
+
+    fn map_reachable(
+        &self,
+        index: usize,
+        mapping: &mut BTreeMap<usize, usize>,
+        order: &mut Vec<usize>,
+    ) {
+        if mapping.contains_key(&index) {
+            return;
+        }
+        mapping.insert(index, order.len());
+        order.push(index);
+
+        match &self.entry(index).ty {
+            PropertyType::Const(_) | PropertyType::Atomic(_) | PropertyType::FixedVariable(_) => {}
+            PropertyType::Negation(inner) => self.map_reachable(*inner, mapping, order),
+            PropertyType::BiLogic(op) => {
+                self.map_reachable(op.a, mapping, order);
+                self.map_reachable(op.b, mapping, order);
+            }
+            PropertyType::Next(op) => self.map_reachable(op.inner, mapping, order),
+            PropertyType::FixedPoint(op) => self.map_reachable(op.inner, mapping, order),
+        }
+    }
+}
+
+#[cfg(test)]
+fn types(property: &Property) -> Vec<PropertyType> {
+    property
+        .arena
+        .iter()
+        .map(|entry| entry.ty.clone())
+        .collect()
+}
+
+#[test]
+fn test_simplify() {
+    let simplified = Property::parse("!(!(AG![true && a == 1]))")
+        .unwrap()
+        .simplify();
+    assert_eq!(
+        types(&simplified),
+        types(&Property::parse("AG![a == 1]").unwrap().simplify())
+    );
+    assert_eq!(
+        simplified.root_subproperty().display_str(),
+        Some("!(!(AG![true && a == 1]))")
+    );
+    assert!(
+        simplified.num_subproperties()
+            < Property::parse("AG![a == 1]").unwrap().num_subproperties()
+    );
+
+    // the negation is pushed inwards through the fixed-point operator
+    assert_eq!(
+        types(&Property::parse("!(EF![a == 1])").unwrap().simplify()),
+        types(&Property::parse("AG![!(a == 1)]").unwrap().simplify())
+    );
+    assert_eq!(
+        types(
+            &Property::parse("!(a == 1 && AX![b == 2])")
+                .unwrap()
+                .simplify()
+        ),
+        types(
+            &Property::parse("!(a == 1) || EX![!(b == 2)]")
+                .unwrap()
+                .simplify()
+        )
+    );
+
+    // constants are folded and trivial fixed points removed
+    for (input, value) in [
+        ("a == 1 || true", true),
+        ("EX![false] && a == 1", false),
+        ("EF![true]", true),
+        ("AG![true]", true),
+        ("!(AG![b == 2 || !(false)])", false),
+        ("lfp![Z, EX![Z]]", false),
+    ] {
+        let simplified = Property::parse(input).unwrap().simplify();
+        assert_eq!(types(&simplified), vec![PropertyType::Const(value)]);
+        assert_eq!(simplified.root_subproperty().display_str(), Some(input));
+    }
+
+    // equal subproperties without fixed points are shared
+    let simplified = Property::parse("a == 1 && (b == 2 || a == 1)")
+        .unwrap()
+        .simplify();
+    assert_eq!(simplified.num_subproperties(), 4);
+    let simplified = Property::parse("EF![a == 1] && EF![a == 1]")
+        .unwrap()
+        .simplify();
+    assert!(simplified.affected_fixed_points(0).len() == 2);
+}
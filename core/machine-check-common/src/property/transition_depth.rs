@@ -5,6 +5,13 @@ impl Property {
         self.transition_depth_inner(0)
     }
 
+    /// Returns the transition depth of the subproperty at a given index.
+    ///
+    /// Panics if the subproperty index is invalid.
+    pub fn subproperty_transition_depth(&self, subproperty_index: usize) -> usize {
+        self.transition_depth_inner(subproperty_index)
+    }
+
     fn transition_depth_inner(&self, subproperty_index: usize) -> usize {
         let subproperty_entry = self.subproperty_entry(subproperty_index);
 
@@ -1,27 +1,41 @@
 use std::collections::BTreeMap;
 use std::ops::ControlFlow;
+use std::time::Instant;
 
+use checkpoint::{CheckpointAction, Checkpointing};
 use log::log_enabled;
 use log::trace;
 use machine_check_common::check::Conclusion;
+use machine_check_common::check::Culprit;
 use machine_check_common::check::KnownConclusion;
 use machine_check_common::check::Property;
 use machine_check_common::property::Subproperty;
 use machine_check_common::ExecError;
 use machine_check_common::ExecStats;
-use machine_check_common::NodeId;
 use machine_check_common::ParamValuation;
+use machine_check_common::ResourceLimit;
 use machine_check_common::StateId;
 use mck::concr::FullMachine;
 use work_state::WorkState;
 
-use crate::space::StateSpace;
-use crate::Strategy;
+pub use checkpoint::Checkpoint;
+pub use export::SpaceFormat;
+pub use observer::{PrecisionKind, RefinedPrecision, VerificationEvent, VerificationObserver};
+
+use crate::space::{EntryTracking, StateSpace};
 use crate::{RefinInput, RefinPanicState, RefinParam};
+use crate::{ResourceLimits, Strategy};
 use mck::refin::Refine;
 
+mod checkpoint;
+mod export;
+mod observer;
+mod param_classes;
 mod refine;
 mod regenerate;
+mod replay;
+mod steps;
+mod trace;
 mod work_state;
 
 /// Three-valued abstraction refinement framework.
@@ -38,6 +52,36 @@ pub struct Framework<M: FullMachine> {
     /// Default step precision.
     default_step_precision: RefinPanicState<M>,
 
+    /// Whether each input covers only a single concrete input by default.
+    naive_inputs: bool,
+
+    /// Whether the step outputs decay by default.
+    use_decay: bool,
+
+    /// The number of threads computing the successors in parallel.
+    num_threads: usize,
+
+    /// Limits on the resources used by the verification.
+    limits: ResourceLimits,
+
+    /// The time of the construction, the time limit is measured from it.
+    start: Instant,
+
+    /// Observer of the verification progress.
+    observer: Option<Box<dyn VerificationObserver>>,
+
+    /// Whether the observer requested cancellation of the verification.
+    cancelled: bool,
+
+    /// Periodic saving of the checkpoints, if requested.
+    checkpointing: Option<Checkpointing>,
+
+    /// The properties verified since the work state was last reset.
+    verified_properties: Vec<Property>,
+
+    /// The actions changing the work state since it was last reset, recorded for the checkpoints.
+    actions: Vec<CheckpointAction>,
+
     /// Work state containing the structures that change during verification.
     work_state: WorkState<M>,
 }
@@ -65,21 +109,62 @@ impl<M: FullMachine> Framework<M> {
             default_input_precision,
             default_step_precision,
             default_param_precision,
-            work_state: WorkState::new(),
+            naive_inputs: strategy.naive_inputs,
+            use_decay: strategy.use_decay,
+            num_threads: strategy.num_threads.max(1),
+            limits: ResourceLimits::default(),
+            start: Instant::now(),
+            observer: None,
+            cancelled: false,
+            checkpointing: None,
+            verified_properties: Vec::new(),
+            actions: Vec::new(),
+            work_state: WorkState::new(EntryTracking::default()),
         }
     }
 
+    /// Sets the limits on the resources used by the verification.
+    ///
+    /// There are no limits by default.
+    pub fn set_limits(&mut self, limits: ResourceLimits) {
+        self.limits = limits;
+    }
+
+    /// Sets the observer of the verification progress.
+    ///
+    /// Any cancellation requested by the previous observer is discarded.
+    pub fn set_observer(&mut self, observer: Box<dyn VerificationObserver>) {
+        self.observer = Some(observer);
+        self.cancelled = false;
+    }
+
     pub fn verify(&mut self, property: &Property) -> Result<KnownConclusion, ExecError> {
         // loop verification steps until some conclusion is reached
         let result = loop {
             match self.step_verification(property) {
-                ControlFlow::Continue(()) => {}
+                ControlFlow::Continue(()) => {
+                    // stop before the next refinement if cancelled or a limit was reached
+                    let culprit = self
+                        .work_state
+                        .culprit
+                        .as_ref()
+                        .expect("Culprit should be present when continuing verification");
+                    let limits = self.check_limits(culprit);
+                    // save the checkpoint if a limit was reached so that it can be resumed
+                    self.save_checkpoint_if_due(matches!(
+                        limits,
+                        Err(ExecError::ResourceLimit(..))
+                    ));
+                    if let Err(err) = limits {
+                        break Err(err);
+                    }
+                }
                 ControlFlow::Break(result) => break result,
             }
         };
 
         // make compact after verification for nice state space information
-        self.work_state.make_compact();
+        self.compact();
 
         if log_enabled!(log::Level::Trace) {
             trace!("Verification final space: {:#?}", self.work_state.space);
@@ -91,9 +176,24 @@ impl<M: FullMachine> Framework<M> {
         &mut self,
         property: &Property,
     ) -> ControlFlow<Result<KnownConclusion, ExecError>> {
+        // the inputs and parameters referenced by the property must be tracked in the state space
+        // start from scratch if they are not
+        let required_tracking = EntryTracking::required_by(property);
+        if !self
+            .work_state
+            .space
+            .entry_tracking()
+            .covers(&required_tracking)
+        {
+            self.retrack(required_tracking);
+        }
+        if !self.verified_properties.contains(property) {
+            self.verified_properties.push(property.clone());
+        }
+
         // if the space is invalid (just after construction), regenerate it
         if !self.work_state.space.is_valid() {
-            self.regenerate(NodeId::ROOT);
+            self.regenerate_space();
         } else if let Some(culprit) = self.work_state.culprit.take() {
             // we have a culprit, refine on it
             if let Err(err) = self.refine(&culprit) {
@@ -109,11 +209,17 @@ impl<M: FullMachine> Framework<M> {
         }
 
         // perform model-checking
-        match self
+        let result = self
             .work_state
             .checker
-            .check_property(&self.work_state.space, property)
-        {
+            .check_property(&self.work_state.space, property);
+        if let Ok(conclusion) = &result {
+            self.notify(|stats| VerificationEvent::ModelChecked {
+                conclusion: conclusion.clone(),
+                stats,
+            });
+        }
+        match result {
             Ok(Conclusion::Known(conclusion)) => {
                 // conclude the result
                 ControlFlow::Break(Ok(conclusion))
@@ -134,6 +240,37 @@ impl<M: FullMachine> Framework<M> {
         }
     }
 
+    /// Returns an error if the verification was cancelled or a resource limit was reached.
+    ///
+    /// The culprit to be refined next is returned with the resource limit error.
+    fn check_limits(&self, culprit: &Culprit) -> Result<(), ExecError> {
+        if self.cancelled {
+            return Err(ExecError::Cancelled);
+        }
+        let limit = if self
+            .limits
+            .timeout
+            .is_some_and(|timeout| self.start.elapsed() >= timeout)
+        {
+            ResourceLimit::Timeout
+        } else if self
+            .limits
+            .max_refinements
+            .is_some_and(|max_refinements| self.work_state.num_refinements >= max_refinements)
+        {
+            ResourceLimit::Refinements
+        } else if self
+            .limits
+            .max_states
+            .is_some_and(|max_states| self.work_state.space.num_states() > max_states)
+        {
+            ResourceLimit::States
+        } else {
+            return Ok(());
+        };
+        Err(ExecError::ResourceLimit(limit, Box::new(culprit.clone())))
+    }
+
     pub fn check_subproperty_with_labelling(
         &mut self,
         property: &Subproperty,
@@ -143,13 +280,29 @@ impl<M: FullMachine> Framework<M> {
             .check_subproperty_with_labelling(&self.work_state.space, property)
     }
 
+    /// Returns the valuation of the subproperty in the initial states.
+    pub fn check_subproperty_in_initial(
+        &mut self,
+        subproperty: &Subproperty,
+    ) -> Result<ParamValuation, ExecError> {
+        self.work_state
+            .checker
+            .check_subproperty_in_initial(&self.work_state.space, subproperty)
+    }
+
     pub fn find_panic_string(&mut self) -> Option<&'static str> {
         self.work_state.find_panic_string()
     }
 
     pub fn reset(&mut self) {
-        // reset the work state
-        self.work_state = WorkState::new()
+        // reset the work state, keeping the tracking of steps entering the states
+        let entry_tracking = self.work_state.space.entry_tracking();
+        self.work_state = WorkState::new(entry_tracking);
+        self.verified_properties.clear();
+        self.actions.clear();
+        if entry_tracking != EntryTracking::default() {
+            self.actions.push(CheckpointAction::Retrack(entry_tracking));
+        }
     }
 
     pub fn info(&mut self) -> ExecStats {
@@ -161,6 +314,6 @@ impl<M: FullMachine> Framework<M> {
     }
 
     pub fn make_compact(&mut self) {
-        self.work_state.make_compact();
+        self.compact();
     }
 }
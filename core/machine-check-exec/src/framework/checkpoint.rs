@@ -0,0 +1,323 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use machine_check_common::check::{Culprit, Property};
+use machine_check_common::{ExecError, ExecStats, NodeId, Signedness};
+use mck::abstr::{Field, Manipulatable, Phi};
+use mck::concr::FullMachine;
+use mck::misc::Meta;
+use mck::refin::Refine;
+use serde::{Deserialize, Serialize};
+
+use super::work_state::WorkState;
+use super::Framework;
+use crate::space::EntryTracking;
+use crate::{AbstrState, RefinInput, RefinParam};
+
+/// Version of the checkpoint format, increased when the recorded actions change meaning.
+const CHECKPOINT_VERSION: u32 = 2;
+
+/// Checkpoint of the verification work state, which can be saved and resumed from.
+///
+/// The abstract states and precisions are not serializable, so the checkpoint records
+/// the actions changing the work state instead. Since the actions are deterministic,
+/// replaying them on a fresh work state reconstructs the state space and the precisions.
+/// The model checker is not recorded, it recomputes the labellings when checking again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// The version of the checkpoint format.
+    version: u32,
+    /// The system the checkpoint belongs to.
+    system: SystemIdentity,
+    /// Whether each input covers only a single concrete input by default.
+    naive_inputs: bool,
+    /// Whether the step outputs decay by default.
+    use_decay: bool,
+    /// The properties verified until now.
+    properties: Vec<Property>,
+    /// The actions changing the work state, in the order they were made.
+    actions: Vec<CheckpointAction>,
+    /// The statistics after the actions, used to check the replay.
+    stats: ExecStats,
+}
+
+impl Checkpoint {
+    /// Returns the statistics of the verification at the time of the checkpoint.
+    pub fn stats(&self) -> &ExecStats {
+        &self.stats
+    }
+}
+
+/// Identity of the system, i.e. the names and types of its fields.
+///
+/// The fields do not depend on the compiler or the module the system is in,
+/// so the checkpoint can be resumed by a recompiled or moved system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SystemIdentity {
+    state_fields: Vec<FieldIdentity>,
+    input_fields: Vec<FieldIdentity>,
+    param_fields: Vec<FieldIdentity>,
+}
+
+/// Name and type of a field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FieldIdentity {
+    name: String,
+    signedness: Signedness,
+    /// The bit-width of the field, or of the elements if it is an array.
+    bit_width: u32,
+    /// The bit-width of the array index, or none if the field is not an array.
+    index_width: Option<u32>,
+}
+
+impl SystemIdentity {
+    fn of<M: FullMachine>() -> Self {
+        fn fields<T: Manipulatable>(example: T) -> Vec<FieldIdentity> {
+            T::field_names()
+                .into_iter()
+                .map(|name| {
+                    let field = example
+                        .get(name)
+                        .expect("Field with a listed name should exist");
+                    let (bit_width, index_width) = match field.description() {
+                        Field::Bitvector(bitvector) => (bitvector.bit_width, None),
+                        Field::Array(array) => (array.bit_width, Some(array.bit_length)),
+                    };
+                    FieldIdentity {
+                        name: String::from(name),
+                        signedness: T::signedness(name),
+                        bit_width,
+                        index_width,
+                    }
+                })
+                .collect()
+        }
+
+        SystemIdentity {
+            state_fields: fields(AbstrState::<M>::uninit()),
+            input_fields: fields(RefinInput::<M>::clean().proto_first()),
+            param_fields: fields(RefinParam::<M>::clean().proto_first()),
+        }
+    }
+
+    /// Describes the first field which differs from the other identity, if there is one.
+    fn difference(&self, other: &SystemIdentity) -> Option<String> {
+        let describe = |field: Option<&FieldIdentity>| {
+            field.map_or(String::from("no field"), |field| format!("'{}'", field))
+        };
+        [
+            ("state", &self.state_fields, &other.state_fields),
+            ("input", &self.input_fields, &other.input_fields),
+            ("param", &self.param_fields, &other.param_fields),
+        ]
+        .into_iter()
+        .find_map(|(kind, fields, other_fields)| {
+            (0..fields.len().max(other_fields.len())).find_map(|index| {
+                let (field, other_field) = (fields.get(index), other_fields.get(index));
+                (field != other_field).then(|| {
+                    format!(
+                        "{} instead of {} among the {} fields",
+                        describe(field),
+                        describe(other_field),
+                        kind
+                    )
+                })
+            })
+        })
+    }
+}
+
+impl Display for FieldIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.name)?;
+        match (self.index_width, self.signedness) {
+            (Some(index_width), _) => {
+                write!(f, "BitvectorArray<{}, {}>", index_width, self.bit_width)
+            }
+            (None, Signedness::None) => write!(f, "Bitvector<{}>", self.bit_width),
+            (None, Signedness::Unsigned) => write!(f, "Unsigned<{}>", self.bit_width),
+            (None, Signedness::Signed) => write!(f, "Signed<{}>", self.bit_width),
+        }
+    }
+}
+
+/// An action changing the work state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) enum CheckpointAction {
+    /// The work state was started from scratch with the given tracking.
+    Retrack(EntryTracking),
+    /// The whole state space was regenerated.
+    Regenerate,
+    /// The precision was refined on the culprit, followed by garbage collection.
+    Refine(Culprit),
+    /// The state space was made compact, retaining the culprit.
+    Compact(Option<Culprit>),
+}
+
+/// Periodic saving of the checkpoints.
+pub(super) struct Checkpointing {
+    /// The interval between saving the checkpoints.
+    interval: Duration,
+    /// The time of the last saving, or of setting the checkpointing.
+    last_saved: Instant,
+    /// Saver of the checkpoints.
+    saver: Box<dyn FnMut(Checkpoint) + Send>,
+}
+
+impl<M: FullMachine> Framework<M> {
+    /// Saves the checkpoint of the current work state.
+    pub fn save_checkpoint(&mut self) -> Checkpoint {
+        Checkpoint {
+            version: CHECKPOINT_VERSION,
+            system: SystemIdentity::of::<M>(),
+            naive_inputs: self.naive_inputs,
+            use_decay: self.use_decay,
+            properties: self.verified_properties.clone(),
+            actions: self.actions.clone(),
+            stats: self.work_state.info(),
+        }
+    }
+
+    /// Loads the checkpoint, replacing the current work state.
+    ///
+    /// The checkpoint must have been saved by a framework with the same system and strategy,
+    /// and the properties verified before saving it must be among the given properties.
+    /// The work state is reconstructed by replaying the checkpoint, without notifying the observer.
+    pub fn load_checkpoint(
+        &mut self,
+        checkpoint: &Checkpoint,
+        properties: &[Property],
+    ) -> Result<(), ExecError> {
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(ExecError::CheckpointIncompatible(format!(
+                "saved in format version {}, not {}",
+                checkpoint.version, CHECKPOINT_VERSION
+            )));
+        }
+        if let Some(difference) = checkpoint.system.difference(&SystemIdentity::of::<M>()) {
+            return Err(ExecError::CheckpointIncompatible(format!(
+                "saved for a system with {}",
+                difference
+            )));
+        }
+        if checkpoint.naive_inputs != self.naive_inputs || checkpoint.use_decay != self.use_decay {
+            return Err(ExecError::CheckpointIncompatible(String::from(
+                "saved with a different strategy",
+            )));
+        }
+        if !checkpoint
+            .properties
+            .iter()
+            .all(|property| properties.contains(property))
+        {
+            return Err(ExecError::CheckpointIncompatible(String::from(
+                "saved for a property which is not verified",
+            )));
+        }
+
+        let observer = self.observer.take();
+        let result = self.replay_checkpoint(checkpoint);
+        self.observer = observer;
+        if let Err(err) = result {
+            // do not leave a partially replayed work state
+            self.work_state = WorkState::new(EntryTracking::default());
+            self.verified_properties.clear();
+            self.actions.clear();
+            return Err(err);
+        }
+        self.verified_properties = checkpoint.properties.clone();
+        Ok(())
+    }
+
+    /// Sets periodic saving of the checkpoints while verifying.
+    ///
+    /// The checkpoint is saved at most once per interval, between the verification steps,
+    /// and also when a resource limit is reached so that the verification can be resumed.
+    pub fn set_checkpointing(
+        &mut self,
+        interval: Duration,
+        saver: Box<dyn FnMut(Checkpoint) + Send>,
+    ) {
+        self.checkpointing = Some(Checkpointing {
+            interval,
+            last_saved: Instant::now(),
+            saver,
+        });
+    }
+
+    /// Saves the checkpoint if the interval elapsed or it is forced.
+    pub(super) fn save_checkpoint_if_due(&mut self, force: bool) {
+        let Some(mut checkpointing) = self.checkpointing.take() else {
+            return;
+        };
+        if force || checkpointing.last_saved.elapsed() >= checkpointing.interval {
+            (checkpointing.saver)(self.save_checkpoint());
+            checkpointing.last_saved = Instant::now();
+        }
+        self.checkpointing = Some(checkpointing);
+    }
+
+    /// Starts the work state from scratch with the given tracking, recording it.
+    pub(super) fn retrack(&mut self, entry_tracking: EntryTracking) {
+        self.actions.push(CheckpointAction::Retrack(entry_tracking));
+        self.work_state.retrack(entry_tracking);
+    }
+
+    /// Regenerates the whole state space, recording it.
+    pub(super) fn regenerate_space(&mut self) {
+        self.actions.push(CheckpointAction::Regenerate);
+        self.regenerate(NodeId::ROOT);
+    }
+
+    /// Makes the state space compact, recording it.
+    pub(super) fn compact(&mut self) {
+        self.actions
+            .push(CheckpointAction::Compact(self.work_state.culprit.clone()));
+        self.work_state.make_compact();
+    }
+
+    /// Replays the checkpoint actions on a fresh work state.
+    fn replay_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<(), ExecError> {
+        self.work_state = WorkState::new(EntryTracking::default());
+        self.actions.clear();
+
+        for action in &checkpoint.actions {
+            match action {
+                CheckpointAction::Retrack(entry_tracking) => self.retrack(*entry_tracking),
+                CheckpointAction::Regenerate => self.regenerate_space(),
+                CheckpointAction::Refine(culprit) => {
+                    let space = &self.work_state.space;
+                    if !space.is_valid()
+                        || !culprit
+                            .path
+                            .iter()
+                            .all(|state_id| space.contains_state(*state_id))
+                    {
+                        return Err(ExecError::CheckpointIncompatible(String::from(
+                            "refined culprit not in the state space",
+                        )));
+                    }
+                    self.refine(culprit)?;
+                    self.work_state.garbage_collect();
+                }
+                CheckpointAction::Compact(culprit) => {
+                    self.work_state.culprit = culprit.clone();
+                    self.compact();
+                    self.work_state.culprit = None;
+                }
+            }
+        }
+
+        let stats = self.work_state.info();
+        if stats != checkpoint.stats {
+            return Err(ExecError::CheckpointIncompatible(format!(
+                "replay resulted in {} states and {} refinements instead of {} and {}",
+                stats.num_final_states,
+                stats.num_refinements,
+                checkpoint.stats.num_final_states,
+                checkpoint.stats.num_refinements
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use machine_check_common::property::Subproperty;
+use machine_check_common::{ExecError, NodeId, ParamValuation, StateId};
+use mck::concr::FullMachine;
+
+use super::trace::describe_fields;
+use super::Framework;
+
+/// Format of the exported state space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpaceFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// GraphML.
+    GraphMl,
+}
+
+/// The state space prepared for the export.
+struct ExportedSpace {
+    /// Nodes with their labels.
+    nodes: Vec<(NodeId, String)>,
+    /// Edges with the labels describing their representative inputs.
+    edges: Vec<(NodeId, StateId, String)>,
+    /// Names of the subproperties and their valuations in the states.
+    labellings: Vec<(String, BTreeMap<StateId, ParamValuation>)>,
+}
+
+impl<M: FullMachine> Framework<M> {
+    /// Exports the state space in the given format.
+    ///
+    /// The states are labelled by their field values and the transitions by the inputs
+    /// representing them. The states are also given the valuations of the given subproperties
+    /// as attributes `subproperty0`, `subproperty1` and so on, with the names of the subproperties
+    /// declared for the whole graph. The state space should be made compact first,
+    /// otherwise the states no longer reachable are exported as well.
+    pub fn export_space(
+        &mut self,
+        format: SpaceFormat,
+        subproperties: &[Subproperty],
+    ) -> Result<String, ExecError> {
+        let mut labellings = Vec::new();
+        for subproperty in subproperties {
+            let name = subproperty
+                .display_str()
+                .map(String::from)
+                .unwrap_or_else(|| format!("subproperty {}", subproperty.index()));
+            let (_, labelling) = self.check_subproperty_with_labelling(subproperty)?;
+            labellings.push((name, labelling));
+        }
+
+        // export the nodes in the order of their ids so that the export is stable
+        let space = &self.work_state.space;
+        let mut node_ids: Vec<NodeId> = space.nodes().collect();
+        node_ids.sort();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        for node_id in node_ids {
+            let label = match StateId::try_from(node_id) {
+                Ok(state_id) => {
+                    let state = space.state_data(state_id);
+                    let mut fields = describe_fields(&state.result);
+                    fields.insert(String::from("panic"), state.panic.to_string());
+                    describe_label(&fields)
+                }
+                Err(_) => String::from("root"),
+            };
+            nodes.push((node_id, label));
+            for tail_id in space.direct_successor_iter(node_id) {
+                let input = describe_fields(space.representative_input(node_id, tail_id));
+                edges.push((node_id, tail_id, describe_label(&input)));
+            }
+        }
+
+        let exported = ExportedSpace {
+            nodes,
+            edges,
+            labellings,
+        };
+        Ok(match format {
+            SpaceFormat::Dot => exported.to_dot(),
+            SpaceFormat::GraphMl => exported.to_graphml(),
+        })
+    }
+}
+
+impl ExportedSpace {
+    fn to_dot(&self) -> String {
+        let mut result = String::from("digraph space {\n    node [shape=box];\n");
+        // the subproperty names are not valid identifiers, so they are given by the graph
+        for (index, (name, _)) in self.labellings.iter().enumerate() {
+            writeln!(
+                result,
+                "    graph [subproperty{}={}];",
+                index,
+                dot_string(name)
+            )
+            .unwrap();
+        }
+        for (node_id, label) in &self.nodes {
+            write!(result, "    {} [label={}", node_id, dot_string(label)).unwrap();
+            if let Ok(state_id) = StateId::try_from(*node_id) {
+                for (index, (_, labelling)) in self.labellings.iter().enumerate() {
+                    if let Some(valuation) = labelling.get(&state_id) {
+                        write!(
+                            result,
+                            ", subproperty{}={}",
+                            index,
+                            dot_string(&valuation.to_string())
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+            result.push_str("];\n");
+        }
+        for (head_id, tail_id, label) in &self.edges {
+            writeln!(
+                result,
+                "    {} -> {} [label={}];",
+                head_id,
+                tail_id,
+                dot_string(label)
+            )
+            .unwrap();
+        }
+        result.push_str("}\n");
+        result
+    }
+
+    fn to_graphml(&self) -> String {
+        let mut result = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"all\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        ));
+        for (index, (name, _)) in self.labellings.iter().enumerate() {
+            writeln!(
+                result,
+                "  <key id=\"subproperty{}\" for=\"node\" attr.name=\"{}\" attr.type=\"string\"/>",
+                index,
+                xml_escape(name)
+            )
+            .unwrap();
+        }
+        result.push_str("  <graph id=\"space\" edgedefault=\"directed\">\n");
+        for (node_id, label) in &self.nodes {
+            writeln!(result, "    <node id=\"n{}\">", node_id).unwrap();
+            writeln!(
+                result,
+                "      <data key=\"label\">{}</data>",
+                xml_escape(label)
+            )
+            .unwrap();
+            if let Ok(state_id) = StateId::try_from(*node_id) {
+                for (index, (_, labelling)) in self.labellings.iter().enumerate() {
+                    if let Some(valuation) = labelling.get(&state_id) {
+                        writeln!(
+                            result,
+                            "      <data key=\"subproperty{}\">{}</data>",
+                            index, valuation
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+            result.push_str("    </node>\n");
+        }
+        for (head_id, tail_id, label) in &self.edges {
+            writeln!(
+                result,
+                "    <edge source=\"n{}\" target=\"n{}\">",
+                head_id, tail_id
+            )
+            .unwrap();
+            writeln!(
+                result,
+                "      <data key=\"label\">{}</data>",
+                xml_escape(label)
+            )
+            .unwrap();
+            result.push_str("    </edge>\n");
+        }
+        result.push_str("  </graph>\n</graphml>\n");
+        result
+    }
+}
+
+/// Describes the field values on separate lines.
+fn describe_label(fields: &BTreeMap<String, String>) -> String {
+    fields
+        .iter()
+        .map(|(name, value)| format!("{} = {}", name, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Quotes the string for DOT, with the lines separated by escaped newlines.
+fn dot_string(string: &str) -> String {
+    let escaped = string
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+/// Escapes the string for XML text and attribute values.
+fn xml_escape(string: &str) -> String {
+    string
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[test]
+fn test_dot_string() {
+    assert_eq!(dot_string("root"), r#""root""#);
+    assert_eq!(
+        dot_string("a = \"01\"\nb = \\X"),
+        r#""a = \"01\"\nb = \\X""#
+    );
+}
+
+#[test]
+fn test_xml_escape() {
+    assert_eq!(xml_escape("root"), "root");
+    assert_eq!(
+        xml_escape("AG![a < 1 && b > \"0\"]"),
+        "AG![a &lt; 1 &amp;&amp; b &gt; &quot;0&quot;]"
+    );
+    // already escaped text is escaped again
+    assert_eq!(xml_escape("&lt;"), "&amp;lt;");
+}
@@ -0,0 +1,79 @@
+use std::ops::ControlFlow;
+
+use machine_check_common::check::Conclusion;
+use machine_check_common::{ExecStats, NodeId};
+use mck::concr::FullMachine;
+
+/// Observer of the verification progress of the framework.
+///
+/// The observer is notified after each regeneration, refinement and model-checking.
+/// It must be sendable between threads, as the framework is.
+pub trait VerificationObserver: Send {
+    /// Observes the event, breaking the control flow to request cancellation.
+    ///
+    /// The verification is cancelled before the next refinement,
+    /// returning [`ExecError::Cancelled`](machine_check_common::ExecError::Cancelled).
+    fn observe(&mut self, event: &VerificationEvent) -> ControlFlow<()>;
+}
+
+/// An event of the verification progress.
+///
+/// The statistics are the ones after the event.
+#[derive(Debug, Clone)]
+pub enum VerificationEvent {
+    /// The state space was regenerated from the given node.
+    Regenerated {
+        node_id: NodeId,
+        /// Whether the state space changed.
+        changed: bool,
+        stats: ExecStats,
+    },
+    /// The precision was refined on a culprit until the state space changed.
+    Refined {
+        /// The number of states in the culprit path.
+        culprit_path_length: usize,
+        /// The precisions refined, in the order of refinement.
+        refined: Vec<RefinedPrecision>,
+        stats: ExecStats,
+    },
+    /// The state space was model-checked.
+    ModelChecked {
+        conclusion: Conclusion,
+        stats: ExecStats,
+    },
+}
+
+/// A precision refined in a node of the state space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefinedPrecision {
+    /// The node whose successors are generated with the precision.
+    pub node_id: NodeId,
+    pub kind: PrecisionKind,
+}
+
+/// The kind of the refined precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecisionKind {
+    /// The precision of the inputs of the step from the node.
+    Input,
+    /// The precision of the parameters of the step from the node.
+    Param,
+    /// The precision of the states after the step from the node, i.e. less decay.
+    State,
+}
+
+impl<M: FullMachine> super::Framework<M> {
+    /// Notifies the observer of the event, if there is an observer.
+    ///
+    /// The verification is marked to be cancelled if the observer requests it.
+    pub(super) fn notify(&mut self, event: impl FnOnce(ExecStats) -> VerificationEvent) {
+        let Some(mut observer) = self.observer.take() else {
+            return;
+        };
+        let event = event(self.work_state.info());
+        if observer.observe(&event).is_break() {
+            self.cancelled = true;
+        }
+        self.observer = Some(observer);
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+
+use machine_check_common::check::{ParamClass, Property};
+use machine_check_common::{ExecError, NodeId, Signedness};
+use mck::abstr::{Field, ManipField, Manipulatable};
+use mck::concr::FullMachine;
+
+use super::Framework;
+use crate::model_check::ParamClassesConclusion;
+use crate::space::EntryTracking;
+
+impl<M: FullMachine> Framework<M> {
+    /// Decides the property for each class of parameter values.
+    ///
+    /// Intended to be used when the property was verified to depend on the parameters.
+    /// The parameters are kept along the paths from the initial states and refined
+    /// until the property is known to hold or not to hold for each class.
+    ///
+    /// If the parameters were not kept yet, the verification starts from scratch,
+    /// as the refined precisions belong to the states of the state space without them.
+    pub fn decide_param_classes(
+        &mut self,
+        property: &Property,
+    ) -> Result<Vec<ParamClass>, ExecError> {
+        // the classes could still depend on the parameters if they were not kept
+        // start from scratch if they are not, the states entered with different parameters
+        // are distinct when they are kept, so the refined precisions cannot be carried over
+        let mut required_tracking = EntryTracking::required_by(property);
+        required_tracking.param = true;
+        if !self
+            .work_state
+            .space
+            .entry_tracking()
+            .covers(&required_tracking)
+        {
+            self.retrack(required_tracking);
+        }
+        if !self.verified_properties.contains(property) {
+            self.verified_properties.push(property.clone());
+        }
+
+        loop {
+            if !self.work_state.space.is_valid() {
+                self.regenerate_space();
+            }
+
+            match self
+                .work_state
+                .checker
+                .check_param_classes(&self.work_state.space, property)?
+            {
+                ParamClassesConclusion::Known(classes) => {
+                    let space = &self.work_state.space;
+                    let classes = classes
+                        .into_iter()
+                        .map(|(initial_id, holds)| ValuedClass {
+                            values: class_values(
+                                space.representative_param(NodeId::ROOT, initial_id),
+                            ),
+                            holds,
+                        })
+                        .collect();
+                    return Ok(merge_param_classes(classes)
+                        .into_iter()
+                        .map(ValuedClass::into_param_class)
+                        .collect());
+                }
+                ParamClassesConclusion::Unknown(culprit) => {
+                    let limits = self.check_limits(&culprit);
+                    self.save_checkpoint_if_due(matches!(
+                        limits,
+                        Err(ExecError::ResourceLimit(..))
+                    ));
+                    limits?;
+                    self.refine(&culprit)?;
+                    self.work_state.garbage_collect();
+                }
+            }
+        }
+    }
+}
+
+/// The maximum number of unknown bits above the lowest known bit of a field value
+/// for the value to be described by ranges.
+///
+/// Each combination of these bits gives a separate range.
+const MAX_SPLITTING_BITS: usize = 6;
+
+/// A class of parameter values with the values of each parameter field.
+struct ValuedClass {
+    values: BTreeMap<String, FieldValues>,
+    holds: bool,
+}
+
+impl ValuedClass {
+    fn into_param_class(self) -> ParamClass {
+        ParamClass {
+            param: self
+                .values
+                .into_iter()
+                .map(|(name, values)| (name, values.to_string()))
+                .collect(),
+            holds: self.holds,
+        }
+    }
+}
+
+/// The values of a parameter field within a class.
+#[derive(Clone, PartialEq, Eq)]
+enum FieldValues {
+    /// Disjoint and non-adjacent inclusive ranges, in ascending order.
+    Ranges(Vec<(i128, i128)>),
+    /// The values cannot be described by a few ranges, so they are described as in the state space.
+    Described(String),
+}
+
+impl std::fmt::Display for FieldValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ranges = match self {
+            FieldValues::Ranges(ranges) => ranges,
+            FieldValues::Described(description) => return write!(f, "{}", description),
+        };
+        let ranges = ranges
+            .iter()
+            .map(|(min, max)| {
+                if min == max {
+                    min.to_string()
+                } else {
+                    format!("{}..={}", min, max)
+                }
+            })
+            .collect::<Vec<_>>();
+        if ranges.len() == 1 {
+            write!(f, "{}", ranges[0])
+        } else {
+            write!(f, "{{{}}}", ranges.join(", "))
+        }
+    }
+}
+
+/// Computes the values of the fields of an abstract parameter.
+///
+/// Arrays are given per runs of the same elements, i.e. `name[i..=j]`.
+fn class_values<T: Manipulatable>(param: &T) -> BTreeMap<String, FieldValues> {
+    let mut result = BTreeMap::new();
+    for field_name in T::field_names() {
+        let Some(field) = param.get(field_name) else {
+            continue;
+        };
+        let is_signed = T::signedness(field_name) == Signedness::Signed;
+        let Field::Array(array) = field.description() else {
+            result.insert(field_name.to_string(), field_values(field, is_signed));
+            continue;
+        };
+        let mut run_starts = field
+            .index_run_starts()
+            .expect("Array field should have runs")
+            .into_iter()
+            .peekable();
+        while let Some(start_index) = run_starts.next() {
+            let end_index = match run_starts.peek() {
+                Some(next_index) => next_index - 1,
+                None => low_mask(array.bit_length),
+            };
+            let field_part_name = if start_index == end_index {
+                format!("{}[{}]", field_name, start_index)
+            } else {
+                format!("{}[{}..={}]", field_name, start_index, end_index)
+            };
+            let element = field
+                .index(start_index)
+                .expect("Array run start should be indexable");
+            result.insert(field_part_name, field_values(element, is_signed));
+        }
+    }
+    result
+}
+
+/// Computes the values of an abstract bit-vector.
+///
+/// The known bits give the ranges, which are further restricted by the bounds of the value.
+fn field_values(field: &dyn ManipField, is_signed: bool) -> FieldValues {
+    let described = || match field.description() {
+        Field::Bitvector(bitvector) => FieldValues::Described(bitvector.to_string()),
+        Field::Array(_) => panic!("Array element should be a bit-vector"),
+    };
+    let (Some(bit_width), Some(min), Some(max)) =
+        (field.num_bits(), field.min_unsigned(), field.max_unsigned())
+    else {
+        return described();
+    };
+
+    let mut known_ones = 0u64;
+    let mut unknown_positions = Vec::new();
+    for position in 0..bit_width {
+        let Some(bit) = field.slice(position, position) else {
+            return described();
+        };
+        match (bit.min_unsigned(), bit.max_unsigned()) {
+            (Some(0), Some(0)) => {}
+            (Some(1), Some(1)) => known_ones |= 1 << position,
+            _ => unknown_positions.push(position),
+        }
+    }
+
+    // the lowest unknown bits span each range, the other unknown bits split them
+    let num_spanning = unknown_positions
+        .iter()
+        .enumerate()
+        .take_while(|(index, position)| *index as u32 == **position)
+        .count();
+    let splitting_positions = &unknown_positions[num_spanning..];
+    if splitting_positions.len() > MAX_SPLITTING_BITS {
+        return described();
+    }
+    let span = low_mask(num_spanning as u32);
+
+    let mut ranges = Vec::new();
+    for combination in 0u64..(1 << splitting_positions.len()) {
+        let mut range_min = known_ones;
+        for (index, position) in splitting_positions.iter().enumerate() {
+            if combination & (1 << index) != 0 {
+                range_min |= 1 << position;
+            }
+        }
+        let range_max = (range_min | span).min(max);
+        let range_min = range_min.max(min);
+        if range_min <= range_max {
+            ranges.push((i128::from(range_min), i128::from(range_max)));
+        }
+    }
+
+    if is_signed && bit_width > 0 {
+        // the values with the sign bit set are negative
+        let sign_bit = 1i128 << (bit_width - 1);
+        let modulus = 1i128 << bit_width;
+        ranges = ranges
+            .into_iter()
+            .flat_map(|(range_min, range_max)| {
+                let non_negative =
+                    (range_min < sign_bit).then(|| (range_min, range_max.min(sign_bit - 1)));
+                let negative = (range_max >= sign_bit)
+                    .then(|| (range_min.max(sign_bit) - modulus, range_max - modulus));
+                non_negative.into_iter().chain(negative)
+            })
+            .collect();
+    }
+    FieldValues::Ranges(normalize_ranges(ranges))
+}
+
+/// Returns the mask of the given number of lowest bits.
+fn low_mask(num_bits: u32) -> u64 {
+    u64::MAX.checked_shr(u64::BITS - num_bits).unwrap_or(0)
+}
+
+/// Sorts the ranges and joins the overlapping and adjacent ones.
+fn normalize_ranges(mut ranges: Vec<(i128, i128)>) -> Vec<(i128, i128)> {
+    ranges.sort();
+    let mut result: Vec<(i128, i128)> = Vec::new();
+    for (range_min, range_max) in ranges {
+        match result.last_mut() {
+            Some((_, last_max)) if range_min <= *last_max + 1 => {
+                *last_max = range_max.max(*last_max);
+            }
+            _ => result.push((range_min, range_max)),
+        }
+    }
+    result
+}
+
+/// Merges the classes with the same result that differ in the values of a single field.
+///
+/// The values of the field are joined, so the merged class contains exactly the values
+/// of the original classes.
+fn merge_param_classes(mut classes: Vec<ValuedClass>) -> Vec<ValuedClass> {
+    let mut class_index = 0;
+    while class_index < classes.len() {
+        let merged = (class_index + 1..classes.len()).find_map(|other_index| {
+            let (class, other) = (&classes[class_index], &classes[other_index]);
+            if class.holds != other.holds {
+                return None;
+            }
+            merge_values(&class.values, &other.values).map(|values| (other_index, values))
+        });
+        match merged {
+            Some((other_index, values)) => {
+                // the merged class can be merged further, so do not advance
+                classes.remove(other_index);
+                classes[class_index].values = values;
+            }
+            None => class_index += 1,
+        }
+    }
+    classes
+}
+
+/// Returns the joined field values if they differ in the ranges of at most a single field.
+fn merge_values(
+    a: &BTreeMap<String, FieldValues>,
+    b: &BTreeMap<String, FieldValues>,
+) -> Option<BTreeMap<String, FieldValues>> {
+    if !a.keys().eq(b.keys()) {
+        return None;
+    }
+    let mut differing = a
+        .iter()
+        .zip(b.values())
+        .filter(|((_, a_values), b_values)| a_values != b_values);
+    let Some(((name, a_values), b_values)) = differing.next() else {
+        return Some(a.clone());
+    };
+    if differing.next().is_some() {
+        return None;
+    }
+    let (FieldValues::Ranges(a_ranges), FieldValues::Ranges(b_ranges)) = (a_values, b_values)
+    else {
+        return None;
+    };
+    let mut result = a.clone();
+    result.insert(
+        name.clone(),
+        FieldValues::Ranges(normalize_ranges(
+            a_ranges.iter().chain(b_ranges).copied().collect(),
+        )),
+    );
+    Some(result)
+}
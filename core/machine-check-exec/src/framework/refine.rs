@@ -1,15 +1,21 @@
+use std::collections::VecDeque;
 use std::time::Instant;
 
+use super::checkpoint::CheckpointAction;
+use super::{PrecisionKind, RefinedPrecision, VerificationEvent};
 use crate::{RefinInput, RefinPanicState, RefinParam, RefinState};
 use log::debug;
 use log::log_enabled;
 use log::trace;
 use machine_check_common::check::Culprit;
+use machine_check_common::property::FieldSource;
 use machine_check_common::ExecError;
 use machine_check_common::NodeId;
 use machine_check_common::StateId;
 use mck::concr::FullMachine;
+use mck::misc::MetaEq;
 use mck::refin::Machine as RefinMachine;
+use mck::refin::ManipField;
 use mck::refin::Manipulatable;
 use mck::refin::Refine;
 use mck::refin::{self};
@@ -18,32 +24,96 @@ impl<M: FullMachine> super::Framework<M> {
     /// Refines the precision and the state space given a culprit of unknown verification result.
     pub(super) fn refine(&mut self, culprit: &Culprit) -> Result<(), ExecError> {
         // subrefine bits until the state space changes.
-        while !self.subrefine(culprit)? {}
+        let mut refined = Vec::new();
+        while !self.subrefine(culprit, &mut refined)? {}
         self.work_state.num_refinements += 1;
+        self.actions.push(CheckpointAction::Refine(culprit.clone()));
+        self.notify(|stats| VerificationEvent::Refined {
+            culprit_path_length: culprit.path.len(),
+            refined,
+            stats,
+        });
         Ok(())
     }
 
     /// Refines a single bit. OK result contains whether the state space changed.
-    fn subrefine(&mut self, culprit: &Culprit) -> Result<bool, ExecError> {
+    ///
+    /// The refined precision is added to the given ones.
+    fn subrefine(
+        &mut self,
+        culprit: &Culprit,
+        refined: &mut Vec<RefinedPrecision>,
+    ) -> Result<bool, ExecError> {
         let start_instant = if log_enabled!(log::Level::Debug) {
             Some(Instant::now())
         } else {
             None
         };
+
+        let mut result = self.subrefine_path(&culprit.path, culprit, false, refined);
+        if result.is_none() {
+            // the culprit path cannot be refined further,
+            // refine the initial state the initial fields are unknown in
+            let culprit_state_id = *culprit
+                .path
+                .back()
+                .expect("Culprit path should not be empty");
+            if let Some(initial_state_id) = self
+                .work_state
+                .space
+                .unknown_initial_state(&culprit.atomic_property, culprit_state_id)?
+            {
+                let initial_path = VecDeque::from([initial_state_id]);
+                result = self.subrefine_path(&initial_path, culprit, true, refined);
+            }
+        }
+
+        if let Some(start_instant) = start_instant {
+            debug!(
+                "Refinement #{} took {:?}.",
+                self.work_state.num_refinements,
+                start_instant.elapsed()
+            );
+        }
+
+        // if no refinement can be applied, the verification is incomplete
+        result.ok_or(ExecError::Incomplete)
+    }
+
+    /// Refines a single bit along the path, marking the fields of the culprit in its last state.
+    ///
+    /// The initial fields are marked instead of the others if requested, the path should then
+    /// only contain the initial state. Returns whether the state space changed,
+    /// or none if no refinement can be applied.
+    fn subrefine_path(
+        &mut self,
+        path: &VecDeque<StateId>,
+        culprit: &Culprit,
+        mark_initial: bool,
+        refined: &mut Vec<RefinedPrecision>,
+    ) -> Option<bool> {
         // compute marking
         let mut current_state_mark = RefinPanicState::<M>::clean();
+        // the input and parameter fields are the ones of the step entering the culprit state
+        let mut entry_input_mark = RefinInput::<M>::clean();
+        let mut entry_param_mark = RefinParam::<M>::clean();
+
+        // mark the fields on both sides of the atomic property
+        for field in culprit.atomic_property.fields() {
+            if (field.source() == FieldSource::Init) != mark_initial {
+                continue;
+            }
+            let manip_mark: &mut dyn ManipField = match field.source() {
+                FieldSource::Panic => Some(&mut current_state_mark.panic as &mut dyn ManipField),
+                FieldSource::State | FieldSource::Init => {
+                    current_state_mark.result.get_mut(field.name())
+                }
+                FieldSource::Input => entry_input_mark.get_mut(field.name()),
+                FieldSource::Param => entry_param_mark.get_mut(field.name()),
+            }
+            .expect("Culprit mark should be manipulatable");
 
-        // TODO: rework panic name kludge
-        if culprit.atomic_property.left().name() == "__panic" {
-            current_state_mark.panic = refin::PanicBitvector::dirty();
-        } else {
-            // TODO: mark more adequately
-            let manip_mark = current_state_mark
-                .result
-                .get_mut(culprit.atomic_property.left().name())
-                .expect("Culprit mark should be manipulatable");
-
-            let manip_mark = if let Some(index) = culprit.atomic_property.left().index() {
+            let manip_mark = if let Some(index) = field.index() {
                 let Some(indexed_manip_mark) = manip_mark.index_mut(index) else {
                     panic!("Indexed culprit mark should be indexable");
                 };
@@ -51,15 +121,25 @@ impl<M: FullMachine> super::Framework<M> {
             } else {
                 manip_mark
             };
-            manip_mark.mark();
+            // TODO: mark more adequately
+            if let Some(slice) = field.slice() {
+                // only the sliced bits matter
+                manip_mark.mark_bits(slice.lowest_bit(), slice.highest_bit());
+            } else {
+                manip_mark.mark();
+            }
         }
 
         // try increasing precision of the state preceding current mark
-        let mut iter = culprit.path.iter().cloned().rev().peekable();
+        let mut iter = path.iter().cloned().rev().peekable();
 
         // store the input precision refinements so that the oldest input can be refined first
         let mut candidate_refinement: Option<RefinCandidate<M>> = None;
 
+        // nodes where the marks were already in the precision, but their successors
+        // may have been generated before the precision was raised
+        let mut stale_node_ids = Vec::new();
+
         while let Some(current_state_id) = iter.next() {
             let previous_node_id = match iter.peek() {
                 Some(previous_state_id) => (*previous_state_id).into(),
@@ -81,8 +161,12 @@ impl<M: FullMachine> super::Framework<M> {
                     step_precision,
                     &self.default_step_precision,
                 );
+                refined.push(RefinedPrecision {
+                    node_id: previous_node_id,
+                    kind: PrecisionKind::State,
+                });
 
-                return Ok(self.regenerate(previous_node_id));
+                return Some(self.regenerate(previous_node_id));
             }
 
             let mut input_precision = self.work_state.input_precision.get(
@@ -97,16 +181,38 @@ impl<M: FullMachine> super::Framework<M> {
                 &self.default_param_precision,
             );
 
-            let (input_mark, param_mark, new_state_mark) =
+            let (mut input_mark, mut param_mark, new_state_mark) =
                 self.compute_marks(previous_node_id, current_state_id, current_state_mark);
 
+            // the parameter of a state is kept from its entry if tracked, so it cannot be refined
+            let param_refinable = match StateId::try_from(previous_node_id) {
+                Ok(previous_state_id) => self
+                    .work_state
+                    .space
+                    .entry_param(previous_state_id)
+                    .is_none(),
+                Err(_) => true,
+            };
+
+            // the entry input mark only applies to the step entering the culprit state
+            input_mark.apply_join(&entry_input_mark);
+            entry_input_mark = RefinInput::<M>::clean();
+            // the param mark is carried back until the parameter can be refined
+            param_mark.apply_join(&entry_param_mark);
+            entry_param_mark = if param_refinable {
+                RefinParam::<M>::clean()
+            } else {
+                param_mark.clone()
+            };
+
             // refinement can be applied to input or param precision
             // we will replace the refinement if either there has been no refinement previously
             // or the current importance is equal or greater to the previous one
             // i.e. we prefer to refine the earliest state possible when the importance is equal
             // we will also prefer refining inputs to parameters
 
-            if param_precision.apply_refin(&param_mark) {
+            let param_applied = param_refinable && param_precision.apply_refin(&param_mark);
+            if param_applied {
                 let candidate_importance = candidate_refinement
                     .as_ref()
                     .map(|candidate| candidate.importance())
@@ -118,7 +224,8 @@ impl<M: FullMachine> super::Framework<M> {
                 }
             }
 
-            if input_precision.apply_refin(&input_mark) {
+            let input_applied = input_precision.apply_refin(&input_mark);
+            if input_applied {
                 let candidate_importance = candidate_refinement
                     .as_ref()
                     .map(|candidate| candidate.importance())
@@ -130,6 +237,12 @@ impl<M: FullMachine> super::Framework<M> {
                 }
             }
 
+            let marked = !input_mark.meta_eq(&RefinInput::<M>::clean())
+                || (param_refinable && !param_mark.meta_eq(&RefinParam::<M>::clean()));
+            if marked && !input_applied && !param_applied {
+                stale_node_ids.push(previous_node_id);
+            }
+
             // mark not applied, continue iteration
             if let Some(new_state_mark) = new_state_mark {
                 // update current state mark
@@ -146,7 +259,7 @@ impl<M: FullMachine> super::Framework<M> {
         }
 
         // if there is an input/parameter precision refinement candidate, apply it
-        let result = match candidate_refinement {
+        match candidate_refinement {
             Some(RefinCandidate::Input(node_id, refined_input_precision)) => {
                 // single mark applied, insert it back and regenerate
                 self.work_state.input_precision.insert(
@@ -155,8 +268,12 @@ impl<M: FullMachine> super::Framework<M> {
                     refined_input_precision,
                     &self.default_input_precision,
                 );
+                refined.push(RefinedPrecision {
+                    node_id,
+                    kind: PrecisionKind::Input,
+                });
 
-                Ok(self.regenerate(node_id))
+                Some(self.regenerate(node_id))
             }
             Some(RefinCandidate::Param(node_id, refined_param_precision)) => {
                 // single mark applied, insert it back and regenerate
@@ -166,24 +283,26 @@ impl<M: FullMachine> super::Framework<M> {
                     refined_param_precision,
                     &self.default_param_precision,
                 );
+                refined.push(RefinedPrecision {
+                    node_id,
+                    kind: PrecisionKind::Param,
+                });
 
-                Ok(self.regenerate(node_id))
+                Some(self.regenerate(node_id))
             }
             None => {
-                // cannot apply any refinement, verification incomplete
-                Err(ExecError::Incomplete)
+                // the precision of the nodes may have been raised by refining the states covering them
+                // after their successors were generated, so regenerate them
+                // only the steps from the stale nodes and the newly reached states are regenerated,
+                // the rest of the state space and the model-checking results are kept
+                let mut changed = false;
+                for node_id in stale_node_ids {
+                    changed |= self.regenerate(node_id);
+                }
+                // cannot apply any refinement if nothing changed
+                changed.then_some(true)
             }
-        };
-
-        if let Some(start_instant) = start_instant {
-            debug!(
-                "Refinement #{} took {:?}.",
-                self.work_state.num_refinements,
-                start_instant.elapsed()
-            );
         }
-
-        result
     }
 
     fn compute_marks(
@@ -8,10 +8,25 @@ use mck::concr::FullMachine;
 use mck::misc::Meta;
 use mck::refin::Refine;
 
-use crate::{RefinInput, RefinPanicState, RefinParam};
+use super::VerificationEvent;
+use crate::{AbstrInput, AbstrPanicState, AbstrParam, RefinInput, RefinPanicState, RefinParam};
+
+/// The successor generation from a single node.
+struct StepJob<M: FullMachine> {
+    node_id: NodeId,
+    /// The current state, none if we are at start node.
+    current_state: Option<AbstrPanicState<M>>,
+    params: Vec<AbstrParam<M>>,
+    inputs: Vec<AbstrInput<M>>,
+    step_precision: RefinPanicState<M>,
+}
 
 impl<M: FullMachine> super::Framework<M> {
     /// Regenerates the state space from a given node, keeping its other parts. Returns whether the state space changed.
+    ///
+    /// The successors of the nodes in the breadth-first search frontier are computed in parallel
+    /// if the strategy allows multiple threads. They are added to the state space in the same order
+    /// as if computed one by one, so the resulting state space and state ids do not depend on it.
     pub(super) fn regenerate(&mut self, from_node_id: NodeId) -> bool {
         let mut queue = VecDeque::new();
 
@@ -24,131 +39,87 @@ impl<M: FullMachine> super::Framework<M> {
         let mut new_states = BTreeSet::new();
         let mut changed_successors = BTreeSet::new();
 
-        // construct state space by breadth-first search
-        while let Some(node_id) = queue.pop_front() {
-            // if it has already been processed, continue
-            let current_has_direct_successor = self
-                .work_state
-                .space
-                .direct_successor_iter(node_id)
-                .next()
-                .is_some();
-            if current_has_direct_successor {
-                continue;
-            }
+        // construct state space by breadth-first search, a frontier at a time
+        while !queue.is_empty() {
+            let jobs: Vec<StepJob<M>> = queue
+                .drain(..)
+                .filter(|node_id| !self.has_direct_successor(*node_id))
+                .map(|node_id| self.prepare_step_job(node_id))
+                .collect();
+            let mut next_states = self.compute_next_states(&jobs).into_iter();
 
-            self.work_state.num_generated_states += 1;
-            // remove outgoing edges
-            let (removed_direct_successors, removed_tail_partition) =
-                self.work_state.space.clear_step(node_id);
-
-            // prepare precision
-            let input_precision: RefinInput<M> = self.work_state.input_precision.get(
-                &self.work_state.space,
-                node_id,
-                &self.default_input_precision,
-            );
-            let param_precision: RefinParam<M> = self.work_state.param_precision.get(
-                &self.work_state.space,
-                node_id,
-                &self.default_param_precision,
-            );
-            let step_precision: RefinPanicState<M> = self.work_state.step_precision.get(
-                &self.work_state.space,
-                node_id,
-                &self.default_step_precision,
-            );
-
-            // get current state, none if we are at start node
-            let current_state = if let Ok(state_id) = StateId::try_from(node_id) {
-                Some(self.work_state.space.state_data(state_id).clone())
-            } else {
-                None
-            };
-
-            // generate direct successors
-
-            for param in param_precision.into_proto_iter() {
-                let mut param_id = None;
-
-                for input in input_precision.clone().into_proto_iter() {
-                    // compute the next state
-                    let mut next_state = {
-                        if let Some(current_state) = &current_state {
-                            M::Abstr::next(
-                                &self.abstract_system,
-                                &current_state.result,
-                                &input,
-                                &param,
-                            )
-                        } else {
-                            M::Abstr::init(&self.abstract_system, &input, &param)
-                        }
-                    };
-                    log::trace!(
-                        "Next state from {:?} with param {:?} and input {:?} is {:?}",
-                        current_state,
-                        param,
-                        input,
-                        next_state
-                    );
-
-                    // apply decay
-                    step_precision.force_decay(&mut next_state);
-
-                    // add the step to the state space
-                    self.work_state.num_generated_transitions += 1;
-                    let (next_state_index, inserted, added_param_id) = self
-                        .work_state
-                        .space
-                        .add_step(node_id, next_state, &input, &param, param_id);
-
-                    param_id = Some(added_param_id);
-
-                    if inserted {
-                        new_states.insert(next_state_index);
+            for job in jobs {
+                let node_id = job.node_id;
+                // if it has already been processed in this frontier, continue
+                if self.has_direct_successor(node_id) {
+                    // skip the successors computed for the duplicate
+                    for _ in 0..job.params.len() * job.inputs.len() {
+                        next_states.next();
                     }
+                    continue;
+                }
+
+                self.work_state.num_generated_states += 1;
+                // remove outgoing edges
+                let (removed_direct_successors, removed_tail_partition) =
+                    self.work_state.space.clear_step(node_id);
+
+                // add the direct successors
+                for param in &job.params {
+                    let mut param_id = None;
+
+                    for input in &job.inputs {
+                        let next_state = next_states
+                            .next()
+                            .expect("Next state should be computed for each input and param");
+
+                        // add the step to the state space
+                        self.work_state.num_generated_transitions += 1;
+                        let (next_state_index, inserted, added_param_id) = self
+                            .work_state
+                            .space
+                            .add_step(node_id, next_state, input, param, param_id);
+
+                        param_id = Some(added_param_id);
 
-                    // add the tail to the queue if it has no direct successors yet
-                    let next_has_direct_successor = self
-                        .work_state
-                        .space
-                        .direct_successor_iter(next_state_index.into())
-                        .next()
-                        .is_some();
-
-                    if !next_has_direct_successor {
-                        // add to queue
-                        queue.push_back(next_state_index.into());
+                        if inserted {
+                            new_states.insert(next_state_index);
+                        }
+
+                        // add the tail to the queue if it has no direct successors yet
+                        if !self.has_direct_successor(next_state_index.into()) {
+                            // add to queue
+                            queue.push_back(next_state_index.into());
+                        }
                     }
                 }
-            }
 
-            // compare sets of node ids
-            let direct_successors: BTreeSet<StateId> = self
-                .work_state
-                .space
-                .direct_successor_iter(node_id)
-                .collect();
+                // compare sets of node ids
+                let direct_successors: BTreeSet<StateId> = self
+                    .work_state
+                    .space
+                    .direct_successor_iter(node_id)
+                    .collect();
 
-            let node_changed = direct_successors != removed_direct_successors
-                || removed_tail_partition.as_ref()
-                    != self
-                        .work_state
-                        .space
-                        .direct_successor_param_partition(node_id);
+                let node_changed = direct_successors != removed_direct_successors
+                    || removed_tail_partition.as_ref()
+                        != self
+                            .work_state
+                            .space
+                            .direct_successor_param_partition(node_id);
 
-            if node_changed {
-                if let Ok(state_id) = StateId::try_from(node_id) {
-                    changed_successors.insert(state_id);
+                if node_changed {
+                    if let Ok(state_id) = StateId::try_from(node_id) {
+                        changed_successors.insert(state_id);
+                    }
                 }
-            }
 
-            // make sure changed is true if the target nodes are different from the removed ones
-            // ignore the edges changing, currently only used for representative inputs
-            // which has no impact on verification
-            if !something_changed {
-                something_changed = node_changed;
+                // make sure changed is true if the target nodes are different from the removed ones
+                // ignore the edges changing, currently only used for representative inputs
+                // which has no impact on verification
+                if !something_changed {
+                    something_changed = node_changed;
+                }
             }
         }
 
@@ -162,6 +133,142 @@ impl<M: FullMachine> super::Framework<M> {
         // Assert it to be sure.
         self.space().assert_left_total();
 
+        self.notify(|stats| VerificationEvent::Regenerated {
+            node_id: from_node_id,
+            changed: something_changed,
+            stats,
+        });
         something_changed
     }
+
+    fn has_direct_successor(&self, node_id: NodeId) -> bool {
+        self.work_state
+            .space
+            .direct_successor_iter(node_id)
+            .next()
+            .is_some()
+    }
+
+    /// Prepares the successor generation from the node.
+    fn prepare_step_job(&self, node_id: NodeId) -> StepJob<M> {
+        // prepare precision
+        let input_precision: RefinInput<M> = self.work_state.input_precision.get(
+            &self.work_state.space,
+            node_id,
+            &self.default_input_precision,
+        );
+        let param_precision: RefinParam<M> = self.work_state.param_precision.get(
+            &self.work_state.space,
+            node_id,
+            &self.default_param_precision,
+        );
+        let step_precision: RefinPanicState<M> = self.work_state.step_precision.get(
+            &self.work_state.space,
+            node_id,
+            &self.default_step_precision,
+        );
+
+        // get current state, none if we are at start node
+        let current_state = if let Ok(state_id) = StateId::try_from(node_id) {
+            Some(self.work_state.space.state_data(state_id).clone())
+        } else {
+            None
+        };
+
+        // the parameter does not change during the run
+        // if the parameter entering the state is tracked, keep it
+        let entry_param = StateId::try_from(node_id)
+            .ok()
+            .and_then(|state_id| self.work_state.space.entry_param(state_id))
+            .cloned();
+        let params: Vec<AbstrParam<M>> = match entry_param {
+            Some(entry_param) => vec![entry_param],
+            None => param_precision.into_proto_iter().collect(),
+        };
+
+        StepJob {
+            node_id,
+            current_state,
+            params,
+            inputs: input_precision.into_proto_iter().collect(),
+            step_precision,
+        }
+    }
+
+    /// Computes the next states of the jobs for each param and input, in this order.
+    ///
+    /// The computation is split evenly between the threads of the strategy.
+    fn compute_next_states(&self, jobs: &[StepJob<M>]) -> Vec<AbstrPanicState<M>> {
+        let steps: Vec<(&StepJob<M>, &AbstrParam<M>, &AbstrInput<M>)> = jobs
+            .iter()
+            .flat_map(|job| {
+                job.params
+                    .iter()
+                    .flat_map(move |param| job.inputs.iter().map(move |input| (job, param, input)))
+            })
+            .collect();
+
+        // only the system is shared between the threads
+        let abstract_system = &self.abstract_system;
+        let num_threads = self.num_threads.min(steps.len()).max(1);
+        if num_threads == 1 {
+            return steps
+                .into_iter()
+                .map(|(job, param, input)| compute_next_state(abstract_system, job, param, input))
+                .collect();
+        }
+
+        let chunk_size = steps.len().div_ceil(num_threads);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = steps
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(job, param, input)| {
+                                compute_next_state(abstract_system, job, param, input)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            // join in the order of the chunks to keep the order of the steps
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .expect("Next state computation thread should not panic")
+                })
+                .collect()
+        })
+    }
+}
+
+fn compute_next_state<M: FullMachine>(
+    abstract_system: &M::Abstr,
+    job: &StepJob<M>,
+    param: &AbstrParam<M>,
+    input: &AbstrInput<M>,
+) -> AbstrPanicState<M> {
+    // compute the next state
+    let mut next_state = {
+        if let Some(current_state) = &job.current_state {
+            M::Abstr::next(abstract_system, &current_state.result, input, param)
+        } else {
+            M::Abstr::init(abstract_system, input, param)
+        }
+    };
+    log::trace!(
+        "Next state from {:?} with param {:?} and input {:?} is {:?}",
+        job.current_state,
+        param,
+        input,
+        next_state
+    );
+
+    // apply decay
+    job.step_precision.force_decay(&mut next_state);
+    next_state
 }
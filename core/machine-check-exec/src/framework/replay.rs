@@ -0,0 +1,293 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use machine_check_common::check::{Replay, ReplayStep, ReplayVerdict, Trace};
+use machine_check_common::{NodeId, StateId};
+use mck::abstr::{Abstr, PanicBitvector, PanicResult, Phi};
+use mck::concr::{self, FullMachine};
+use mck::misc::MetaEq;
+
+use super::trace::describe_fields;
+use super::Framework;
+use crate::{AbstrInput, AbstrParam, AbstrState};
+
+/// Maximum number of loop unrollings when trying to close a lasso concretely.
+const MAX_LOOP_UNROLLINGS: usize = 1024;
+
+/// Installation of the panic hook not printing the caught concrete panics.
+static QUIET_PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    /// Whether the thread is running the concrete system and catching its panics.
+    static CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+impl<M: FullMachine> Framework<M> {
+    /// Replays the trace on the concrete system.
+    ///
+    /// Concrete inputs and parameter are chosen from the representative abstract ones
+    /// and the concrete system is run on them. The replay is confirmed if the concrete
+    /// states stay within the abstract states of the trace and the concrete run panics
+    /// exactly where the trace does. As the values of the abstract states decide
+    /// the property, the concrete run then reproduces the result.
+    ///
+    /// The parameter does not change during the run, but the representative parameters
+    /// of the transitions may be refined differently along the trace. The parameters
+    /// chosen from each of them are tried in order until the replay is not spurious.
+    ///
+    /// If the trace is a lasso, the loop is unrolled until the concrete run repeats a state.
+    /// The replay is inconclusive if it does not repeat a state within the maximum number
+    /// of unrollings.
+    pub fn replay_trace(&self, trace: &Trace) -> Replay {
+        let space = &self.work_state.space;
+        let mut params: Vec<M::Param> = Vec::new();
+        let mut head_id = NodeId::ROOT;
+        for trace_step in &trace.steps {
+            let param = space
+                .representative_param(head_id, trace_step.state_id)
+                .concrete_choice();
+            if !params.contains(&param) {
+                params.push(param);
+            }
+            head_id = trace_step.state_id.into();
+        }
+
+        let mut first_replay = None;
+        for param in params {
+            let replay = self.replay_with_param(trace, param);
+            if !matches!(replay.verdict, ReplayVerdict::Spurious { .. }) {
+                return replay;
+            }
+            first_replay.get_or_insert(replay);
+        }
+        first_replay.unwrap_or_else(|| Replay {
+            param: BTreeMap::new(),
+            steps: Vec::new(),
+            verdict: ReplayVerdict::Confirmed,
+        })
+    }
+
+    /// Replays the trace on the concrete system with the given parameter.
+    fn replay_with_param(&self, trace: &Trace, param: M::Param) -> Replay {
+        let system: M = self.abstract_system.concrete_choice();
+        let param_description = describe_fields(&AbstrParam::<M>::from_concrete(param.clone()));
+
+        let mut replayer = Replayer {
+            framework: self,
+            system,
+            param,
+            current: None,
+        };
+
+        let mut steps = Vec::new();
+        let mut head_id = NodeId::ROOT;
+        let mut loop_start_state = None;
+        for (step_index, trace_step) in trace.steps.iter().enumerate() {
+            let is_last = step_index + 1 == trace.steps.len() && trace.loop_start.is_none();
+            let (replay_step, spurious_reason) =
+                replayer.step(head_id, trace_step.state_id, is_last);
+            steps.push(replay_step);
+            if let Some(reason) = spurious_reason {
+                return Replay {
+                    param: param_description,
+                    steps,
+                    verdict: ReplayVerdict::Spurious {
+                        step: step_index,
+                        reason,
+                    },
+                };
+            }
+            if trace.loop_start == Some(step_index) {
+                loop_start_state = replayer.current.clone();
+            }
+            head_id = trace_step.state_id.into();
+        }
+
+        let verdict = match (trace.loop_start, loop_start_state) {
+            (Some(loop_start), Some(loop_start_state)) => {
+                replayer.close_loop(trace, loop_start, loop_start_state)
+            }
+            _ => ReplayVerdict::Confirmed,
+        };
+
+        Replay {
+            param: param_description,
+            steps,
+            verdict,
+        }
+    }
+}
+
+struct Replayer<'a, M: FullMachine> {
+    framework: &'a Framework<M>,
+    system: M,
+    param: M::Param,
+    /// The current concrete state, kept in its exact abstract form.
+    current: Option<AbstrState<M>>,
+}
+
+impl<M: FullMachine> Replayer<'_, M> {
+    /// Replays a single step, returning the reason if it does not follow the trace.
+    fn step(
+        &mut self,
+        head_id: NodeId,
+        tail_id: StateId,
+        is_last: bool,
+    ) -> (ReplayStep, Option<String>) {
+        let space = &self.framework.work_state.space;
+        let input: M::Input = space
+            .representative_input(head_id, tail_id)
+            .concrete_choice();
+        let input_description = describe_fields(&AbstrInput::<M>::from_concrete(input.clone()));
+        let abstract_state = space.state_data(tail_id);
+
+        let current = self.current.take().map(|current| current.concrete_choice());
+        let result = catch_concrete_panic(|| match &current {
+            Some(current) => self.system.next(current, &input, &self.param),
+            None => self.system.init(&input, &self.param),
+        });
+
+        match result {
+            Ok(state) => {
+                let exact_state = AbstrState::<M>::from_concrete(state);
+                let replay_step = ReplayStep {
+                    input: input_description,
+                    state: describe_fields(&exact_state),
+                    panic_message: None,
+                };
+                let exact_panic_state = PanicResult {
+                    panic: PanicBitvector::from_concrete(concr::Bitvector::new(0)),
+                    result: exact_state.clone(),
+                };
+                self.current = Some(exact_state);
+                if !contains(abstract_state, &exact_panic_state) {
+                    return (
+                        replay_step,
+                        Some(String::from(
+                            "the concrete state is not within the abstract state",
+                        )),
+                    );
+                }
+                (replay_step, None)
+            }
+            Err(panic_message) => {
+                let abstract_message = abstract_state
+                    .panic
+                    .concrete_value()
+                    .filter(|panic_value| panic_value.is_nonzero())
+                    .map(|panic_value| M::panic_message(panic_value.to_u64() as u32));
+                let reason = if abstract_message != Some(panic_message.as_str()) {
+                    Some(String::from(
+                        "the concrete run panicked differently than the abstract state",
+                    ))
+                } else if !is_last {
+                    Some(String::from(
+                        "the concrete run panicked before the end of the trace",
+                    ))
+                } else {
+                    None
+                };
+                let replay_step = ReplayStep {
+                    input: input_description,
+                    state: BTreeMap::new(),
+                    panic_message: Some(panic_message),
+                };
+                (replay_step, reason)
+            }
+        }
+    }
+
+    /// Unrolls the loop of a lasso trace until the concrete run repeats a state at its start.
+    fn close_loop(
+        &mut self,
+        trace: &Trace,
+        loop_start: usize,
+        loop_start_state: AbstrState<M>,
+    ) -> ReplayVerdict {
+        let loop_ids: Vec<StateId> = trace.steps[loop_start..]
+            .iter()
+            .map(|trace_step| trace_step.state_id)
+            .collect();
+        let last_id = trace
+            .steps
+            .last()
+            .expect("Lasso trace should not be empty")
+            .state_id;
+
+        let mut loop_start_states = vec![loop_start_state];
+
+        for _ in 0..MAX_LOOP_UNROLLINGS {
+            let mut head_id: NodeId = last_id.into();
+            for (loop_index, tail_id) in loop_ids.iter().copied().enumerate() {
+                let (_, spurious_reason) = self.step(head_id, tail_id, false);
+                if let Some(reason) = spurious_reason {
+                    return ReplayVerdict::Spurious {
+                        step: loop_start + loop_index,
+                        reason: format!("{} when unrolling the loop", reason),
+                    };
+                }
+                head_id = tail_id.into();
+
+                if loop_index == 0 {
+                    let current = self
+                        .current
+                        .as_ref()
+                        .expect("Replayed trace should have a current state");
+                    if loop_start_states
+                        .iter()
+                        .any(|previous| previous.meta_eq(current))
+                    {
+                        return ReplayVerdict::Confirmed;
+                    }
+                    loop_start_states.push(current.clone());
+                }
+            }
+        }
+
+        // the concrete run may still repeat a state later
+        ReplayVerdict::Inconclusive {
+            reason: format!(
+                "the concrete run did not repeat a state within {} loop unrollings",
+                MAX_LOOP_UNROLLINGS
+            ),
+        }
+    }
+}
+
+/// Returns whether the abstract value contains the exact one.
+fn contains<T: Phi + MetaEq + Clone>(abstract_state: &T, exact: &T) -> bool {
+    abstract_state
+        .clone()
+        .phi(exact.clone())
+        .meta_eq(abstract_state)
+}
+
+/// Runs the concrete system function, catching the panic message if it panics.
+fn catch_concrete_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    // do not print the expected panics; the hook is installed only once for the whole process
+    // and passes the panics not caught here, e.g. from other threads, to the previous hook
+    QUIET_PANIC_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANIC.with(Cell::get) {
+                previous_hook(info);
+            }
+        }));
+    });
+    CATCHING_PANIC.with(|catching| catching.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING_PANIC.with(|catching| catching.set(false));
+
+    result.map_err(|payload: Box<dyn Any + Send>| {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            String::from(*message)
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("unknown panic")
+        }
+    })
+}
@@ -0,0 +1,266 @@
+use std::collections::{btree_map::Entry, BTreeMap, BTreeSet, VecDeque};
+
+use log::info;
+use machine_check_common::check::{Property, Trace};
+use machine_check_common::{ExecError, NodeId, ParamValuation, StateId};
+use mck::concr::FullMachine;
+
+use super::Framework;
+use crate::space::StateSpace;
+
+impl<M: FullMachine> Framework<M> {
+    /// Computes the number of steps until the formula is reached from the initial states.
+    ///
+    /// If universal, the maximum number of steps is computed, i.e. the smallest bound within
+    /// which the formula is reached on all paths. Otherwise, the minimum number of steps
+    /// is computed, i.e. the smallest bound within which the formula is reached on some path.
+    /// The number must hold for all of the initial states.
+    ///
+    /// The reachability of the formula, i.e. `AF![formula]` or `EF![formula]`, must have been
+    /// verified to hold beforehand. The numbers of steps are computed directly over the state
+    /// space, which is refined until the states where the formula is unknown do not matter.
+    /// The states where the formula depends on the parameters are considered not to reach it,
+    /// so that the number holds for all parameter values.
+    ///
+    /// The returned trace shows a path on which the formula is reached after the number of steps.
+    pub fn steps_until(
+        &mut self,
+        formula: &Property,
+        is_universal: bool,
+    ) -> Result<(u64, Trace), ExecError> {
+        let result = loop {
+            let (_, labelling) =
+                self.check_subproperty_with_labelling(&formula.root_subproperty())?;
+            let space = &self.work_state.space;
+
+            // the formula may be reached in the unknown states, which gives the lower bound
+            let lower = StepDistances::compute(space, is_universal, |state_id| {
+                labelling[&state_id] != ParamValuation::False
+            });
+            let upper = StepDistances::compute(space, is_universal, |state_id| {
+                labelling[&state_id] == ParamValuation::True
+            });
+            let (lower_steps, upper_steps) = (lower.initial_max(space), upper.initial_max(space));
+            info!(
+                "The formula is reached within {:?} to {:?} steps.",
+                lower_steps, upper_steps
+            );
+            if let Some(steps) = upper_steps {
+                if lower_steps == upper_steps {
+                    break Ok((steps, upper.path(space, is_universal)));
+                }
+            }
+
+            // refine the first unknown state visited before the formula is reached
+            let Some(mut path) = unknown_path(space, &labelling) else {
+                // only the states where the formula depends on the parameters remain
+                break match upper_steps {
+                    Some(steps) => Ok((steps, upper.path(space, is_universal))),
+                    None => Err(ExecError::Incomplete),
+                };
+            };
+            let unknown_id = path.pop_back().expect("Unknown path should not be empty");
+            let mut culprit = self
+                .work_state
+                .checker
+                .deduce_culprit_in(space, formula, unknown_id)?;
+            // the culprit must start in an initial state
+            for state_id in path.into_iter().rev() {
+                culprit.path.push_front(state_id);
+            }
+
+            self.check_limits(&culprit)?;
+            self.refine(&culprit)?;
+            self.work_state.garbage_collect();
+        };
+
+        // make compact after the computation for nice state space information,
+        // the path only visits the reachable states which are retained
+        self.compact();
+        let (steps, path) = result?;
+        Ok((steps, self.path_trace(path, None)))
+    }
+}
+
+/// The numbers of steps until the formula is reached from the states.
+///
+/// The states which do not reach the formula are not present.
+struct StepDistances {
+    distances: BTreeMap<StateId, u64>,
+}
+
+impl StepDistances {
+    fn compute<M: FullMachine>(
+        space: &StateSpace<M>,
+        is_universal: bool,
+        reached: impl Fn(StateId) -> bool,
+    ) -> Self {
+        if is_universal {
+            Self::compute_longest(space, reached)
+        } else {
+            Self::compute_shortest(space, reached)
+        }
+    }
+
+    /// Computes the smallest numbers of steps within which the formula is reached on some path.
+    ///
+    /// The states are visited backwards from the ones where the formula is reached.
+    fn compute_shortest<M: FullMachine>(
+        space: &StateSpace<M>,
+        reached: impl Fn(StateId) -> bool,
+    ) -> Self {
+        let mut distances = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        for state_id in space.states() {
+            if reached(state_id) {
+                distances.insert(state_id, 0);
+                queue.push_back(state_id);
+            }
+        }
+        while let Some(state_id) = queue.pop_front() {
+            let distance = distances[&state_id] + 1;
+            for predecessor_id in space.direct_predecessor_iter(state_id.into()) {
+                let Ok(predecessor_id) = StateId::try_from(predecessor_id) else {
+                    continue;
+                };
+                if let Entry::Vacant(entry) = distances.entry(predecessor_id) {
+                    entry.insert(distance);
+                    queue.push_back(predecessor_id);
+                }
+            }
+        }
+        StepDistances { distances }
+    }
+
+    /// Computes the smallest numbers of steps within which the formula is reached on all paths.
+    ///
+    /// The formula is not reached from the states that can loop before reaching it.
+    /// A state without successors reaches it within a single step, as there is no path
+    /// where it is not reached.
+    fn compute_longest<M: FullMachine>(
+        space: &StateSpace<M>,
+        reached: impl Fn(StateId) -> bool,
+    ) -> Self {
+        // the distances are computed in postorder, the states without distance do not reach
+        let mut distances: BTreeMap<StateId, Option<u64>> = BTreeMap::new();
+        for start_id in space.initial_iter() {
+            if distances.contains_key(&start_id) {
+                continue;
+            }
+            // the states being visited are on the stack and do not have a distance yet
+            let mut on_stack = BTreeSet::new();
+            let mut stack = vec![(start_id, false)];
+            while let Some((state_id, expanded)) = stack.pop() {
+                if reached(state_id) {
+                    distances.insert(state_id, Some(0));
+                    continue;
+                }
+                if expanded {
+                    on_stack.remove(&state_id);
+                    let mut distance = Some(1);
+                    for successor_id in space.direct_successor_iter(state_id.into()) {
+                        distance = distance
+                            .zip(distances[&successor_id])
+                            .map(|(distance, successor)| distance.max(successor + 1));
+                    }
+                    distances.insert(state_id, distance);
+                    continue;
+                }
+                if distances.contains_key(&state_id) {
+                    continue;
+                }
+                on_stack.insert(state_id);
+                stack.push((state_id, true));
+                for successor_id in space.direct_successor_iter(state_id.into()) {
+                    if on_stack.contains(&successor_id) {
+                        // a loop without reaching the formula
+                        distances.insert(successor_id, None);
+                    } else if !distances.contains_key(&successor_id) {
+                        stack.push((successor_id, false));
+                    }
+                }
+            }
+        }
+        StepDistances {
+            distances: distances
+                .into_iter()
+                .filter_map(|(state_id, distance)| Some((state_id, distance?)))
+                .collect(),
+        }
+    }
+
+    /// Returns the number of steps within which the formula is reached from all initial states,
+    /// or none if it is not reached from some of them.
+    fn initial_max<M: FullMachine>(&self, space: &StateSpace<M>) -> Option<u64> {
+        let mut result = 0;
+        for initial_id in space.initial_iter() {
+            result = result.max(*self.distances.get(&initial_id)?);
+        }
+        Some(result)
+    }
+
+    /// Returns a path from the initial state with the most steps,
+    /// on which the formula is reached after the computed number of steps.
+    ///
+    /// The formula must be reached from all of the initial states.
+    fn path<M: FullMachine>(&self, space: &StateSpace<M>, is_universal: bool) -> Vec<StateId> {
+        let mut state_id = space
+            .initial_iter()
+            .max_by_key(|initial_id| self.distances[initial_id])
+            .expect("Initial states should reach the formula");
+        let mut path = vec![state_id];
+        while self.distances[&state_id] > 0 {
+            let successors = space
+                .direct_successor_iter(state_id.into())
+                .filter(|successor_id| self.distances.contains_key(successor_id));
+            let next_id = if is_universal {
+                successors.max_by_key(|successor_id| self.distances[successor_id])
+            } else {
+                successors.min_by_key(|successor_id| self.distances[successor_id])
+            };
+            // a state without successors reaches the formula within a single step
+            let Some(next_id) = next_id else {
+                break;
+            };
+            state_id = next_id;
+            path.push(state_id);
+        }
+        path
+    }
+}
+
+/// Returns a path from an initial state to a state where the formula is unknown,
+/// visiting only the states where it is not known to be reached.
+fn unknown_path<M: FullMachine>(
+    space: &StateSpace<M>,
+    labelling: &BTreeMap<StateId, ParamValuation>,
+) -> Option<VecDeque<StateId>> {
+    let mut predecessors: BTreeMap<StateId, NodeId> = BTreeMap::new();
+    let mut queue = VecDeque::new();
+    for initial_id in space.initial_iter() {
+        predecessors.entry(initial_id).or_insert(NodeId::ROOT);
+        queue.push_back(initial_id);
+    }
+    while let Some(state_id) = queue.pop_front() {
+        match labelling[&state_id] {
+            ParamValuation::Unknown => {
+                let mut path = VecDeque::from([state_id]);
+                let mut node_id = predecessors[&state_id];
+                while let Ok(predecessor_id) = StateId::try_from(node_id) {
+                    path.push_front(predecessor_id);
+                    node_id = predecessors[&predecessor_id];
+                }
+                return Some(path);
+            }
+            ParamValuation::True => continue,
+            ParamValuation::False | ParamValuation::Dependent => {}
+        }
+        for successor_id in space.direct_successor_iter(state_id.into()) {
+            if let Entry::Vacant(entry) = predecessors.entry(successor_id) {
+                entry.insert(state_id.into());
+                queue.push_back(successor_id);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use machine_check_common::check::{Property, Trace, TraceStep};
+use machine_check_common::{ExecError, NodeId, StateId};
+use mck::abstr::{BitvectorField, Field, Manipulatable};
+use mck::concr::FullMachine;
+
+use super::Framework;
+
+impl<M: FullMachine> Framework<M> {
+    /// Finds a trace explaining why the previously verified property holds or does not hold.
+    ///
+    /// The trace is a counterexample if the property is not held and a witness if it is.
+    /// Returns none if the property was not verified yet or no explaining trace was found.
+    pub fn find_trace(
+        &mut self,
+        property: &Property,
+        holds: bool,
+    ) -> Result<Option<Trace>, ExecError> {
+        let space = &self.work_state.space;
+        let Some(explanation) = self
+            .work_state
+            .checker
+            .explain_property(space, property, holds)?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(
+            self.path_trace(explanation.path, explanation.loop_start),
+        ))
+    }
+
+    /// Describes the states on a path from the initial states as a trace.
+    pub(super) fn path_trace(
+        &self,
+        path: impl IntoIterator<Item = StateId>,
+        loop_start: Option<usize>,
+    ) -> Trace {
+        let space = &self.work_state.space;
+        let mut steps = Vec::new();
+        let mut head_id = NodeId::ROOT;
+        for state_id in path {
+            let input = space.representative_input(head_id, state_id);
+            let param = space.representative_param(head_id, state_id);
+            let state = space.state_data(state_id);
+
+            steps.push(TraceStep {
+                state_id,
+                input: describe_fields(input),
+                param: describe_fields(param),
+                state: describe_fields(&state.result),
+                panic: state.panic.to_string(),
+            });
+            head_id = state_id.into();
+        }
+
+        Trace {
+            steps,
+            loop_start,
+            replay: None,
+        }
+    }
+}
+
+/// Describes the values of all fields of a manipulatable value.
+///
+/// Arrays are described per runs of the same elements, i.e. `name[i..=j]`.
+pub(super) fn describe_fields<T: Manipulatable>(value: &T) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+    for field_name in T::field_names() {
+        let Some(field) = value.get(field_name) else {
+            continue;
+        };
+        match field.description() {
+            Field::Bitvector(bitvector) => {
+                result.insert(field_name.to_string(), bitvector.to_string());
+            }
+            Field::Array(array) => {
+                // the description contains the elements only for the leftmost indices of the runs
+                let mut iter = array.inner.into_iter().peekable();
+                while let Some((start_index, element)) = iter.next() {
+                    let end_index = if let Some((next_index, _)) = iter.peek() {
+                        next_index - 1
+                    } else if array.bit_length == u64::BITS {
+                        // the right shift would overflow
+                        u64::MAX
+                    } else {
+                        (1u64 << array.bit_length) - 1
+                    };
+
+                    let field_part_name = if start_index == end_index {
+                        format!("{}[{}]", field_name, start_index)
+                    } else {
+                        format!("{}[{}..={}]", field_name, start_index, end_index)
+                    };
+                    let value = BitvectorField {
+                        bit_width: array.bit_width,
+                        element,
+                    };
+                    result.insert(field_part_name, value.to_string());
+                }
+            }
+        }
+    }
+    result
+}
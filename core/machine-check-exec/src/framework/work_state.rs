@@ -7,7 +7,7 @@ use mck::concr::FullMachine;
 
 use crate::model_check::ThreeValuedChecker;
 use crate::precision::Precision;
-use crate::space::StateSpace;
+use crate::space::{EntryTracking, StateSpace};
 use crate::{AbstrInput, AbstrPanicState, AbstrParam};
 use crate::{RefinInput, RefinPanicState, RefinParam};
 
@@ -35,12 +35,12 @@ pub struct WorkState<M: FullMachine> {
 }
 
 impl<M: FullMachine> WorkState<M> {
-    pub fn new() -> Self {
+    pub fn new(entry_tracking: EntryTracking) -> Self {
         Self {
             input_precision: Precision::new(),
             param_precision: Precision::new(),
             step_precision: Precision::new(),
-            space: StateSpace::new(),
+            space: StateSpace::new(entry_tracking),
             checker: ThreeValuedChecker::new(),
             culprit: None,
             num_refinements: 0,
@@ -49,6 +49,18 @@ impl<M: FullMachine> WorkState<M> {
         }
     }
 
+    /// Starts from scratch with the given tracking of the steps entering the states.
+    ///
+    /// The statistics are kept.
+    pub fn retrack(&mut self, entry_tracking: EntryTracking) {
+        *self = Self {
+            num_refinements: self.num_refinements,
+            num_generated_states: self.num_generated_states,
+            num_generated_transitions: self.num_generated_transitions,
+            ..Self::new(entry_tracking)
+        };
+    }
+
     pub fn info(&mut self) -> ExecStats {
         ExecStats {
             num_refinements: self.num_refinements,
@@ -84,6 +96,7 @@ impl<M: FullMachine> WorkState<M> {
         };
 
         outside_used_ids.extend(self.input_precision.used_state_ids());
+        outside_used_ids.extend(self.param_precision.used_state_ids());
         outside_used_ids.extend(self.step_precision.used_state_ids());
         let removed_states = self.space.make_compact(outside_used_ids);
         self.checker.remove_states(&removed_states);
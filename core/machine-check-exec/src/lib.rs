@@ -5,10 +5,15 @@ mod model_check;
 mod precision;
 mod space;
 
+use std::time::Duration;
+
 use machine_check_common::property::Property;
 use mck::{abstr, concr::FullMachine, misc::MetaWrap, refin};
 
-pub use framework::Framework;
+pub use framework::{
+    Checkpoint, Framework, PrecisionKind, RefinedPrecision, SpaceFormat, VerificationEvent,
+    VerificationObserver,
+};
 
 /// Abstraction and refinement strategy.
 pub struct Strategy {
@@ -16,6 +21,23 @@ pub struct Strategy {
     pub naive_inputs: bool,
     /// Whether each step output should decay to fully-unknown by default.
     pub use_decay: bool,
+    /// The number of threads computing the successors of states in parallel.
+    ///
+    /// The state space is the same regardless of the number of threads.
+    pub num_threads: usize,
+}
+
+/// Limits on the resources used by the verification.
+///
+/// The limits are checked between the verification steps, so they can be slightly exceeded.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceLimits {
+    /// The maximum time since the construction of the framework.
+    pub timeout: Option<Duration>,
+    /// The maximum number of refinements.
+    pub max_refinements: Option<usize>,
+    /// The maximum number of states in the state space.
+    pub max_states: Option<usize>,
 }
 
 /// Whether we are verifying the inherent property or a standard property.
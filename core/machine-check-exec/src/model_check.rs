@@ -1,13 +1,14 @@
 mod deduce;
+mod explain;
 mod property_checker;
 
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use log::trace;
 use machine_check_common::{
-    check::{Conclusion, KnownConclusion},
-    property::{Property, Subproperty},
-    ExecError, ParamValuation, StateId,
+    check::{Conclusion, Culprit, KnownConclusion},
+    property::{NextOperator, Property, Subproperty},
+    ExecError, NodeId, ParamValuation, StateId,
 };
 use mck::concr::FullMachine;
 
@@ -15,8 +16,19 @@ use crate::{model_check::property_checker::PropertyChecker, space::StateSpace};
 
 use self::deduce::deduce_culprit;
 
+pub use self::explain::Explanation;
+
 use std::fmt::Debug;
 
+/// Three-valued model-checking result for the classes of parameter values.
+///
+/// If some class is unknown, the culprit is given.
+pub enum ParamClassesConclusion {
+    /// A representative initial state of each class and whether the property holds for the class.
+    Known(Vec<(StateId, bool)>),
+    Unknown(Culprit),
+}
+
 #[derive(Debug)]
 /// Three-valued model checker.
 pub struct ThreeValuedChecker {
@@ -54,6 +66,34 @@ impl ThreeValuedChecker {
         Ok((conclusion, labelling))
     }
 
+    /// Returns the valuation of the subproperty in the initial states.
+    ///
+    /// As with the whole property, the subproperty must hold in all of the initial states.
+    pub fn check_subproperty_in_initial<M: FullMachine>(
+        &mut self,
+        space: &StateSpace<M>,
+        subproperty: &Subproperty,
+    ) -> Result<ParamValuation, ExecError> {
+        let property = subproperty.property();
+        self.check_property(space, property)?;
+
+        let property_checker = self
+            .property_checkers
+            .get(property)
+            .expect("Property checker should be inserted after the property was checked");
+
+        // the valuation in the initial states is AX of the root node
+        let getter = property_checker.last_getter(space);
+        let timed = getter.compute_next_labelling(
+            &NextOperator {
+                is_universal: true,
+                inner: subproperty.index(),
+            },
+            NodeId::ROOT,
+        )?;
+        Ok(timed.value.valuation)
+    }
+
     /// Model-checks a mu-calculus proposition.
     pub fn check_property<M: FullMachine>(
         &mut self,
@@ -92,10 +132,95 @@ impl ThreeValuedChecker {
                 property_checker,
                 space,
                 property,
+                space.initial_iter(),
             )?)),
         }
     }
 
+    /// Deduces the culprit of the property being unknown in the given state.
+    ///
+    /// The property must have been checked and be unknown in the state. The culprit
+    /// starts in the given state rather than in an initial state.
+    pub fn deduce_culprit_in<M: FullMachine>(
+        &self,
+        space: &StateSpace<M>,
+        property: &Property,
+        state_id: StateId,
+    ) -> Result<Culprit, ExecError> {
+        let property_checker = self
+            .property_checkers
+            .get(property)
+            .expect("Property checker should be inserted after the property was checked");
+        deduce_culprit(property_checker, space, property, [state_id])
+    }
+
+    /// Model-checks a mu-calculus proposition for each class of parameter values.
+    ///
+    /// The classes are given by the parameters the initial states were generated with.
+    /// The parameters entering the states must be tracked, so that they are kept
+    /// along the paths and each class can be decided.
+    pub fn check_param_classes<M: FullMachine>(
+        &mut self,
+        space: &StateSpace<M>,
+        property: &Property,
+    ) -> Result<ParamClassesConclusion, ExecError> {
+        assert!(space.entry_tracking().param);
+        self.check_property(space, property)?;
+
+        let property_checker = self
+            .property_checkers
+            .get(property)
+            .expect("Property checker should be inserted after the property was checked");
+        let getter = property_checker.last_getter(space);
+
+        let partition = space
+            .direct_successor_param_partition(NodeId::ROOT)
+            .expect("Valid state space should have initial states");
+
+        let mut classes = Vec::new();
+        for parametric_set in partition.all_sets() {
+            let initial_ids: Vec<StateId> =
+                parametric_set.map(|(_, initial_id)| *initial_id).collect();
+
+            // the property must hold in all initial states of the class
+            let mut valuation = ParamValuation::True;
+            for initial_id in initial_ids.iter().copied() {
+                valuation = valuation & getter.compute_latest_timed(0, initial_id)?.value.valuation;
+            }
+
+            match valuation {
+                ParamValuation::False | ParamValuation::True => {
+                    classes.push((initial_ids[0], valuation == ParamValuation::True));
+                }
+                ParamValuation::Unknown => {
+                    return Ok(ParamClassesConclusion::Unknown(deduce_culprit(
+                        property_checker,
+                        space,
+                        property,
+                        initial_ids,
+                    )?));
+                }
+                ParamValuation::Dependent => return Err(ExecError::ParamClassDependent),
+            }
+        }
+        Ok(ParamClassesConclusion::Known(classes))
+    }
+
+    /// Explains a known result of a previously checked property by a path through the state space.
+    ///
+    /// Returns none if the property was not checked yet or no initial state has the wanted value.
+    pub fn explain_property<M: FullMachine>(
+        &self,
+        space: &StateSpace<M>,
+        property: &Property,
+        wanted: bool,
+    ) -> Result<Option<Explanation>, ExecError> {
+        let Some(property_checker) = self.property_checkers.get(property) else {
+            return Ok(None);
+        };
+        self::explain::explain(property_checker, space, property, wanted)
+    }
+
     pub fn declare_regeneration<M: FullMachine>(
         &mut self,
         space: &StateSpace<M>,
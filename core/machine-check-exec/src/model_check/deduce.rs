@@ -17,10 +17,13 @@ use crate::{
 };
 
 /// Deduces the culprit of unknown three-valued model-checking result.
+///
+/// The culprit starts in the first of the given initial states that has an unknown valuation.
 pub(super) fn deduce_culprit<M: FullMachine>(
     checker: &PropertyChecker,
     space: &StateSpace<M>,
     property: &Property,
+    initial_ids: impl IntoIterator<Item = StateId>,
 ) -> Result<Culprit, ExecError> {
     //trace!("Deducing culprit, checker: {:#?}", checker);
 
@@ -29,7 +32,7 @@ pub(super) fn deduce_culprit<M: FullMachine>(
 
     let getter = checker.last_getter(space);
 
-    for initial_id in space.initial_iter() {
+    for initial_id in initial_ids {
         let timed = getter.compute_latest_timed(0, initial_id)?;
 
         if timed.value.valuation.is_known() {
@@ -62,6 +65,8 @@ struct Deducer<'a, M: FullMachine> {
 struct FixedPointDeduction {
     path: VecDeque<StateId>,
     variable: usize,
+    /// Time at which the variable value was computed.
+    time: u64,
 }
 
 #[derive(Debug)]
@@ -89,10 +94,11 @@ impl<M: FullMachine> Deducer<'_, M> {
                 panic!("const should never be the labelling culprit")
             }
             PropertyType::Atomic(literal) => {
-                // culprit ends here
+                // culprit ends here, narrowed to the responsible index if quantified
+                let atomic_property = self.getter.space().atomic_culprit(literal, last_state_id)?;
                 Ok(Deduction::Culprit(Culprit {
                     path: self.path.clone(),
-                    atomic_property: literal.clone(),
+                    atomic_property,
                 }))
             }
             PropertyType::Negation(inner) => {
@@ -119,7 +125,6 @@ impl<M: FullMachine> Deducer<'_, M> {
                     .last()
                     .expect("Culprit state should have next state for next operator");
 
-                assert_ne!(last_state_id, next_state);
                 assert!(self
                     .getter
                     .space()
@@ -140,16 +145,24 @@ impl<M: FullMachine> Deducer<'_, M> {
                                 break Ok(Deduction::FixedPoint(deduction));
                             }
                             // our variable, update path and loop
+                            // the inner labelling must be considered at the time the variable value
+                            // was computed, as the nested fixed points may have been recomputed since,
+                            // which also ensures the deduction progresses backwards in time
                             self.path = deduction.path;
+                            self.getter = self.getter.at_time(deduction.time);
                         }
                     }
                 }
             }
             PropertyType::FixedVariable(variable) => {
                 // return fixed-point deduction
+                let timed = self
+                    .getter
+                    .compute_latest_timed(subproperty_index, last_state_id)?;
                 Ok(Deduction::FixedPoint(FixedPointDeduction {
                     path: self.path.clone(),
                     variable: *variable,
+                    time: timed.time,
                 }))
             }
         }
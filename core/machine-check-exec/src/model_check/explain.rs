@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use machine_check_common::{
+    check::Property, property::PropertyType, ExecError, ParamValuation, StateId,
+};
+use mck::concr::FullMachine;
+
+use crate::{
+    model_check::property_checker::{LabellingCacher, PropertyChecker},
+    space::StateSpace,
+};
+
+/// A path explaining a known three-valued model-checking result.
+///
+/// If the loop start is present, the last state of the path
+/// transitions to the state at the loop start.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    pub path: Vec<StateId>,
+    pub loop_start: Option<usize>,
+}
+
+/// Explains the known result of a previously computed labelling by a path.
+///
+/// Returns none if no initial state has the given valuation.
+pub(super) fn explain<M: FullMachine>(
+    checker: &PropertyChecker,
+    space: &StateSpace<M>,
+    property: &Property,
+    wanted: bool,
+) -> Result<Option<Explanation>, ExecError> {
+    let getter = checker.last_getter(space);
+
+    // start from the initial state with the wanted valuation that was decided the earliest
+    let mut best_initial = None;
+    for initial_id in space.initial_iter() {
+        let timed = getter.compute_latest_timed(0, initial_id)?;
+        if timed.value.valuation != ParamValuation::from_bool(wanted) {
+            continue;
+        }
+        let key = (timed.time, initial_id);
+        if best_initial.is_none_or(|best_key| key < best_key) {
+            best_initial = Some(key);
+        }
+    }
+
+    let Some((_, initial_id)) = best_initial else {
+        return Ok(None);
+    };
+
+    let mut explainer = Explainer::<M> {
+        getter,
+        property,
+        path: vec![initial_id],
+        fixed_point_visits: BTreeMap::new(),
+    };
+
+    let loop_start = match explainer.explain(0, wanted)? {
+        Ending::Loop(loop_start) => Some(loop_start),
+        Ending::End | Ending::FixedPoint(_) => None,
+    };
+
+    Ok(Some(Explanation {
+        path: explainer.path,
+        loop_start,
+    }))
+}
+
+struct Explainer<'a, M: FullMachine> {
+    getter: LabellingCacher<'a, M>,
+    property: &'a Property,
+    path: Vec<StateId>,
+    /// Path positions where the fixed points were entered in given states.
+    fixed_point_visits: BTreeMap<(usize, StateId), usize>,
+}
+
+#[derive(Debug)]
+enum Ending {
+    /// The explanation ends in the last state of the path.
+    End,
+    /// The last state of the path transitions to the state at the given position.
+    Loop(usize),
+    /// The explanation continues with the given fixed point in the last state of the path.
+    FixedPoint(usize),
+}
+
+impl<M: FullMachine> Explainer<'_, M> {
+    /// Explains why the subproperty has the wanted value in the last state of the path.
+    fn explain(&mut self, subproperty_index: usize, wanted: bool) -> Result<Ending, ExecError> {
+        let last_state_id = *self.path.last().unwrap();
+        let subproperty_entry = self.property.subproperty_entry(subproperty_index);
+
+        match &subproperty_entry.ty {
+            PropertyType::Const(_) | PropertyType::Atomic(_) => {
+                // the value is explained by the state itself
+                Ok(Ending::End)
+            }
+            PropertyType::Negation(inner) => self.explain(*inner, !wanted),
+            PropertyType::BiLogic(op) => {
+                let a_timed = self.getter.compute_latest_timed(op.a, last_state_id)?;
+                let b_timed = self.getter.compute_latest_timed(op.b, last_state_id)?;
+                let wanted_valuation = ParamValuation::from_bool(wanted);
+                let a_wanted = a_timed.value.valuation == wanted_valuation;
+                let b_wanted = b_timed.value.valuation == wanted_valuation;
+
+                let choose_a = if op.is_and == wanted {
+                    // both operands must have the wanted value, but we can only follow
+                    // a single path, so continue with the one that can prolong it
+                    self.property.subproperty_transition_depth(op.a)
+                        >= self.property.subproperty_transition_depth(op.b)
+                } else {
+                    // a single operand with the wanted value suffices, choose the earliest decided
+                    match (a_wanted, b_wanted) {
+                        (true, true) => a_timed.time <= b_timed.time,
+                        (true, false) => true,
+                        (false, true) => false,
+                        (false, false) => return Ok(Ending::End),
+                    }
+                };
+
+                if choose_a {
+                    self.explain(op.a, wanted)
+                } else {
+                    self.explain(op.b, wanted)
+                }
+            }
+            PropertyType::Next(op) => {
+                if op.is_universal == wanted {
+                    // all successors are concerned, a single path does not explain it
+                    return Ok(Ending::End);
+                }
+
+                // choose the successor with the wanted value that was decided the earliest
+                let wanted_valuation = ParamValuation::from_bool(wanted);
+                let mut best_successor = None;
+                for successor_id in self
+                    .getter
+                    .space()
+                    .direct_successor_iter(last_state_id.into())
+                {
+                    let timed = self.getter.compute_latest_timed(op.inner, successor_id)?;
+                    if timed.value.valuation != wanted_valuation {
+                        continue;
+                    }
+                    let key = (timed.time, successor_id);
+                    if best_successor.is_none_or(|best_key| key < best_key) {
+                        best_successor = Some(key);
+                    }
+                }
+
+                let Some((_, successor_id)) = best_successor else {
+                    return Ok(Ending::End);
+                };
+
+                self.path.push(successor_id);
+                self.explain(op.inner, wanted)
+            }
+            PropertyType::FixedPoint(op) => loop {
+                let last_state_id = *self.path.last().unwrap();
+                let position = self.path.len() - 1;
+                if let Some(visit_position) = self
+                    .fixed_point_visits
+                    .insert((subproperty_index, last_state_id), position)
+                {
+                    if visit_position == position {
+                        // no progress was made, do not loop infinitely
+                        return Ok(Ending::End);
+                    }
+                    // we are in a state we have already explained, so the path loops
+                    self.path.pop();
+                    return Ok(Ending::Loop(visit_position));
+                }
+
+                match self.explain(op.inner, wanted)? {
+                    Ending::FixedPoint(variable) if variable == subproperty_index => {
+                        // our variable, continue explaining in the new last state
+                    }
+                    ending => return Ok(ending),
+                }
+            },
+            PropertyType::FixedVariable(fixed_point_index) => {
+                Ok(Ending::FixedPoint(*fixed_point_index))
+            }
+        }
+    }
+}
@@ -11,7 +11,9 @@ use std::{
 
 use log::trace;
 use machine_check_common::{
-    check::Property, property::PropertyType, ExecError, ParamValuation, StateId,
+    check::Property,
+    property::{FieldSource, PropertyType},
+    ExecError, ParamValuation, StateId,
 };
 use mck::concr::FullMachine;
 
@@ -36,6 +38,10 @@ pub struct PropertyChecker {
     computations: Vec<FixedPointComputation>,
 
     focus: Focus,
+
+    /// The initial states the labelling was computed with,
+    /// if the property references the fields in the initial states.
+    initial_states: Option<BTreeSet<StateId>>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -62,6 +68,9 @@ impl PropertyChecker {
         }
 
         let focus = Focus::new(&property);
+        let initial_states = property
+            .references_source(FieldSource::Init)
+            .then(BTreeSet::new);
 
         Self {
             property,
@@ -69,6 +78,7 @@ impl PropertyChecker {
             focus,
             histories,
             computations: Vec::new(),
+            initial_states,
         }
     }
 
@@ -95,6 +105,15 @@ impl PropertyChecker {
             "Histories before computing interpretation: {:#?}",
             self.histories
         );
+        if let Some(initial_states) = &self.initial_states {
+            // the labels of all states depend on the initial states
+            let current_initial_states = BTreeSet::from_iter(space.initial_iter());
+            if *initial_states != current_initial_states {
+                self.invalidate();
+                self.focus.make_whole_dirty(space);
+                self.initial_states = Some(current_initial_states);
+            }
+        }
         let labelling_computer = LabellingUpdater::new(self, space)?;
         let result = labelling_computer.compute()?;
 
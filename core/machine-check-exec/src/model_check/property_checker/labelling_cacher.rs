@@ -2,6 +2,8 @@ mod fixed_point;
 mod local;
 mod next;
 
+use std::{cell::RefCell, collections::BTreeMap};
+
 use machine_check_common::{property::PropertyType, ExecError, ParamValuation, StateId};
 
 pub use local::BiChoice;
@@ -16,6 +18,10 @@ pub struct LabellingCacher<'a, M: FullMachine> {
     property_checker: &'a PropertyChecker,
     space: &'a StateSpace<M>,
     current_time: u64,
+    /// Labellings already computed at the current time.
+    ///
+    /// The property checker is borrowed, so they cannot change while the cacher exists.
+    cache: RefCell<BTreeMap<(usize, StateId), TimedCheckValue>>,
 }
 
 impl<'a, M: FullMachine> LabellingCacher<'a, M> {
@@ -28,6 +34,17 @@ impl<'a, M: FullMachine> LabellingCacher<'a, M> {
             property_checker,
             space,
             current_time,
+            cache: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the cacher considering the labelling as it was at the given time.
+    pub fn at_time(&self, current_time: u64) -> Self {
+        LabellingCacher {
+            property_checker: self.property_checker,
+            space: self.space,
+            current_time,
+            cache: RefCell::new(BTreeMap::new()),
         }
     }
 
@@ -40,6 +57,12 @@ impl<'a, M: FullMachine> LabellingCacher<'a, M> {
         subproperty_index: usize,
         state_id: StateId,
     ) -> Result<TimedCheckValue, ExecError> {
+        // the subproperties can be nested deeply, e.g. in bounded operators,
+        // so reuse the labellings to avoid recomputing them for every path
+        if let Some(cached) = self.cache.borrow().get(&(subproperty_index, state_id)) {
+            return Ok(cached.clone());
+        }
+
         let subproperty_entry = self
             .property_checker
             .property
@@ -69,6 +92,9 @@ impl<'a, M: FullMachine> LabellingCacher<'a, M> {
             }
         };
 
+        self.cache
+            .borrow_mut()
+            .insert((subproperty_index, state_id), result.clone());
         Ok(result)
     }
 }
@@ -28,6 +28,8 @@ pub struct LabellingUpdater<'a, M: FullMachine> {
     invalidate: bool,
 
     calmable_fixed_points: BTreeSet<usize>,
+    /// Times of the latest iterations of the fixed points being computed.
+    fixed_point_iteration_times: BTreeMap<usize, u64>,
 
     num_fixed_point_computations: u64,
     num_fixed_point_iterations: u64,
@@ -45,6 +47,7 @@ impl<'a, M: FullMachine> LabellingUpdater<'a, M> {
             next_computation_index: 0,
             invalidate: false,
             calmable_fixed_points: BTreeSet::new(),
+            fixed_point_iteration_times: BTreeMap::new(),
             num_fixed_point_computations: 0,
             num_fixed_point_iterations: 0,
         };
@@ -107,6 +110,7 @@ impl<'a, M: FullMachine> LabellingUpdater<'a, M> {
         self.current_time = 0;
         self.next_computation_index = 0;
         self.calmable_fixed_points.clear();
+        self.fixed_point_iteration_times.clear();
         self.num_fixed_point_computations = 0;
         self.num_fixed_point_iterations = 0;
         self.update_labelling(0)?;
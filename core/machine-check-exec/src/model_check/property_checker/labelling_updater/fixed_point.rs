@@ -21,6 +21,10 @@ struct FixedPointIterationParams {
     fixed_point_index: usize,
     inner_index: usize,
     old_computation_end_time: Option<u64>,
+    first_iteration: bool,
+    /// The time and the next computation index after the iteration
+    /// since which the fixed point has not changed.
+    unchanged_since: Option<(u64, usize)>,
 }
 
 impl<M: FullMachine> LabellingUpdater<'_, M> {
@@ -76,6 +80,8 @@ impl<M: FullMachine> LabellingUpdater<'_, M> {
             // clear later times
             history.insert(start_time, state_id, ground_value.clone());
         }
+        self.fixed_point_iteration_times
+            .insert(fixed_point_index, start_time);
 
         // iterate until the fixed point is reached
 
@@ -83,9 +89,21 @@ impl<M: FullMachine> LabellingUpdater<'_, M> {
             fixed_point_index,
             inner_index: op.inner,
             old_computation_end_time,
+            first_iteration: true,
+            unchanged_since: None,
         };
 
         while let ControlFlow::Continue(()) = self.fixed_point_iteration(&mut params)? {}
+        if self.invalidate {
+            return Ok(BTreeMap::new());
+        }
+        if let Some((unchanged_time, unchanged_computation_index)) = params.unchanged_since {
+            self.discard_unchanged_iterations(
+                fixed_point_index,
+                unchanged_time,
+                unchanged_computation_index,
+            );
+        }
 
         // we reached the fixed point
         // the inner updated have been cleared
@@ -102,6 +120,9 @@ impl<M: FullMachine> LabellingUpdater<'_, M> {
 
         if old_computation_end_time.is_some() {
             self.adjust_end_time_using_old(start_time, computation_clone)?;
+            if self.invalidate {
+                return Ok(BTreeMap::new());
+            }
         } else {
             self.adjust_end_time_padding(current_computation_index, computation_clone);
         }
@@ -1,4 +1,4 @@
-use std::ops::ControlFlow;
+use std::{collections::btree_map, ops::ControlFlow};
 
 use log::trace;
 use machine_check_common::ExecError;
@@ -24,6 +24,26 @@ impl<M: FullMachine> LabellingUpdater<'_, M> {
         // compute the iteration
 
         let mut current_update = self.update_labelling(params.inner_index)?;
+        if self.invalidate {
+            // the histories are inconsistent, everything will be recomputed
+            return Ok(ControlFlow::Break(()));
+        }
+
+        if params.first_iteration {
+            // the dirty states may have different successors than before, so their inner labelling
+            // may change even if no inner update reaches them, e.g. when the successors are only
+            // labelled by a closed-form fixed point which updates the dirty states only
+            params.first_iteration = false;
+            let dirty: Vec<_> = self.property_checker.focus.dirty_iter().collect();
+            for state_id in dirty {
+                if let btree_map::Entry::Vacant(entry) = current_update.entry(state_id) {
+                    entry.insert(
+                        self.getter()
+                            .compute_latest_timed(params.inner_index, state_id)?,
+                    );
+                }
+            }
+        }
 
         // add previously updated
         // only do this for backward-affected states since the others will not see a meaningful change during one iteration
@@ -76,19 +96,58 @@ impl<M: FullMachine> LabellingUpdater<'_, M> {
                 .focus
                 .insert_dirty(self.space, state_id);
         }
+        self.fixed_point_iteration_times
+            .insert(params.fixed_point_index, self.current_time);
 
-        if !history.time_changes(self.current_time) {
-            if let Some(old_computation_end_time) = params.old_computation_end_time {
-                if self.current_time >= old_computation_end_time
-                    || !history.range_changes(self.current_time, old_computation_end_time)
-                {
-                    return Ok(ControlFlow::Break(()));
-                }
-            } else {
-                return Ok(ControlFlow::Break(()));
+        if history.time_changes(self.current_time) {
+            params.unchanged_since = None;
+            return Ok(ControlFlow::Continue(()));
+        }
+        params
+            .unchanged_since
+            .get_or_insert((self.current_time, self.next_computation_index));
+
+        if let Some(old_computation_end_time) = params.old_computation_end_time {
+            // the changes of the old computation may still be valid, replay them
+            if self.current_time < old_computation_end_time
+                && history.range_changes(self.current_time, old_computation_end_time)
+            {
+                return Ok(ControlFlow::Continue(()));
             }
         }
+        Ok(ControlFlow::Break(()))
+    }
 
-        Ok(ControlFlow::Continue(()))
+    /// Discards the iterations after the one since which the fixed point has not changed.
+    ///
+    /// When the old changes are replayed, they may turn out not to be valid anymore.
+    /// The iterations performed just to replay them do not change the fixed point, but they
+    /// compute the inner fixed points again. They are discarded so that the computations
+    /// and histories correspond to the ones computed from scratch.
+    pub(super) fn discard_unchanged_iterations(
+        &mut self,
+        fixed_point_index: usize,
+        unchanged_time: u64,
+        unchanged_computation_index: usize,
+    ) {
+        if unchanged_time == self.current_time {
+            return;
+        }
+        trace!(
+            "Discarding the iterations of fixed point {} in times ({}, {}]",
+            fixed_point_index,
+            unchanged_time,
+            self.current_time
+        );
+        self.property_checker
+            .computations
+            .drain(unchanged_computation_index..self.next_computation_index);
+        for history in self.property_checker.histories.values_mut() {
+            history.remove_times(unchanged_time + 1, self.current_time + 1);
+        }
+        self.current_time = unchanged_time;
+        self.next_computation_index = unchanged_computation_index;
+        self.fixed_point_iteration_times
+            .insert(fixed_point_index, unchanged_time);
     }
 }
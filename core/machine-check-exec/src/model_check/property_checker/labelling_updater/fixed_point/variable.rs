@@ -17,10 +17,11 @@ impl<M: FullMachine> LabellingUpdater<'_, M> {
         let mut update = BTreeMap::new();
         let affected_forward = self.property_checker.focus.affected_forward();
         let history = select_history(&self.property_checker.histories, fixed_point_index);
-        let last_time = self
-            .current_time
-            .checked_sub(1)
-            .expect("Updates should not commence at time zero");
+        // other fixed points may have been computed since the last iteration of ours
+        let last_time = *self
+            .fixed_point_iteration_times
+            .get(&fixed_point_index)
+            .expect("Fixed point should be computed when updating its variable");
         let Some(changed_states) = history.states_at_exact_time_opt(last_time) else {
             return Ok(BTreeMap::new());
         };
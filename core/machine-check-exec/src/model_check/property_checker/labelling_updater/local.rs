@@ -27,6 +27,10 @@ impl<M: FullMachine> LabellingUpdater<'_, M> {
     ) -> Result<BTreeMap<StateId, TimedCheckValue>, ExecError> {
         let mut result = self.update_labelling(op.a)?;
         let mut result_b = self.update_labelling(op.b)?;
+        if self.invalidate {
+            // the histories are inconsistent, everything will be recomputed
+            return Ok(BTreeMap::new());
+        }
 
         for (state_id, timed) in result.iter_mut() {
             let timed_b = if let Some(timed_b) = result_b.remove(state_id) {
@@ -13,6 +13,10 @@ impl<M: FullMachine> LabellingUpdater<'_, M> {
         op: &NextOperator,
     ) -> Result<BTreeMap<StateId, TimedCheckValue>, ExecError> {
         let inner_updated = self.update_labelling(op.inner)?;
+        if self.invalidate {
+            // the histories are inconsistent, everything will be recomputed
+            return Ok(BTreeMap::new());
+        }
 
         let mut result = BTreeMap::new();
 
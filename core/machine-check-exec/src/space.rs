@@ -6,6 +6,8 @@ use mck::concr::FullMachine;
 use partitions::PartitionVec;
 use store::StateStore;
 
+pub use store::EntryTracking;
+
 use crate::{AbstrInput, AbstrPanicState, AbstrParam};
 
 mod graph;
@@ -30,10 +32,10 @@ impl<M: FullMachine> Debug for StateSpace<M> {
 }
 
 impl<M: FullMachine> StateSpace<M> {
-    pub fn new() -> Self {
+    pub fn new(entry_tracking: EntryTracking) -> Self {
         Self {
             graph: StateGraph::new(),
-            store: StateStore::new(),
+            store: StateStore::new(entry_tracking),
             num_graph_nodes_for_sweep: 2,
         }
     }
@@ -46,7 +48,7 @@ impl<M: FullMachine> StateSpace<M> {
         param: &AbstrParam<M>,
         param_id: Option<usize>,
     ) -> (StateId, bool, usize) {
-        let (tail_id, state_inserted) = self.store.state_id(tail_data);
+        let (tail_id, state_inserted) = self.store.state_id(tail_data, Some((input, param)));
 
         let result_param_id = self
             .graph
@@ -91,13 +93,27 @@ impl<M: FullMachine> StateSpace<M> {
 
     pub fn state_id(&mut self, state_data: AbstrPanicState<M>) -> StateId {
         // TODO: this is a dangerous thing with garbage collection
-        self.store.state_id(state_data).0
+        self.store.state_id(state_data, None).0
     }
 
     pub fn state_data(&self, state_id: StateId) -> &AbstrPanicState<M> {
         self.store.state_data(state_id)
     }
 
+    /// Returns the input of the step entering the state if it is tracked.
+    pub fn entry_input(&self, state_id: StateId) -> Option<&AbstrInput<M>> {
+        self.store.entry_input(state_id)
+    }
+
+    /// Returns the parameter of the step entering the state if it is tracked.
+    pub fn entry_param(&self, state_id: StateId) -> Option<&AbstrParam<M>> {
+        self.store.entry_param(state_id)
+    }
+
+    pub fn entry_tracking(&self) -> EntryTracking {
+        self.store.entry_tracking()
+    }
+
     pub fn clear_step(
         &mut self,
         head_id: NodeId,
@@ -182,6 +198,6 @@ impl<M: FullMachine> StateSpace<M> {
 
 impl<M: FullMachine> Default for StateSpace<M> {
     fn default() -> Self {
-        Self::new()
+        Self::new(EntryTracking::default())
     }
 }
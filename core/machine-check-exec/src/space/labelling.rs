@@ -2,8 +2,13 @@ use std::collections::BTreeSet;
 
 use super::StateId;
 use super::StateSpace;
+use crate::{AbstrInput, AbstrParam, AbstrState};
 use machine_check_common::property::AtomicProperty;
 use machine_check_common::property::ComparisonType;
+use machine_check_common::property::FieldReference;
+use machine_check_common::property::FieldSource;
+use machine_check_common::property::OperationType;
+use machine_check_common::property::ValueExpression;
 use machine_check_common::ExecError;
 use machine_check_common::Signedness;
 use machine_check_common::ThreeValued;
@@ -18,82 +23,529 @@ impl<M: FullMachine> StateSpace<M> {
         atomic_property: &AtomicProperty,
         state_id: StateId,
     ) -> Result<ThreeValued, ExecError> {
-        let state = self.state_data(state_id);
+        let Some(quantifier) = atomic_property.quantifier() else {
+            return self.comparison_label(atomic_property, state_id);
+        };
 
-        let left = atomic_property.left();
-        let left_name = left.name();
-        let manip_field = if left_name == "__panic" {
-            let manip_field: &dyn ManipField = &state.panic;
-            manip_field
-        } else {
-            match state.result.get(left_name) {
-                Some(manip_field) => manip_field,
-                None => return Err(ExecError::FieldNotFound(String::from(left_name))),
+        // the comparison only needs to be evaluated once for each run of equal elements
+        let mut result = ThreeValued::from_bool(quantifier.is_universal());
+        for index in self.quantified_indices(atomic_property, state_id)? {
+            let label = self.comparison_label(&atomic_property.instantiate(index), state_id)?;
+            if quantifier.is_universal() {
+                result = result & label;
+                if result.is_false() {
+                    break;
+                }
+            } else {
+                result = result | label;
+                if result.is_true() {
+                    break;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the atomic property responsible for the unknown label of the state.
+    ///
+    /// For a quantified property, this is its comparison instantiated
+    /// for the first index where it is unknown. The verification is incomplete
+    /// if there is no such index.
+    pub fn atomic_culprit(
+        &self,
+        atomic_property: &AtomicProperty,
+        state_id: StateId,
+    ) -> Result<AtomicProperty, ExecError> {
+        if atomic_property.quantifier().is_none() {
+            return Ok(atomic_property.clone());
+        }
+        for index in self.quantified_indices(atomic_property, state_id)? {
+            let instantiated = atomic_property.instantiate(index);
+            if self.comparison_label(&instantiated, state_id)?.is_unknown() {
+                return Ok(instantiated);
+            }
+        }
+        Err(ExecError::Incomplete)
+    }
+
+    /// Returns the indices where the runs of equal elements of the quantified fields start.
+    ///
+    /// The first index of the quantified range is always included.
+    fn quantified_indices(
+        &self,
+        atomic_property: &AtomicProperty,
+        state_id: StateId,
+    ) -> Result<BTreeSet<u64>, ExecError> {
+        let quantifier = atomic_property
+            .quantifier()
+            .expect("Atomic property should be quantified");
+        let (start, end) = (quantifier.start(), quantifier.end());
+
+        let mut result = BTreeSet::from([start]);
+        for field in atomic_property.fields() {
+            if field.index_variable().is_none() {
+                continue;
+            }
+            for source_state_id in self.source_states(state_id, field) {
+                let manip_field = self.source_field(source_state_id, field)?;
+                // the whole range must be within the array
+                let Some(run_starts) = manip_field
+                    .index(end - 1)
+                    .and(manip_field.index_run_starts())
+                else {
+                    return Err(ExecError::IndexInvalid(end - 1, String::from(field.name())));
+                };
+                result.extend(
+                    run_starts
+                        .into_iter()
+                        .filter(|run_start| *run_start > start && *run_start < end),
+                );
             }
+        }
+        Ok(result)
+    }
+
+    /// Returns the label of an unquantified atomic property, together with the conjoined comparisons.
+    ///
+    /// If the initial fields are referenced, the property holds if it holds for some initial state.
+    fn comparison_label(
+        &self,
+        atomic_property: &AtomicProperty,
+        state_id: StateId,
+    ) -> Result<ThreeValued, ExecError> {
+        if !Self::references_init(atomic_property) {
+            return self.conjoined_label(atomic_property, state_id, None);
+        }
+        let mut result = ThreeValued::False;
+        for initial_state_id in self.initial_iter() {
+            result =
+                result | self.conjoined_label(atomic_property, state_id, Some(initial_state_id))?;
+            if result.is_true() {
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the first initial state for which the label of the atomic property is unknown.
+    ///
+    /// The property should be unquantified. Returns none if it does not reference the initial fields.
+    pub fn unknown_initial_state(
+        &self,
+        atomic_property: &AtomicProperty,
+        state_id: StateId,
+    ) -> Result<Option<StateId>, ExecError> {
+        if !Self::references_init(atomic_property) {
+            return Ok(None);
+        }
+        for initial_state_id in self.initial_iter() {
+            if self
+                .conjoined_label(atomic_property, state_id, Some(initial_state_id))?
+                .is_unknown()
+            {
+                return Ok(Some(initial_state_id));
+            }
+        }
+        Ok(None)
+    }
+
+    fn references_init(atomic_property: &AtomicProperty) -> bool {
+        atomic_property
+            .fields()
+            .iter()
+            .any(|field| field.source() == FieldSource::Init)
+    }
+
+    /// Returns the label of the comparison and the conjoined ones,
+    /// with the initial fields taken from the given initial state.
+    fn conjoined_label(
+        &self,
+        atomic_property: &AtomicProperty,
+        state_id: StateId,
+        initial_state_id: Option<StateId>,
+    ) -> Result<ThreeValued, ExecError> {
+        let mut result = ThreeValued::True;
+        for comparison in std::iter::once(atomic_property).chain(atomic_property.conjoined()) {
+            result =
+                result & self.single_comparison_label(comparison, state_id, initial_state_id)?;
+            if result.is_false() {
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    fn single_comparison_label(
+        &self,
+        atomic_property: &AtomicProperty,
+        state_id: StateId,
+        initial_state_id: Option<StateId>,
+    ) -> Result<ThreeValued, ExecError> {
+        if initial_state_id == Some(state_id) && atomic_property.is_reflexive_in_init() {
+            // the field is compared with itself
+            return Ok(ThreeValued::True);
+        }
+        if let Some(label) =
+            self.array_equality_label(atomic_property, state_id, initial_state_id)?
+        {
+            return Ok(label);
+        }
+        let comparison_type = atomic_property.comparison_type();
+
+        // fields without forced or declared signedness are interpreted as unsigned in equalities
+        let signedness_required =
+            !matches!(comparison_type, ComparisonType::Eq | ComparisonType::Ne);
+
+        let left = self.value_bounds(
+            state_id,
+            initial_state_id,
+            atomic_property.left(),
+            signedness_required,
+        )?;
+        let right = self.value_bounds(
+            state_id,
+            initial_state_id,
+            atomic_property.right(),
+            signedness_required,
+        )?;
+
+        let (Some(left), Some(right)) = (left, right) else {
+            // the value bounds are not known
+            return Ok(ThreeValued::Unknown);
+        };
+
+        Ok(Self::resolve_comparison(comparison_type, left, right))
+    }
+
+    /// Returns the label of an equality or inequality of whole arrays,
+    /// e.g. of an array with its initial value, or none if the compared fields are not arrays.
+    ///
+    /// The arrays are compared element-wise.
+    fn array_equality_label(
+        &self,
+        atomic_property: &AtomicProperty,
+        state_id: StateId,
+        initial_state_id: Option<StateId>,
+    ) -> Result<Option<ThreeValued>, ExecError> {
+        let (ValueExpression::Field(left), ValueExpression::Field(right)) =
+            (atomic_property.left(), atomic_property.right())
+        else {
+            return Ok(None);
+        };
+        let is_equality = match atomic_property.comparison_type() {
+            ComparisonType::Eq => true,
+            ComparisonType::Ne => false,
+            _ => return Ok(None),
         };
-        let manip_field = if let Some(index) = left.index() {
+        if [left, right]
+            .iter()
+            .any(|field| field.index().is_some() || field.slice().is_some())
+        {
+            return Ok(None);
+        }
+        let left_array =
+            self.source_field(self.source_state(state_id, initial_state_id, left), left)?;
+        let right_array =
+            self.source_field(self.source_state(state_id, initial_state_id, right), right)?;
+        let (Some(left_run_starts), Some(right_run_starts)) = (
+            left_array.index_run_starts(),
+            right_array.index_run_starts(),
+        ) else {
+            return Ok(None);
+        };
+
+        // only the first elements of the runs of equal elements need to be compared
+        let indices: BTreeSet<u64> = left_run_starts
+            .into_iter()
+            .chain(right_run_starts)
+            .collect();
+
+        let mut result = ThreeValued::True;
+        for index in indices {
+            let left_bounds = Self::element_bounds(left_array, index, left)?;
+            let right_bounds = Self::element_bounds(right_array, index, right)?;
+            let (Some(left_bounds), Some(right_bounds)) = (left_bounds, right_bounds) else {
+                return Ok(Some(ThreeValued::Unknown));
+            };
+            result =
+                result & Self::resolve_comparison(&ComparisonType::Eq, left_bounds, right_bounds);
+            if result.is_false() {
+                break;
+            }
+        }
+        Ok(Some(if is_equality { result } else { !result }))
+    }
+
+    /// Computes the bounds of the array element at the given index.
+    ///
+    /// The elements are interpreted as unsigned, as only their equality matters.
+    fn element_bounds(
+        array: &dyn ManipField,
+        index: u64,
+        field: &FieldReference,
+    ) -> Result<Option<ValueBounds>, ExecError> {
+        let Some(element) = array.index(index) else {
+            return Err(ExecError::IndexInvalid(index, String::from(field.name())));
+        };
+        Ok(element
+            .min_unsigned()
+            .zip(element.max_unsigned())
+            .map(|(min, max)| ValueBounds {
+                min: min as i128,
+                max: max as i128,
+            }))
+    }
+
+    /// Computes the bounds of the value expression in the state,
+    /// with the initial fields taken from the given initial state.
+    ///
+    /// The expression is evaluated on mathematical integers. Returns none
+    /// if the bounds cannot be represented.
+    fn value_bounds(
+        &self,
+        state_id: StateId,
+        initial_state_id: Option<StateId>,
+        value_expression: &ValueExpression,
+        signedness_required: bool,
+    ) -> Result<Option<ValueBounds>, ExecError> {
+        let operation = match value_expression {
+            ValueExpression::Field(field) => {
+                let source_state_id = self.source_state(state_id, initial_state_id, field);
+                return self
+                    .field_bounds(source_state_id, field, signedness_required)
+                    .map(Some);
+            }
+            ValueExpression::Constant(constant) => {
+                return Ok(Some(ValueBounds::exact(*constant as i128)))
+            }
+            ValueExpression::Operation(operation) => operation,
+        };
+
+        let a = self.value_bounds(
+            state_id,
+            initial_state_id,
+            operation.a(),
+            signedness_required,
+        )?;
+        let b = self.value_bounds(
+            state_id,
+            initial_state_id,
+            operation.b(),
+            signedness_required,
+        )?;
+        let (Some(a), Some(b)) = (a, b) else {
+            return Ok(None);
+        };
+
+        Ok(match operation.ty() {
+            OperationType::Add => a
+                .min
+                .checked_add(b.min)
+                .zip(a.max.checked_add(b.max))
+                .map(|(min, max)| ValueBounds { min, max }),
+            OperationType::Sub => a
+                .min
+                .checked_sub(b.max)
+                .zip(a.max.checked_sub(b.min))
+                .map(|(min, max)| ValueBounds { min, max }),
+            OperationType::Mul => {
+                let products = [
+                    a.min.checked_mul(b.min),
+                    a.min.checked_mul(b.max),
+                    a.max.checked_mul(b.min),
+                    a.max.checked_mul(b.max),
+                ];
+                products
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .map(|products| ValueBounds {
+                        min: products.iter().copied().min().unwrap(),
+                        max: products.iter().copied().max().unwrap(),
+                    })
+            }
+            ty @ (OperationType::BitAnd | OperationType::BitOr | OperationType::BitXor) => {
+                Self::bitwise_bounds(ty, a, b)
+            }
+        })
+    }
+
+    fn bitwise_bounds(ty: OperationType, a: ValueBounds, b: ValueBounds) -> Option<ValueBounds> {
+        if let (Some(a), Some(b)) = (a.exact_value(), b.exact_value()) {
+            let value = match ty {
+                OperationType::BitAnd => a & b,
+                OperationType::BitOr => a | b,
+                OperationType::BitXor => a ^ b,
+                _ => panic!("Bitwise operation should be supplied"),
+            };
+            return Some(ValueBounds::exact(value));
+        }
+        if a.min < 0 || b.min < 0 {
+            // the result of bitwise operations on negative values is not bounded by the operands
+            return None;
+        }
+
+        // the result cannot have higher bits set than the operands
+        let max_operand = a.max.max(b.max);
+        let mask = u128::MAX
+            .checked_shr(max_operand.leading_zeros())
+            .unwrap_or(0) as i128;
+        Some(match ty {
+            OperationType::BitAnd => ValueBounds {
+                min: 0,
+                max: a.max.min(b.max),
+            },
+            OperationType::BitOr => ValueBounds {
+                min: a.min.max(b.min),
+                max: mask,
+            },
+            OperationType::BitXor => ValueBounds { min: 0, max: mask },
+            _ => panic!("Bitwise operation should be supplied"),
+        })
+    }
+
+    /// Computes the bounds of the field in the state it is taken from.
+    fn field_bounds(
+        &self,
+        state_id: StateId,
+        field: &FieldReference,
+        signedness_required: bool,
+    ) -> Result<ValueBounds, ExecError> {
+        let field_name = field.name();
+        let manip_field = self.source_field(state_id, field)?;
+        let manip_field = if let Some(index) = field.index() {
             let Some(indexed_manip_field) = manip_field.index(index) else {
-                return Err(ExecError::IndexInvalid(index, String::from(left_name)));
+                return Err(ExecError::IndexInvalid(index, String::from(field_name)));
             };
             indexed_manip_field
         } else {
             manip_field
         };
-
-        let (Some(min_unsigned), Some(max_unsigned)) =
-            (manip_field.min_unsigned(), manip_field.max_unsigned())
-        else {
-            return Err(ExecError::IndexRequired(String::from(left_name)));
+        let sliced_manip_field;
+        let manip_field = if let Some(slice) = field.slice() {
+            if manip_field.num_bits().is_none() {
+                return Err(ExecError::IndexRequired(String::from(field_name)));
+            }
+            let Some(slice_field) = manip_field.slice(slice.lowest_bit(), slice.highest_bit())
+            else {
+                return Err(ExecError::SliceInvalid(
+                    slice.highest_bit(),
+                    slice.lowest_bit(),
+                    String::from(field_name),
+                ));
+            };
+            sliced_manip_field = slice_field;
+            &sliced_manip_field
+        } else {
+            manip_field
         };
-        let right_unsigned = atomic_property.right_number_unsigned();
 
-        let comparison_result = match atomic_property.comparison_type() {
-            ComparisonType::Eq => {
-                if min_unsigned == max_unsigned {
-                    ThreeValued::from_bool(min_unsigned == right_unsigned)
-                } else {
-                    ThreeValued::Unknown
+        let signedness = match field.forced_signedness() {
+            Signedness::None if field.slice().is_none() => {
+                // use the signedness the field was declared with
+                match field.source() {
+                    FieldSource::State | FieldSource::Init => {
+                        AbstrState::<M>::signedness(field_name)
+                    }
+                    FieldSource::Input => AbstrInput::<M>::signedness(field_name),
+                    FieldSource::Param => AbstrParam::<M>::signedness(field_name),
+                    // the panic ids are not negative
+                    FieldSource::Panic => Signedness::Unsigned,
                 }
             }
-            ComparisonType::Ne => {
-                if min_unsigned == max_unsigned {
-                    ThreeValued::from_bool(min_unsigned != right_unsigned)
-                } else {
-                    ThreeValued::Unknown
+            forced_signedness => forced_signedness,
+        };
+
+        let bounds = match signedness {
+            Signedness::None => {
+                if signedness_required {
+                    // signedness neither forced nor declared
+                    return Err(ExecError::SignednessNotEstabilished(field.to_string()));
                 }
+                // the interpretation does not matter, use the unsigned one
+                manip_field
+                    .min_unsigned()
+                    .zip(manip_field.max_unsigned())
+                    .map(|(min, max)| ValueBounds {
+                        min: min as i128,
+                        max: max as i128,
+                    })
             }
-            comparison_type => {
-                match left.forced_signedness() {
-                    Signedness::None => {
-                        // signedness not specified
-                        // TODO: try to estabilish signedness by using the types in this case
-                        return Err(ExecError::SignednessNotEstabilished(left.to_string()));
-                    }
-                    Signedness::Unsigned => Self::resolve_inequality(
-                        comparison_type,
-                        min_unsigned,
-                        max_unsigned,
-                        right_unsigned,
-                    ),
-                    Signedness::Signed => {
-                        let (Some(min_signed), Some(max_signed)) =
-                            (manip_field.min_signed(), manip_field.max_signed())
-                        else {
-                            return Err(ExecError::IndexRequired(String::from(left_name)));
-                        };
-                        let right_signed = atomic_property.right_number_signed();
-                        Self::resolve_inequality(
-                            comparison_type,
-                            min_signed,
-                            max_signed,
-                            right_signed,
-                        )
-                    }
-                }
+            Signedness::Unsigned => manip_field
+                .min_unsigned()
+                .zip(manip_field.max_unsigned())
+                .map(|(min, max)| ValueBounds {
+                    min: min as i128,
+                    max: max as i128,
+                }),
+            Signedness::Signed => {
+                manip_field
+                    .min_signed()
+                    .zip(manip_field.max_signed())
+                    .map(|(min, max)| ValueBounds {
+                        min: min as i128,
+                        max: max as i128,
+                    })
             }
         };
+        bounds.ok_or_else(|| ExecError::IndexRequired(String::from(field_name)))
+    }
 
-        Ok(comparison_result)
+    /// Returns the states the field can be taken from.
+    ///
+    /// A field in the initial states can be taken from each of them.
+    fn source_states(&self, state_id: StateId, field: &FieldReference) -> Vec<StateId> {
+        match field.source() {
+            FieldSource::Init => self.initial_iter().collect(),
+            _ => vec![state_id],
+        }
+    }
+
+    /// Returns the state the field is taken from, given the initial state to take the initial fields from.
+    fn source_state(
+        &self,
+        state_id: StateId,
+        initial_state_id: Option<StateId>,
+        field: &FieldReference,
+    ) -> StateId {
+        match field.source() {
+            FieldSource::Init => {
+                initial_state_id.expect("Initial state should be given for the initial fields")
+            }
+            _ => state_id,
+        }
+    }
+
+    /// Returns the referenced field from its source, before indexing or slicing.
+    ///
+    /// A field in the initial states is taken from the given state, which should be initial.
+    fn source_field(
+        &self,
+        state_id: StateId,
+        field: &FieldReference,
+    ) -> Result<&dyn ManipField, ExecError> {
+        let field_name = field.name();
+        let manip_field = match field.source() {
+            FieldSource::State | FieldSource::Init => {
+                self.state_data(state_id).result.get(field_name)
+            }
+            FieldSource::Input => {
+                let Some(input) = self.entry_input(state_id) else {
+                    return Err(ExecError::FieldSourceNotTracked(field.to_string()));
+                };
+                input.get(field_name)
+            }
+            FieldSource::Param => {
+                let Some(param) = self.entry_param(state_id) else {
+                    return Err(ExecError::FieldSourceNotTracked(field.to_string()));
+                };
+                param.get(field_name)
+            }
+            FieldSource::Panic => {
+                let manip_field: &dyn ManipField = &self.state_data(state_id).panic;
+                Some(manip_field)
+            }
+        };
+        manip_field.ok_or_else(|| ExecError::FieldNotFound(String::from(field_name)))
     }
 
     /// Returns state ids in nontrivial strongly connected components.
@@ -130,51 +582,63 @@ impl<M: FullMachine> StateSpace<M> {
         result
     }
 
-    fn resolve_inequality<T: Ord>(
-        inequality_type: &ComparisonType,
-        min_left: T,
-        max_left: T,
-        right: T,
+    fn resolve_comparison(
+        comparison_type: &ComparisonType,
+        left: ValueBounds,
+        right: ValueBounds,
     ) -> ThreeValued {
-        // TODO: resolve inequality using mck types
-        match inequality_type {
-            ComparisonType::Lt => {
-                if max_left < right {
-                    ThreeValued::True
-                } else if min_left >= right {
-                    ThreeValued::False
-                } else {
-                    ThreeValued::Unknown
-                }
-            }
-            ComparisonType::Le => {
-                if max_left <= right {
-                    ThreeValued::True
-                } else if min_left > right {
+        // TODO: resolve comparison using mck types
+        match comparison_type {
+            ComparisonType::Eq => {
+                if let (Some(left), Some(right)) = (left.exact_value(), right.exact_value()) {
+                    ThreeValued::from_bool(left == right)
+                } else if left.max < right.min || left.min > right.max {
                     ThreeValued::False
                 } else {
                     ThreeValued::Unknown
                 }
             }
-            ComparisonType::Gt => {
-                if min_left > right {
+            ComparisonType::Ne => !Self::resolve_comparison(&ComparisonType::Eq, left, right),
+            ComparisonType::Lt => {
+                if left.max < right.min {
                     ThreeValued::True
-                } else if max_left <= right {
+                } else if left.min >= right.max {
                     ThreeValued::False
                 } else {
                     ThreeValued::Unknown
                 }
             }
-            ComparisonType::Ge => {
-                if min_left >= right {
+            ComparisonType::Le => {
+                if left.max <= right.min {
                     ThreeValued::True
-                } else if max_left < right {
+                } else if left.min > right.max {
                     ThreeValued::False
                 } else {
                     ThreeValued::Unknown
                 }
             }
-            _ => panic!("Inequality comparison should be supplied"),
+            ComparisonType::Gt => Self::resolve_comparison(&ComparisonType::Lt, right, left),
+            ComparisonType::Ge => Self::resolve_comparison(&ComparisonType::Le, right, left),
+        }
+    }
+}
+
+/// Inclusive bounds of a value expression.
+#[derive(Clone, Copy, Debug)]
+struct ValueBounds {
+    min: i128,
+    max: i128,
+}
+
+impl ValueBounds {
+    fn exact(value: i128) -> Self {
+        ValueBounds {
+            min: value,
+            max: value,
         }
     }
+
+    fn exact_value(&self) -> Option<i128> {
+        (self.min == self.max).then_some(self.min)
+    }
 }
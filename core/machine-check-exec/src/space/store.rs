@@ -4,13 +4,47 @@ use std::{
 };
 
 use bimap::{BiMap, Overwritten};
+use machine_check_common::property::{FieldSource, Property};
 use machine_check_common::StateId;
 
-use crate::{AbstrPanicState, WrappedState};
+use crate::{AbstrInput, AbstrPanicState, AbstrParam, WrappedInput, WrappedParam, WrappedState};
 
 use mck::{concr::FullMachine, misc::MetaWrap};
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+/// Which parts of the step entering a state are tracked alongside it.
+///
+/// If tracked, the states entered with different inputs or parameters are distinct,
+/// so that the input and parameter fields can be referenced by the property.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryTracking {
+    pub input: bool,
+    pub param: bool,
+}
+
+impl EntryTracking {
+    /// Returns the tracking required to reference the fields in the property.
+    pub fn required_by(property: &Property) -> Self {
+        EntryTracking {
+            input: property.references_source(FieldSource::Input),
+            param: property.references_source(FieldSource::Param),
+        }
+    }
+
+    /// Returns whether the tracking includes everything the other one tracks.
+    pub fn covers(&self, other: &EntryTracking) -> bool {
+        (self.input || !other.input) && (self.param || !other.param)
+    }
+}
+
+/// State data together with the tracked input and parameter of the step entering it.
+type StoredState<M> = (
+    WrappedState<M>,
+    Option<WrappedInput<M>>,
+    Option<WrappedParam<M>>,
+);
+
 /// A structure that maps state data to the IDs.
 ///
 /// Every state ID is guaranteed to map to the same state data.
@@ -18,9 +52,11 @@ use std::fmt::Debug;
 /// when it is not in use. The state can then get a new ID.
 pub struct StateStore<M: FullMachine> {
     /// Bidirectional map from state ids to the state values.
-    map: BiMap<StateId, WrappedState<M>>,
+    map: BiMap<StateId, StoredState<M>>,
     /// Next state id.
     next_state_id: StateId,
+    /// Tracking of the steps entering the states.
+    entry_tracking: EntryTracking,
 }
 
 impl<M: FullMachine> Debug for StateStore<M> {
@@ -30,22 +66,40 @@ impl<M: FullMachine> Debug for StateStore<M> {
         f.debug_struct("StateStore")
             .field("map", &ordered_map)
             .field("next_state_id", &self.next_state_id)
+            .field("entry_tracking", &self.entry_tracking)
             .finish()
     }
 }
 
 impl<M: FullMachine> StateStore<M> {
-    pub fn new() -> Self {
+    pub fn new(entry_tracking: EntryTracking) -> Self {
         Self {
             map: BiMap::new(),
             next_state_id: StateId(NonZeroU64::MIN),
+            entry_tracking,
         }
     }
 
+    pub fn entry_tracking(&self) -> EntryTracking {
+        self.entry_tracking
+    }
+
     // Get state id, add if necessary. Return if we have inserted
-    pub fn state_id(&mut self, state: AbstrPanicState<M>) -> (StateId, bool) {
+    //
+    // The entry is only stored if it is tracked and given.
+    pub fn state_id(
+        &mut self,
+        state: AbstrPanicState<M>,
+        entry: Option<(&AbstrInput<M>, &AbstrParam<M>)>,
+    ) -> (StateId, bool) {
         // Check if the state already corresponds to some id.
-        let state = MetaWrap(state);
+        let input = entry
+            .filter(|_| self.entry_tracking.input)
+            .map(|(input, _)| MetaWrap(input.clone()));
+        let param = entry
+            .filter(|_| self.entry_tracking.param)
+            .map(|(_, param)| MetaWrap(param.clone()));
+        let state = (MetaWrap(state), input, param);
         if let Some(state_id) = self.map.get_by_right(&state) {
             // return that we have not inserted and the id
             return (*state_id, false);
@@ -72,8 +126,20 @@ impl<M: FullMachine> StateStore<M> {
     }
 
     pub fn state_data(&self, state_id: StateId) -> &AbstrPanicState<M> {
-        if let Some(state) = &self.map.get_by_left(&state_id) {
-            &state.0
+        &self.stored_state(state_id).0 .0
+    }
+
+    pub fn entry_input(&self, state_id: StateId) -> Option<&AbstrInput<M>> {
+        self.stored_state(state_id).1.as_ref().map(|input| &input.0)
+    }
+
+    pub fn entry_param(&self, state_id: StateId) -> Option<&AbstrParam<M>> {
+        self.stored_state(state_id).2.as_ref().map(|param| &param.0)
+    }
+
+    fn stored_state(&self, state_id: StateId) -> &StoredState<M> {
+        if let Some(state) = self.map.get_by_left(&state_id) {
+            state
         } else {
             panic!("State {} should be in state map", state_id);
         }
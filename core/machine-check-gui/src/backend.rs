@@ -22,7 +22,7 @@ const FAVICON_ICO: &[u8] = include_bytes!("../content/favicon.ico");
 /// Runs the Graphical User Interface backend.
 pub fn run<M: FullMachine>(
     system: M,
-    property: Option<Property>,
+    properties: Vec<Property>,
     strategy: Strategy,
 ) -> Result<(), ExecError> {
     // TODO: allow setting custom titles instead of relying on the binary name
@@ -38,7 +38,7 @@ pub fn run<M: FullMachine>(
     let abstract_system = <M::Abstr as mck::abstr::Abstr<M>>::from_concrete(system);
     // create the backend
     let backend = Backend::new(
-        Workspace::<M>::new(Framework::new(abstract_system, strategy), property),
+        Workspace::<M>::new(Framework::new(abstract_system, strategy), properties),
         exec_name.clone(),
     );
     let response_fn = move |_web_view_id: WebViewId, request: http::Request<Vec<u8>>| {
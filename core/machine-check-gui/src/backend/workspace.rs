@@ -42,13 +42,10 @@ impl WorkspaceProperty {
 }
 
 impl<M: FullMachine> Workspace<M> {
-    pub fn new(framework: Framework<M>, property: Option<Property>) -> Self {
-        // always put the inherent property first, add the other property afterwards if there is one
+    pub fn new(framework: Framework<M>, given_properties: Vec<Property>) -> Self {
+        // always put the inherent property first, add the given properties afterwards
         let mut properties = vec![WorkspaceProperty::new(Property::inherent())];
-
-        if let Some(property) = property {
-            properties.push(WorkspaceProperty::new(property));
-        }
+        properties.extend(given_properties.into_iter().map(WorkspaceProperty::new));
 
         Workspace {
             framework,
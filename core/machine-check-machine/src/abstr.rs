@@ -4,6 +4,7 @@ mod item_struct;
 use syn::{GenericArgument, Item, Path};
 
 use crate::{
+    description::FieldSignedness,
     support::manipulate::{self},
     util::{create_angle_bracketed_path_arguments, create_type_path},
     wir::{
@@ -78,6 +79,7 @@ impl IntoSyn<Path> for WAbstrItemImplTrait {
 
 pub(crate) fn create_abstract_description(
     description: WDescription<YConverted>,
+    field_signedness: &FieldSignedness,
 ) -> (WDescription<YAbstr>, Vec<Item>) {
     let mut machine_types = Vec::new();
     for item_impl in description.impls.iter() {
@@ -106,7 +108,7 @@ pub(crate) fn create_abstract_description(
 
     // add field-manipulate
     misc_items.extend(
-        manipulate::for_abstract_description(&abstract_description)
+        manipulate::for_abstract_description(&abstract_description, field_signedness)
             .into_iter()
             .map(Item::Impl),
     );
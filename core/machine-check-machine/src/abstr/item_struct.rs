@@ -12,8 +12,9 @@ use crate::{
     wir::{WElementaryType, WItemStruct},
 };
 
-use self::from_concrete::from_concrete_fn;
+use self::{concrete_choice::concrete_choice_fn, from_concrete::from_concrete_fn};
 
+mod concrete_choice;
 mod from_concrete;
 mod phi;
 
@@ -74,11 +75,12 @@ fn create_abstr(item_struct: &WItemStruct<WElementaryType>) -> ItemImpl {
         leading_colon: None,
         segments: concr_segments,
     };
-    let concr_ty = create_type_path(concr_path);
+    let concr_ty = create_type_path(concr_path.clone());
     let abstr_path =
         create_path_with_last_generic_type(path!(::mck::abstr::Abstr), concr_ty.clone());
 
     let from_concrete_fn = ImplItem::Fn(from_concrete_fn(item_struct, concr_ty));
+    let concrete_choice_fn = ImplItem::Fn(concrete_choice_fn(item_struct, concr_path));
 
     ItemImpl {
         attrs: vec![],
@@ -91,6 +93,6 @@ fn create_abstr(item_struct: &WItemStruct<WElementaryType>) -> ItemImpl {
             item_struct.ident.to_syn_ident(),
         ))),
         brace_token: Default::default(),
-        items: vec![from_concrete_fn],
+        items: vec![from_concrete_fn, concrete_choice_fn],
     }
 }
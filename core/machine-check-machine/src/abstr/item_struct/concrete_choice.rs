@@ -0,0 +1,80 @@
+use syn::{spanned::Spanned, Expr, ExprField, Ident, ImplItemFn, Path, Stmt, Token};
+use syn_path::path;
+
+use crate::{
+    util::{
+        create_assign, create_expr_call, create_expr_ident, create_expr_path,
+        create_field_value_ident, create_ident, create_impl_item_fn, create_let_bare, create_self,
+        create_self_arg, create_struct_expr, create_type_path, path_starts_with_global_names,
+        ArgType,
+    },
+    wir::{WElementaryType, WItemStruct},
+};
+
+pub fn concrete_choice_fn(
+    item_struct: &WItemStruct<WElementaryType>,
+    concr_path: Path,
+) -> ImplItemFn {
+    let self_arg = create_self_arg(ArgType::Reference);
+    let span = item_struct.ident.span();
+
+    let mut local_stmts = Vec::new();
+    let mut assign_stmts = Vec::new();
+    let mut struct_field_values = Vec::new();
+
+    for (index, field) in item_struct.fields.iter().enumerate() {
+        let abstr_field_expr = Expr::Field(ExprField {
+            attrs: Vec::new(),
+            base: Box::new(create_self()),
+            dot_token: Token![.](span),
+            member: syn::Member::Named(field.ident.to_syn_ident()),
+        });
+
+        let mut concr_field_path = field.ty.clone().into_syn_path();
+
+        let choice_expr = create_expr_call(
+            create_expr_path(path!(::mck::abstr::Abstr::concrete_choice)),
+            vec![(ArgType::Reference, abstr_field_expr)],
+        );
+
+        // the mck types must be converted back to the machine-check types
+        let field_expr = if path_starts_with_global_names(&concr_field_path, &["mck", "forward"]) {
+            concr_field_path.segments[1].ident =
+                Ident::new("concr", concr_field_path.segments[1].span());
+
+            let mck_field_temp_ident = create_ident(&format!("__mck_from_abstr_{}", index));
+            local_stmts.push(create_let_bare(
+                mck_field_temp_ident.clone(),
+                Some(create_type_path(concr_field_path)),
+            ));
+            assign_stmts.push(create_assign(
+                mck_field_temp_ident.clone(),
+                choice_expr,
+                true,
+            ));
+            create_expr_call(
+                create_expr_path(path!(::mck::concr::IntoMck::from_mck)),
+                vec![(ArgType::Normal, create_expr_ident(mck_field_temp_ident))],
+            )
+        } else {
+            choice_expr
+        };
+
+        struct_field_values.push(create_field_value_ident(
+            field.ident.to_syn_ident(),
+            field_expr,
+        ));
+    }
+    local_stmts.extend(assign_stmts);
+    local_stmts.push(Stmt::Expr(
+        create_struct_expr(concr_path.clone(), struct_field_values),
+        None,
+    ));
+
+    create_impl_item_fn(
+        create_ident("concrete_choice"),
+        vec![self_arg],
+        Some(create_type_path(concr_path)),
+        local_stmts,
+    )
+}
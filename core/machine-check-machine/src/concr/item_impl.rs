@@ -1,9 +1,9 @@
 use syn_path::path;
 
 use syn::{
-    punctuated::Punctuated, spanned::Spanned, Arm, Expr, ExprLit, ExprMatch, FnArg, Ident,
-    ImplItem, Item, ItemImpl, Lifetime, Lit, LitInt, LitStr, Pat, PatIdent, PatType, Path,
-    PathSegment, Stmt, Token, Type, TypeReference,
+    parse_quote, punctuated::Punctuated, spanned::Spanned, Arm, Expr, ExprLit, ExprMatch, FnArg,
+    Ident, ImplItem, ImplItemFn, Item, ItemImpl, Lifetime, Lit, LitInt, LitStr, Pat, PatIdent,
+    PatType, Path, PathSegment, Stmt, Token, Type, TypeReference,
 };
 
 use crate::{
@@ -154,6 +154,12 @@ fn create_panic_message_impl(item_impl: &ItemImpl, panic_messages: &[String]) ->
         vec![Stmt::Expr(Expr::Match(panic_match_expr), None)],
     );
 
+    let panic_messages_fn: ImplItemFn = parse_quote!(
+        fn panic_messages() -> ::std::vec::Vec<&'static str> {
+            ::std::vec![#(#panic_messages),*]
+        }
+    );
+
     ItemImpl {
         attrs: vec![],
         defaultness: None,
@@ -163,6 +169,9 @@ fn create_panic_message_impl(item_impl: &ItemImpl, panic_messages: &[String]) ->
         trait_: Some((None, path!(::mck::misc::PanicMessage), Token![for](span))),
         self_ty: item_impl.self_ty.clone(),
         brace_token: Default::default(),
-        items: vec![ImplItem::Fn(panic_message_fn)],
+        items: vec![
+            ImplItem::Fn(panic_message_fn),
+            ImplItem::Fn(panic_messages_fn),
+        ],
     }
 }
@@ -8,25 +8,32 @@ mod from_syn;
 mod infer_types;
 mod resolve_use;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use machine_check_common::Signedness;
 
 use quote::ToTokens;
 use syn::Item;
 
 use crate::{
     support::error_list::ErrorList,
-    wir::{WDescription, WSpan, YConverted},
+    wir::{WDescription, WIdent, WSpan, YConverted},
 };
 
+/// Declared signedness of fields, keyed by the struct and field idents.
+///
+/// Fields without a declared signedness are not present.
+pub type FieldSignedness = BTreeMap<WIdent, BTreeMap<WIdent, Signedness>>;
+
 pub fn create_description(
     items: Vec<Item>,
-) -> Result<(WDescription<YConverted>, Vec<String>), crate::Errors> {
+) -> Result<(WDescription<YConverted>, Vec<String>, FieldSignedness), crate::Errors> {
     create_description_inner(items).map_err(Errors::convert_inner)
 }
 
 fn create_description_inner(
     mut items: Vec<Item>,
-) -> Result<(WDescription<YConverted>, Vec<String>), Errors> {
+) -> Result<(WDescription<YConverted>, Vec<String>, FieldSignedness), Errors> {
     let mut macro_expander = expand_macros::MacroExpander::new();
     loop {
         resolve_use::resolve_use(&mut items)?;
@@ -53,6 +60,7 @@ fn create_description_inner(
     let (w_description, panic_messages) = convert_total::convert_total(w_description);
     let w_description = convert_to_ssa::convert_to_ssa(w_description)?;
     let w_description = infer_types::infer_types(w_description, &HashMap::new())?;
+    let field_signedness = convert_types::field_signedness(&w_description);
     let w_description = convert_types::convert_types(w_description)?;
 
     /*println!(
@@ -63,7 +71,7 @@ fn create_description_inner(
 
     //let items: Vec<Item> = w_description.into_syn().items;
 
-    Ok((w_description, panic_messages))
+    Ok((w_description, panic_messages, field_signedness))
 }
 
 #[derive(thiserror::Error, Debug, Clone)]
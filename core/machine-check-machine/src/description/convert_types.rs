@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use convert_calls::convert_call_fn_path;
+use machine_check_common::Signedness;
 
 use crate::wir::{
     WBasicType, WBlock, WDescription, WElementaryType, WExpr, WExprCall, WExprHighCall,
@@ -9,7 +10,7 @@ use crate::wir::{
     WStmtIf, WType, YConverted, YInferred, ZConverted, ZSsa,
 };
 
-use super::Errors;
+use super::{Errors, FieldSignedness};
 
 mod convert_calls;
 
@@ -74,6 +75,27 @@ pub fn convert_types(
     Ok(WDescription { structs, impls })
 }
 
+/// Collects the signedness of struct fields before it is lost by type conversion.
+pub fn field_signedness(description: &WDescription<YInferred>) -> FieldSignedness {
+    let mut result = FieldSignedness::new();
+    for item_struct in &description.structs {
+        let struct_signedness = item_struct
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let signedness = match field.ty {
+                    WBasicType::Unsigned(_) => Signedness::Unsigned,
+                    WBasicType::Signed(_) => Signedness::Signed,
+                    _ => return None,
+                };
+                Some((field.ident.clone(), signedness))
+            })
+            .collect();
+        result.insert(item_struct.ident.clone(), struct_signedness);
+    }
+    result
+}
+
 fn convert_basic_type(ty: WBasicType) -> WElementaryType {
     match ty {
         WBasicType::Bitvector(width) => WElementaryType::Bitvector(width),
@@ -93,7 +93,8 @@ fn process_items(items: &mut Vec<Item>) -> Result<(), Errors> {
         None
     };
 
-    let (description, panic_messages) = description::create_description(items.clone())?;
+    let (description, panic_messages, field_signedness) =
+        description::create_description(items.clone())?;
 
     if let Some(out_dir) = &out_dir {
         std::fs::write(
@@ -104,7 +105,7 @@ fn process_items(items: &mut Vec<Item>) -> Result<(), Errors> {
     }
 
     let (abstract_description, misc_abstract_items) =
-        abstr::create_abstract_description(description);
+        abstr::create_abstract_description(description, &field_signedness);
 
     if let Some(out_dir) = &out_dir {
         std::fs::write(
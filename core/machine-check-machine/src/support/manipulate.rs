@@ -1,4 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+
+use machine_check_common::Signedness;
 
 use proc_macro2::{Literal, Span};
 use quote::ToTokens;
@@ -11,6 +13,7 @@ use syn_path::path;
 
 use crate::{
     abstr::{WAbstrItemImplTrait, YAbstr},
+    description::FieldSignedness,
     refin::{WRefinItemImplTrait, YRefin},
     util::{
         create_arg, create_expr_call, create_expr_field_named, create_expr_ident, create_expr_path,
@@ -18,10 +21,13 @@ use crate::{
         create_path_from_ident, create_path_with_last_generic_type, create_self, create_self_arg,
         create_type_path, create_type_reference, ArgType,
     },
-    wir::{WDescription, WElementaryType, WItemImplTrait},
+    wir::{WDescription, WElementaryType, WIdent, WItemImplTrait},
 };
 
-pub(crate) fn for_abstract_description(description: &WDescription<YAbstr>) -> Vec<ItemImpl> {
+pub(crate) fn for_abstract_description(
+    description: &WDescription<YAbstr>,
+    field_signedness: &FieldSignedness,
+) -> Vec<ItemImpl> {
     let mut impls_to_add = Vec::new();
 
     let mut process_idents = HashSet::new();
@@ -57,12 +63,20 @@ pub(crate) fn for_abstract_description(description: &WDescription<YAbstr>) -> Ve
                 })
                 .collect();
 
-            impls_to_add.push(create_manipulatable_impl(
+            let mut manipulatable_impl = create_manipulatable_impl(
                 item_struct.ident.span(),
                 item_struct.ident.to_syn_ident(),
                 &manipulable_field_idents,
                 ManipulateKind::Forward,
-            ));
+            );
+            // only the abstract fields can be interpreted using the declared signedness
+            manipulatable_impl
+                .items
+                .push(ImplItem::Fn(create_signedness_fn(
+                    field_signedness.get(&item_struct.ident),
+                    item_struct.ident.span(),
+                )));
+            impls_to_add.push(manipulatable_impl);
         }
     }
     impls_to_add
@@ -305,3 +319,75 @@ fn create_field_names_fn(field_idents: &[Ident], span: Span) -> ImplItemFn {
         vec![Stmt::Expr(result_expr, None)],
     )
 }
+
+fn create_signedness_fn(
+    struct_signedness: Option<&BTreeMap<WIdent, Signedness>>,
+    span: Span,
+) -> ImplItemFn {
+    let fn_ident: Ident = Ident::new("signedness", span);
+    let name_ident = create_ident("name");
+    let name_arg = create_arg(
+        ArgType::Reference,
+        name_ident.clone(),
+        Some(create_type_path(create_path_from_ident(create_ident(
+            "str",
+        )))),
+    );
+    let signedness_path = kind_path(ManipulateKind::Forward, "Signedness", span);
+    let return_type = create_type_path(signedness_path.clone());
+
+    let signedness_expr = |variant: &str| {
+        let mut variant_path = signedness_path.clone();
+        variant_path.segments.push(PathSegment {
+            ident: Ident::new(variant, span),
+            arguments: PathArguments::None,
+        });
+        create_expr_path(variant_path)
+    };
+
+    // add arms
+    let mut arms = Vec::new();
+    for (field_ident, signedness) in struct_signedness.into_iter().flatten() {
+        let variant = match signedness {
+            Signedness::None => continue,
+            Signedness::Unsigned => "Unsigned",
+            Signedness::Signed => "Signed",
+        };
+        arms.push(Arm {
+            attrs: vec![],
+            pat: Pat::Lit(ExprLit {
+                attrs: vec![],
+                lit: Lit::Str(LitStr::new(field_ident.name(), Span::call_site())),
+            }),
+            guard: Default::default(),
+            fat_arrow_token: Default::default(),
+            body: Box::new(signedness_expr(variant)),
+            comma: Some(Default::default()),
+        });
+    }
+
+    // add default arm
+    arms.push(Arm {
+        attrs: vec![],
+        pat: create_pat_wild(),
+        guard: Default::default(),
+        fat_arrow_token: Default::default(),
+        body: Box::new(signedness_expr("None")),
+        comma: Some(Default::default()),
+    });
+
+    let match_expr = Expr::Match(ExprMatch {
+        attrs: vec![],
+        match_token: Default::default(),
+        expr: Box::new(create_expr_ident(name_ident)),
+        brace_token: Default::default(),
+        arms,
+    });
+
+    create_impl_item_fn(
+        fn_ident,
+        vec![name_arg],
+        Some(return_type),
+        vec![Stmt::Expr(match_expr, None)],
+    )
+}
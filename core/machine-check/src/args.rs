@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::{ArgGroup, Args, Parser, ValueEnum};
 
 /// Arguments for executing machine-check.
@@ -5,7 +7,7 @@ use clap::{ArgGroup, Args, Parser, ValueEnum};
 #[clap(group(ArgGroup::new("property-group")
 .required(true)
 .multiple(true)
-.args(&["property", "inherent","gui"]),
+.args(&["property", "ltl", "property_file", "max_steps_until", "min_steps_until", "inherent","gui"]),
 ))]
 #[clap(group(ArgGroup::new("verbosity-group")
 .required(false)
@@ -49,13 +51,191 @@ pub struct ExecArgs {
     pub assume_inherent: bool,
     /// Verifies a given property.
     ///
+    /// Can be given multiple times, the properties are then verified in sequence
+    /// and the results are reported for each of them.
     /// It will be first verified that the inherent property holds unless `assume_inherent` is given.
     #[arg(long, conflicts_with("inherent"))]
-    pub property: Option<String>,
+    pub property: Vec<String>,
+    /// Verifies a given Linear Temporal Logic property.
+    ///
+    /// The property holds if all paths from the initial states satisfy it.
+    /// It will be first verified that the inherent property holds unless `assume_inherent` is given.
+    #[arg(long, conflicts_with("inherent"), conflicts_with("property"))]
+    pub ltl: Option<String>,
+    /// Verifies each named property of a given property file.
+    ///
+    /// The file contains definitions `let name = <property>;` that can be referenced
+    /// by their names and named properties `property name: <property>;`,
+    /// with comments starting with `//`. The results are reported for each name.
+    /// It will be first verified that the inherent property holds unless `assume_inherent` is given.
+    #[arg(
+        long,
+        conflicts_with("inherent"),
+        conflicts_with("property"),
+        conflicts_with("ltl"),
+        conflicts_with("gui")
+    )]
+    pub property_file: Option<PathBuf>,
+    /// Computes the maximum number of steps until a given formula holds.
+    ///
+    /// Every path from the initial states reaches the formula within the number of steps.
+    /// The number is computed on the state space and a path reaching the formula after it is shown.
+    /// If some path never reaches it, the number is infinite and the path is shown.
+    /// It will be first verified that the inherent property holds unless `assume_inherent` is given.
+    #[arg(
+        long,
+        conflicts_with("inherent"),
+        conflicts_with("property"),
+        conflicts_with("ltl"),
+        conflicts_with("property_file"),
+        conflicts_with("gui"),
+        conflicts_with("fairness")
+    )]
+    pub max_steps_until: Option<String>,
+    /// Computes the minimum number of steps until a given formula holds.
+    ///
+    /// Some path from the initial states reaches the formula within the number of steps.
+    /// The number is computed on the state space and a path reaching the formula after it is shown.
+    /// If no path reaches it, the number is infinite.
+    /// It will be first verified that the inherent property holds unless `assume_inherent` is given.
+    #[arg(
+        long,
+        conflicts_with("inherent"),
+        conflicts_with("property"),
+        conflicts_with("ltl"),
+        conflicts_with("property_file"),
+        conflicts_with("gui"),
+        conflicts_with("fairness"),
+        conflicts_with("max_steps_until")
+    )]
+    pub min_steps_until: Option<String>,
+    /// Reports the results of the subproperties of the verified properties.
+    ///
+    /// The result of each displayed subproperty in the initial states
+    /// is reported in the tree of subproperties.
+    #[arg(
+        long,
+        conflicts_with("gui"),
+        conflicts_with("max_steps_until"),
+        conflicts_with("min_steps_until")
+    )]
+    pub subproperty_results: bool,
+    /// Restricts the verified property to fair paths.
+    ///
+    /// A path is fair if each given fairness constraint holds infinitely often on it.
+    /// Can be given multiple times.
+    #[arg(long, conflicts_with("inherent"))]
+    pub fairness: Vec<String>,
+    /// Verifies the properties as written, without simplifying them first.
+    ///
+    /// The simplification does not change the verification results, but the subproperties
+    /// it removes, e.g. double negations and constants, are not reported in the subproperty results.
+    #[arg(long)]
+    pub no_simplify: bool,
 
     /// The verification strategy.
     #[arg(long, default_value("default"))]
     pub strategy: ExecStrategy,
+    /// The number of threads computing the successors of states in parallel.
+    ///
+    /// The verification result and the state space do not depend on it.
+    #[arg(long, default_value("1"))]
+    pub threads: usize,
+
+    /// Stops the verification with an error after the given number of seconds.
+    ///
+    /// The limits are checked between the verification steps. If a limit is reached,
+    /// the statistics and the culprit that would be refined next are reported.
+    #[arg(long)]
+    pub timeout: Option<u64>,
+    /// Stops the verification with an error when another refinement
+    /// would exceed the given number of refinements.
+    #[arg(long)]
+    pub max_refinements: Option<usize>,
+    /// Stops the verification with an error when the state space
+    /// has more than the given number of states.
+    #[arg(long)]
+    pub max_states: Option<usize>,
+
+    /// Periodically saves the checkpoint of the verification to the given file.
+    ///
+    /// The checkpoint is also saved when a resource limit is reached.
+    /// The verification can be resumed from it by `resume`.
+    #[arg(
+        long,
+        conflicts_with("gui"),
+        conflicts_with("max_steps_until"),
+        conflicts_with("min_steps_until")
+    )]
+    pub checkpoint: Option<PathBuf>,
+    /// The number of seconds between saving the checkpoints.
+    #[arg(long, requires("checkpoint"), default_value("600"))]
+    pub checkpoint_every: u64,
+    /// Resumes the verification from the checkpoint in the given file.
+    ///
+    /// The checkpoint must have been saved for the same system, strategy and properties.
+    /// Resuming replays the refinements saved in the checkpoint, regenerating the state space
+    /// after each of them, so it takes about as long as generating the state space took before
+    /// saving. Only the model checking is not repeated.
+    #[arg(
+        long,
+        conflicts_with("gui"),
+        conflicts_with("max_steps_until"),
+        conflicts_with("min_steps_until")
+    )]
+    pub resume: Option<PathBuf>,
+
+    /// Exports the final state space to the given file.
+    ///
+    /// The format is given by the file extension, `dot` or `gv` for Graphviz DOT
+    /// and `graphml` for GraphML. The states are labelled by their field values
+    /// and the transitions by their representative inputs.
+    #[arg(
+        long,
+        conflicts_with("gui"),
+        conflicts_with("max_steps_until"),
+        conflicts_with("min_steps_until")
+    )]
+    pub export_space: Option<PathBuf>,
+    /// Labels the states of the exported state space by the valuations
+    /// of the subproperties of the verified properties.
+    #[arg(long, requires("export_space"))]
+    pub export_labellings: bool,
+}
+
+impl Default for ExecArgs {
+    /// Returns the arguments with the defaults of the command line and nothing to verify.
+    ///
+    /// Intended for constructing the arguments programmatically, e.g.
+    /// `ExecArgs { inherent: true, ..Default::default() }`.
+    fn default() -> Self {
+        Self {
+            silent: false,
+            verbose: 0,
+            batch: false,
+            gui: false,
+            inherent: false,
+            assume_inherent: false,
+            property: Vec::new(),
+            ltl: None,
+            property_file: None,
+            max_steps_until: None,
+            min_steps_until: None,
+            subproperty_results: false,
+            fairness: Vec::new(),
+            no_simplify: false,
+            strategy: ExecStrategy::Default,
+            threads: 1,
+            timeout: None,
+            max_refinements: None,
+            max_states: None,
+            checkpoint: None,
+            checkpoint_every: 600,
+            resume: None,
+            export_space: None,
+            export_labellings: false,
+        }
+    }
 }
 
 /// Verification strategy.
@@ -1,4 +1,6 @@
 #![doc = include_str!("../README.md")]
+// the README changelog continues the list items as rendered by GitHub
+#![allow(clippy::doc_lazy_continuation)]
 
 mod args;
 mod traits;
@@ -11,8 +13,9 @@ use log::log_enabled;
 use log::trace;
 use log::warn;
 use machine_check_common::check::KnownConclusion;
-use machine_check_common::property::Property;
-use machine_check_exec::Strategy;
+use machine_check_common::check::{Culprit, ParamClass, Replay, Trace};
+use machine_check_common::property::{NamedProperty, Property, SystemNames};
+use machine_check_exec::{ResourceLimits, SpaceFormat, Strategy};
 
 use args::ProgramArgs;
 pub use args::{ExecArgs, ExecStrategy};
@@ -115,6 +118,9 @@ pub fn parse_args<A: clap::Args>(args: impl Iterator<Item = String>) -> (ExecArg
 pub use ::machine_check_common::ExecError;
 pub use ::machine_check_common::ExecResult;
 pub use ::machine_check_common::ExecStats;
+pub use ::machine_check_common::PropertyResult;
+pub use ::machine_check_common::StepBound;
+pub use ::machine_check_common::SubpropertyResult;
 
 /// Runs **machine-check** with the given constructed system and parsed arguments.
 ///
@@ -157,38 +163,212 @@ pub fn execute<M: FullMachine>(system: M, exec_args: ExecArgs) -> ExecResult {
     let strategy = Strategy {
         naive_inputs: matches!(exec_args.strategy, ExecStrategy::Naive),
         use_decay: matches!(exec_args.strategy, ExecStrategy::Decay),
+        num_threads: exec_args.threads,
+    };
+    let limits = ResourceLimits {
+        timeout: exec_args.timeout.map(std::time::Duration::from_secs),
+        max_refinements: exec_args.max_refinements,
+        max_states: exec_args.max_states,
+    };
+    let checkpoints = verify::Checkpoints {
+        save: exec_args.checkpoint.map(|path| {
+            (
+                path,
+                std::time::Duration::from_secs(exec_args.checkpoint_every),
+            )
+        }),
+        resume: exec_args.resume,
+    };
+
+    // the format of the exported state space is given by the file extension
+    let export = match exec_args.export_space {
+        Some(path) => {
+            let format = match path.extension().and_then(|extension| extension.to_str()) {
+                Some("dot" | "gv") => SpaceFormat::Dot,
+                Some("graphml") => SpaceFormat::GraphMl,
+                _ => {
+                    error!(
+                        "Cannot determine the state space export format from the file extension"
+                    );
+                    return error_result(ExecError::OtherError(String::from(
+                        "Unknown state space export format",
+                    )));
+                }
+            };
+            Some(verify::SpaceExport {
+                path,
+                format,
+                with_labellings: exec_args.export_labellings,
+            })
+        }
+        None => None,
     };
 
-    // determine the property to verify
-    let prop = if let Some(property_str) = exec_args.property {
-        match Property::parse(&property_str) {
-            Ok(prop) => Some(prop),
+    // the referenced fields and panic messages must exist in the system
+    let names = system_names::<M>();
+
+    // determine the properties to verify, there are none when checking for inherent panics
+    let parsed_props: Vec<(String, Result<Property, ExecError>)> = if let Some(ltl_str) =
+        exec_args.ltl
+    {
+        let parsed = Property::parse_ltl(&ltl_str, &exec_args.fairness, Some(&names));
+        vec![(ltl_str, parsed)]
+    } else {
+        exec_args
+            .property
+            .into_iter()
+            .map(|property_str| {
+                let parsed = Property::parse_fair(&property_str, &exec_args.fairness, Some(&names));
+                (property_str, parsed)
+            })
+            .collect()
+    };
+    // the properties are simplified before model checking unless disabled
+    let prepare = |property: Property| {
+        if exec_args.no_simplify {
+            property
+        } else {
+            property.simplify()
+        }
+    };
+    let mut props = Vec::new();
+    for (property_str, parsed_prop) in parsed_props {
+        match parsed_prop {
+            Ok(property) => props.push(NamedProperty {
+                name: property_str,
+                property: prepare(property),
+            }),
             Err(err) => {
                 error!("Cannot construct the property: {}", err);
-                return ExecResult {
-                    result: Err(err),
-                    stats: ExecStats::default(),
-                };
+                return error_result(err);
+            }
+        }
+    }
+
+    // determine the formula whose number of steps until reached is queried,
+    // the maximum is queried by the universal reachability property
+    let step_query = match (exec_args.max_steps_until, exec_args.min_steps_until) {
+        (Some(formula), _) => Some((true, formula)),
+        (None, Some(formula)) => Some((false, formula)),
+        (None, None) => None,
+    };
+    // the reachability property is verified first, the formula is used to compute the steps
+    let step_query = match step_query {
+        Some((is_universal, formula)) => {
+            match Property::parse_reachability(&formula, is_universal, Some(&names)).and_then(
+                |reachability| {
+                    Property::parse_fair(&formula, &[], Some(&names))
+                        .map(|formula| (prepare(reachability), prepare(formula)))
+                },
+            ) {
+                Ok((reachability, formula)) => Some((is_universal, reachability, formula)),
+                Err(err) => {
+                    error!("Cannot construct the property: {}", err);
+                    return error_result(err);
+                }
+            }
+        }
+        None => None,
+    };
+    let is_max_query = matches!(step_query, Some((true, ..)));
+    let step_bound_name = if is_max_query {
+        "Maximum steps until reached"
+    } else {
+        "Minimum steps until reached"
+    };
+
+    // determine the named properties to verify if given by a file
+    let file_props = if let Some(property_file) = &exec_args.property_file {
+        let file_contents = match std::fs::read_to_string(property_file) {
+            Ok(file_contents) => file_contents,
+            Err(err) => {
+                error!("Cannot read the property file: {}", err);
+                return error_result(ExecError::OtherError(String::from(
+                    "Could not read property file",
+                )));
+            }
+        };
+        match Property::parse_file(&file_contents, &exec_args.fairness, Some(&names)) {
+            Ok(named_props) => Some(
+                named_props
+                    .into_iter()
+                    .map(|named| NamedProperty {
+                        name: named.name,
+                        property: prepare(named.property),
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            Err(err) => {
+                error!("Cannot construct the properties: {}", err);
+                return error_result(err);
             }
         }
     } else {
-        // check for inherent panics
         None
     };
-    if prop.is_none() && !exec_args.gui && !exec_args.inherent {
+
+    if props.is_empty()
+        && file_props.is_none()
+        && step_query.is_none()
+        && !exec_args.gui
+        && !exec_args.inherent
+    {
         panic!("Expected either a property or inherent verification");
     }
 
     let result = if exec_args.gui {
         // start the GUI instead of verifying
-        ExecResult {
-            result: Err(start_gui(system, prop, strategy)),
-            stats: ExecStats::default(),
-        }
+        let props = props.into_iter().map(|named| named.property).collect();
+        error_result(start_gui(system, props, strategy))
     } else {
         info!("Starting verification.");
 
-        let result = verify::verify(system, prop, exec_args.assume_inherent, strategy);
+        // multiple properties are verified in sequence, reusing the state space
+        let result = if let Some((is_universal, reachability, formula)) = step_query {
+            verify::verify_steps(
+                system,
+                is_universal,
+                reachability,
+                formula,
+                exec_args.assume_inherent,
+                strategy,
+                limits,
+            )
+        } else if let Some(file_props) = file_props {
+            verify::verify_multiple(
+                system,
+                file_props,
+                exec_args.assume_inherent,
+                exec_args.subproperty_results,
+                strategy,
+                limits,
+                checkpoints,
+                export,
+            )
+        } else if props.len() > 1 {
+            verify::verify_multiple(
+                system,
+                props,
+                exec_args.assume_inherent,
+                exec_args.subproperty_results,
+                strategy,
+                limits,
+                checkpoints,
+                export,
+            )
+        } else {
+            let prop = props.pop().map(|named| named.property);
+            verify::verify(
+                system,
+                prop,
+                exec_args.assume_inherent,
+                exec_args.subproperty_results,
+                strategy,
+                limits,
+                checkpoints,
+                export,
+            )
+        };
 
         if log_enabled!(log::Level::Trace) {
             trace!("Verification result: {:?}", result);
@@ -214,14 +394,9 @@ pub fn execute<M: FullMachine>(system: M, exec_args: ExecArgs) -> ExecResult {
             && log_enabled!(log::Level::Info)
         {
             // print the verification result nicely
-            let result_title = match &result.result {
-                Ok(KnownConclusion::False) => "Result: DOES NOT HOLD",
-                Ok(KnownConclusion::True) => "Result: HOLDS",
-                Ok(KnownConclusion::Dependent) => "Result: DEPENDS ON PARAMETERS",
-                Err(err) => &format!("Result: ERROR ({})", err),
-            };
+            let result_title = &format!("Result: {}", conclusion_str(&result.result));
 
-            let mut stats_cells: Vec<(&str, String)> = [
+            let mut stats_cells: Vec<(String, String)> = [
                 ("Refinements", result.stats.num_refinements),
                 ("Generated states", result.stats.num_generated_states),
                 ("Final states", result.stats.num_final_states),
@@ -232,16 +407,40 @@ pub fn execute<M: FullMachine>(system: M, exec_args: ExecArgs) -> ExecResult {
                 ("Final transitions", result.stats.num_final_transitions),
             ]
             .into_iter()
-            .map(|(name, value)| (name, value.to_string()))
+            .map(|(name, value)| (String::from(name), value.to_string()))
             .collect();
 
             if let Some(inherent_panic_message) = &result.stats.inherent_panic_message {
                 stats_cells.push((
-                    "Inherent panic message",
+                    String::from("Inherent panic message"),
                     format!("{:?}", inherent_panic_message),
                 ));
             }
 
+            if let Some(step_bound) = &result.step_bound {
+                stats_cells.push((String::from(step_bound_name), step_bound.to_string()));
+            }
+
+            // show the result for each class of parameters if the result depends on them
+            push_param_class_cells(&mut stats_cells, None, &result.param_classes);
+
+            // show the result for each of the multiple properties
+            for property_result in result.property_results.iter().flatten() {
+                stats_cells.push((
+                    format!("Property '{}'", property_result.name),
+                    conclusion_str(&property_result.result),
+                ));
+                stats_cells.push((
+                    format!("Property '{}' refinements", property_result.name),
+                    property_result.stats.num_refinements.to_string(),
+                ));
+                push_param_class_cells(
+                    &mut stats_cells,
+                    Some(&property_result.name),
+                    &property_result.param_classes,
+                );
+            }
+
             let inner_table_width = stats_cells
                 .iter()
                 .map(|(name, value)| format!("{}: {}", name, value).len())
@@ -267,26 +466,217 @@ pub fn execute<M: FullMachine>(system: M, exec_args: ExecArgs) -> ExecResult {
                 )
             }
             eprintln!("{}", table_bar);
+
+            // show where the verification stopped if a resource limit was reached
+            if let Err(ExecError::ResourceLimit(_, culprit)) = &result.result {
+                print_culprit(culprit);
+            }
+
+            if let Some(subproperty_results) = &result.subproperty_results {
+                print_subproperty_results(subproperty_results, None);
+            }
+            for property_result in result.property_results.iter().flatten() {
+                if let Some(subproperty_results) = &property_result.subproperty_results {
+                    print_subproperty_results(subproperty_results, Some(&property_result.name));
+                }
+            }
+
+            if let Some(trace) = &result.trace {
+                print_trace(
+                    trace,
+                    matches!(result.result, Ok(KnownConclusion::True)),
+                    None,
+                );
+            }
+            for property_result in result.property_results.iter().flatten() {
+                if let Some(trace) = &property_result.trace {
+                    print_trace(
+                        trace,
+                        matches!(property_result.result, Ok(KnownConclusion::True)),
+                        Some(&property_result.name),
+                    );
+                }
+            }
         }
     }
     result
 }
 
+fn error_result(err: ExecError) -> ExecResult {
+    ExecResult {
+        result: Err(err),
+        stats: ExecStats::default(),
+        trace: None,
+        param_classes: None,
+        property_results: None,
+        step_bound: None,
+        subproperty_results: None,
+    }
+}
+
+fn conclusion_str(result: &Result<KnownConclusion, ExecError>) -> String {
+    match result {
+        Ok(KnownConclusion::False) => String::from("DOES NOT HOLD"),
+        Ok(KnownConclusion::True) => String::from("HOLDS"),
+        Ok(KnownConclusion::Dependent) => String::from("DEPENDS ON PARAMETERS"),
+        Err(err) => format!("ERROR ({})", err),
+    }
+}
+
+/// Adds a table cell with the result for each class of parameters.
+///
+/// The cells are prefixed by the property name if it is given.
+fn push_param_class_cells(
+    stats_cells: &mut Vec<(String, String)>,
+    property_name: Option<&str>,
+    param_classes: &Option<Vec<ParamClass>>,
+) {
+    for param_class in param_classes.iter().flatten() {
+        let mut param_description = param_class
+            .param
+            .iter()
+            .map(|(name, value)| format!("param.{} = {}", name, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if let Some(property_name) = property_name {
+            param_description = format!("Property '{}' with {}", property_name, param_description);
+        }
+        let class_result = if param_class.holds {
+            "HOLDS"
+        } else {
+            "DOES NOT HOLD"
+        };
+        stats_cells.push((param_description, String::from(class_result)));
+    }
+}
+
+fn print_culprit(culprit: &Culprit) {
+    let path = culprit
+        .path
+        .iter()
+        .map(|state_id| state_id.to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ");
+    eprintln!(
+        "Culprit to be refined next: {} unknown at the end of the path {}",
+        culprit.atomic_property, path
+    );
+}
+
+fn print_subproperty_results(
+    subproperty_results: &[SubpropertyResult],
+    property_name: Option<&str>,
+) {
+    fn print_indented(subproperty_result: &SubpropertyResult, indent: usize) {
+        let valuation = match &subproperty_result.result {
+            Ok(valuation) => valuation.to_string(),
+            Err(err) => format!("error ({})", err),
+        };
+        eprintln!(
+            "{}{}: {}",
+            "  ".repeat(indent),
+            subproperty_result.name,
+            valuation
+        );
+        for child in &subproperty_result.children {
+            print_indented(child, indent + 1);
+        }
+    }
+
+    if let Some(property_name) = property_name {
+        eprintln!(
+            "Subproperties of property '{}' in the initial states:",
+            property_name
+        );
+    } else {
+        eprintln!("Subproperties in the initial states:");
+    }
+    for subproperty_result in subproperty_results {
+        print_indented(subproperty_result, 1);
+    }
+}
+
+fn print_trace(trace: &Trace, is_witness: bool, property_name: Option<&str>) {
+    let trace_kind = if is_witness {
+        "Witness trace"
+    } else {
+        "Counterexample trace"
+    };
+    if let Some(property_name) = property_name {
+        eprintln!("{} of property '{}':", trace_kind, property_name);
+    } else {
+        eprintln!("{}:", trace_kind);
+    }
+    for (step_index, step) in trace.steps.iter().enumerate() {
+        eprintln!("  Step {} (state {}):", step_index, step.state_id);
+        for (kind, fields) in [
+            ("input", &step.input),
+            ("param", &step.param),
+            ("state", &step.state),
+        ] {
+            for (name, value) in fields {
+                eprintln!("    {}.{} = {}", kind, name, value);
+            }
+        }
+        eprintln!("    panic = {}", step.panic);
+    }
+    if let Some(loop_start) = trace.loop_start {
+        eprintln!("  Loops back to step {}.", loop_start);
+    }
+
+    if let Some(replay) = &trace.replay {
+        print_replay(replay);
+    }
+}
+
+fn print_replay(replay: &Replay) {
+    eprintln!("Concrete replay: {}", replay.verdict);
+    for (name, value) in &replay.param {
+        eprintln!("  param.{} = {}", name, value);
+    }
+    for (step_index, step) in replay.steps.iter().enumerate() {
+        eprintln!("  Step {}:", step_index);
+        for (kind, fields) in [("input", &step.input), ("state", &step.state)] {
+            for (name, value) in fields {
+                eprintln!("    {}.{} = {}", kind, name, value);
+            }
+        }
+        if let Some(panic_message) = &step.panic_message {
+            eprintln!("    panic: {:?}", panic_message);
+        }
+    }
+}
+
+/// Returns the names of the fields and the panic messages of the system
+/// that can be referenced by properties.
+fn system_names<M: FullMachine>() -> SystemNames {
+    use ::mck::abstr::{Machine, Manipulatable};
+    fn names<T: Manipulatable>() -> Vec<String> {
+        T::field_names().into_iter().map(String::from).collect()
+    }
+    SystemNames {
+        state: names::<<M::Abstr as Machine<M>>::State>(),
+        input: names::<<M::Abstr as Machine<M>>::Input>(),
+        param: names::<<M::Abstr as Machine<M>>::Param>(),
+        panic_messages: M::panic_messages().into_iter().map(String::from).collect(),
+    }
+}
+
 fn start_gui<M: FullMachine>(
     system: M,
-    property: Option<Property>,
+    properties: Vec<Property>,
     strategy: Strategy,
 ) -> ExecError {
     // the GUI will, at best, return no result
     #[cfg(feature = "gui")]
-    match machine_check_gui::run(system, property, strategy) {
+    match machine_check_gui::run(system, properties, strategy) {
         Ok(()) => ExecError::NoResult,
         Err(err) => err,
     }
     #[cfg(not(feature = "gui"))]
     {
         // make sure there is no warning about unused variables
-        let _ = (system, property, strategy);
+        let _ = (system, properties, strategy);
         ExecError::GuiError(String::from("The GUI feature was not enabled during build"))
     }
 }
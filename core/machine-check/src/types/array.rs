@@ -69,4 +69,10 @@ impl<const I: u32, const W: u32> IntoMck for BitvectorArray<I, W> {
     fn into_mck(self) -> Self::Type {
         Self::Type::from_inner(self.inner.map(|v| v.into_mck()))
     }
+
+    fn from_mck(value: Self::Type) -> Self {
+        Self {
+            inner: value.into_inner().map(|v| Bitvector::from_mck(*v)),
+        }
+    }
 }
@@ -142,4 +142,8 @@ impl<const W: u32> IntoMck for Bitvector<W> {
     fn into_mck(self) -> Self::Type {
         self.0
     }
+
+    fn from_mck(value: Self::Type) -> Self {
+        Self(value)
+    }
 }
@@ -226,4 +226,8 @@ impl<const W: u32> IntoMck for Signed<W> {
     fn into_mck(self) -> Self::Type {
         self.0
     }
+
+    fn from_mck(value: Self::Type) -> Self {
+        Self(value)
+    }
 }
@@ -1,7 +1,33 @@
-use crate::{ExecError, ExecResult, FullMachine};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{ExecError, ExecResult, FullMachine, PropertyResult, SubpropertyResult};
 use log::{info, warn};
-use machine_check_common::{check::KnownConclusion, property::Property, ExecStats};
-use machine_check_exec::{Framework, Strategy};
+use machine_check_common::{
+    check::{KnownConclusion, ParamClass, ReplayVerdict, Trace},
+    property::{NamedProperty, Property, Subproperty},
+    ExecStats, StepBound,
+};
+use machine_check_exec::{Checkpoint, Framework, ResourceLimits, SpaceFormat, Strategy};
+
+/// Saving and resuming of the verification checkpoints.
+#[derive(Debug, Clone, Default)]
+pub struct Checkpoints {
+    /// The file to periodically save the checkpoints to, with the interval between them.
+    pub save: Option<(PathBuf, Duration)>,
+    /// The file with the checkpoint to resume the verification from.
+    pub resume: Option<PathBuf>,
+}
+
+/// Export of the final state space.
+#[derive(Debug, Clone)]
+pub struct SpaceExport {
+    /// The file to export the state space to.
+    pub path: PathBuf,
+    pub format: SpaceFormat,
+    /// Whether the states should be labelled by the valuations of the displayed subproperties.
+    pub with_labellings: bool,
+}
 
 /// Verifies the given system with given arguments.
 ///
@@ -9,24 +35,44 @@ use machine_check_exec::{Framework, Strategy};
 ///
 /// If verifying a standard property and the inherent property is not assumed,
 /// it is verified first. If it does not hold, it is an execution error.
+///
+/// The results of the subproperties in the initial states are also returned if requested.
+/// The final state space is exported if requested.
+#[allow(clippy::too_many_arguments)]
 pub fn verify<M: FullMachine>(
     system: M,
     prop: Option<Property>,
     assume_inherent: bool,
+    with_subproperty_results: bool,
     strategy: Strategy,
+    limits: ResourceLimits,
+    checkpoints: Checkpoints,
+    export: Option<SpaceExport>,
 ) -> ExecResult {
-    let abstract_system = <M::Abstr as mck::abstr::Abstr<M>>::from_concrete(system);
-
     // Short-circuit error on assumption of the inherent property that we are trying to verify.
     if prop.is_none() && assume_inherent {
         return ExecResult {
             result: Err(ExecError::VerifiedInherentAssumed),
             stats: ExecStats::default(),
+            trace: None,
+            param_classes: None,
+            property_results: None,
+            step_bound: None,
+            subproperty_results: None,
         };
     }
 
-    // Construct the framework.
-    let mut framework = Framework::<M>::new(abstract_system, strategy);
+    // Construct the framework, resuming from the checkpoint if requested.
+    let properties: Vec<Property> = (!assume_inherent)
+        .then(Property::inherent)
+        .into_iter()
+        .chain(prop.clone())
+        .collect();
+    let mut framework =
+        match construct_framework(system, strategy, limits, checkpoints, &properties) {
+            Ok(framework) => framework,
+            Err(err) => return crate::error_result(err),
+        };
 
     // Verify the inherent property first if not assumed.
     let inherent_result = if assume_inherent {
@@ -38,59 +84,463 @@ pub fn verify<M: FullMachine>(
             info!("Verifying the inherent property.");
         }
         let inherent_property = Property::inherent();
-        Some(framework.verify(&inherent_property))
+        let inherent_result = framework.verify(&inherent_property);
+        let inherent_trace = find_trace(&mut framework, &inherent_property, &inherent_result);
+        Some((inherent_result, inherent_trace))
     };
 
     let Some(property) = prop else {
         // Inherent property verification only.
         // Print info and return the inherent property verification result.
         // The property should be verified as the short-circuit was done previously.
+        let (result, trace) = inherent_result.expect("Inherent property should not be assumed");
+        let param_classes = decide_param_classes(&mut framework, &Property::inherent(), &result);
+        let subproperty_results = with_subproperty_results
+            .then(|| subproperty_results(&mut framework, &Property::inherent().root_subproperty()));
+        export_space(&mut framework, export, &properties);
         return ExecResult {
-            result: inherent_result.expect("Inherent property should not be assumed"),
+            result,
             stats: framework.info(),
+            trace,
+            param_classes,
+            property_results: None,
+            step_bound: None,
+            subproperty_results,
         };
     };
 
     // Standard property verification.
-
-    if let Some(inherent_result) = inherent_result {
-        match inherent_result {
-            Ok(KnownConclusion::True) => {
-                // Inherent holds, we can continue.
-                info!("The inherent property holds, proceeding to the given property.");
-            }
-            Ok(KnownConclusion::False) => {
-                return ExecResult {
-                    result: Err(ExecError::InherentPanic),
-                    stats: framework.info(),
-                };
-            }
-            Ok(KnownConclusion::Dependent) => {
-                return ExecResult {
-                    result: Err(ExecError::InherentPanicDependent),
-                    stats: framework.info(),
-                };
-            }
-            Err(_) => {
-                // return the errors
-                return ExecResult {
-                    result: inherent_result,
-                    stats: framework.info(),
-                };
-            }
-        }
-    } else {
-        warn!("Assuming that the inherent property holds. If it does not, the verification result will be unusable.");
+    if let Some(result) = require_inherent(&mut framework, inherent_result) {
+        export_space(&mut framework, export, &properties);
+        return result;
     }
 
     info!("Verifying the given property.");
 
     // verify the property, assuming no panic can occur
     let result = framework.verify(&property);
+    let trace = find_trace(&mut framework, &property, &result);
+    let param_classes = decide_param_classes(&mut framework, &property, &result);
+    let subproperty_results = with_subproperty_results
+        .then(|| subproperty_results(&mut framework, &property.root_subproperty()));
+    export_space(&mut framework, export, &properties);
 
     // also return framework stats
     ExecResult {
         result,
         stats: framework.info(),
+        trace,
+        param_classes,
+        property_results: None,
+        step_bound: None,
+        subproperty_results,
+    }
+}
+
+/// Verifies each of the named properties in sequence with given arguments.
+///
+/// The same framework is used for all of the properties, so the refined
+/// state space is reused between them.
+///
+/// The inherent property is verified first as for a standard property.
+/// The verification result holds only if each of the named properties holds.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_multiple<M: FullMachine>(
+    system: M,
+    properties: Vec<NamedProperty>,
+    assume_inherent: bool,
+    with_subproperty_results: bool,
+    strategy: Strategy,
+    limits: ResourceLimits,
+    checkpoints: Checkpoints,
+    export: Option<SpaceExport>,
+) -> ExecResult {
+    let verified_properties: Vec<Property> = (!assume_inherent)
+        .then(Property::inherent)
+        .into_iter()
+        .chain(properties.iter().map(|named| named.property.clone()))
+        .collect();
+    let mut framework =
+        match construct_framework(system, strategy, limits, checkpoints, &verified_properties) {
+            Ok(framework) => framework,
+            Err(err) => return crate::error_result(err),
+        };
+
+    let inherent_result = if assume_inherent {
+        None
+    } else {
+        info!("Verifying the inherent property first.");
+        let inherent_property = Property::inherent();
+        let inherent_result = framework.verify(&inherent_property);
+        let inherent_trace = find_trace(&mut framework, &inherent_property, &inherent_result);
+        Some((inherent_result, inherent_trace))
+    };
+    if let Some(result) = require_inherent(&mut framework, inherent_result) {
+        export_space(&mut framework, export, &verified_properties);
+        return result;
+    }
+
+    // the state space is kept between the properties
+    let property_results: Vec<PropertyResult> = properties
+        .into_iter()
+        .map(|named| {
+            info!("Verifying the property '{}'.", named.name);
+            let earlier_stats = framework.info();
+            let result = framework.verify(&named.property);
+            let trace = find_trace(&mut framework, &named.property, &result);
+            let param_classes = decide_param_classes(&mut framework, &named.property, &result);
+            let subproperty_results = with_subproperty_results
+                .then(|| subproperty_results(&mut framework, &named.property.root_subproperty()));
+            PropertyResult {
+                name: named.name,
+                result,
+                stats: stats_since(&earlier_stats, framework.info()),
+                trace,
+                param_classes,
+                subproperty_results,
+            }
+        })
+        .collect();
+    export_space(&mut framework, export, &verified_properties);
+
+    ExecResult {
+        result: combine_results(&property_results),
+        stats: framework.info(),
+        trace: None,
+        param_classes: None,
+        property_results: Some(property_results),
+        step_bound: None,
+        subproperty_results: None,
+    }
+}
+
+/// Computes the number of steps until a formula is reached with given arguments.
+///
+/// The reachability property of the formula is `AF![formula]` if universal or `EF![formula]`
+/// otherwise. If universal, the maximum number of steps is computed, i.e. the smallest bound
+/// within which the formula is reached on all paths. Otherwise, the minimum number of steps
+/// is computed, i.e. the smallest bound within which the formula is reached on some path.
+///
+/// The reachability property is verified first and the number of steps is infinite
+/// if it does not hold. The inherent property is verified before as for a standard property.
+pub fn verify_steps<M: FullMachine>(
+    system: M,
+    is_universal: bool,
+    reachability: Property,
+    formula: Property,
+    assume_inherent: bool,
+    strategy: Strategy,
+    limits: ResourceLimits,
+) -> ExecResult {
+    let abstract_system = <M::Abstr as mck::abstr::Abstr<M>>::from_concrete(system);
+    let mut framework = Framework::<M>::new(abstract_system, strategy);
+    framework.set_limits(limits);
+
+    let inherent_result = if assume_inherent {
+        None
+    } else {
+        info!("Verifying the inherent property first.");
+        let inherent_property = Property::inherent();
+        let inherent_result = framework.verify(&inherent_property);
+        let inherent_trace = find_trace(&mut framework, &inherent_property, &inherent_result);
+        Some((inherent_result, inherent_trace))
+    };
+    if let Some(result) = require_inherent(&mut framework, inherent_result) {
+        return result;
+    }
+
+    info!("Verifying that the formula is reached.");
+    let result = framework.verify(&reachability);
+    let (result, trace, param_classes, step_bound) = match result {
+        Ok(KnownConclusion::True) => {
+            info!("Computing the number of steps until the formula is reached.");
+            match framework.steps_until(&formula, is_universal) {
+                Ok((steps, trace)) => (
+                    result,
+                    Some(replay_trace(&framework, trace)),
+                    None,
+                    Some(StepBound::Finite(steps)),
+                ),
+                Err(err) => (Err(err), None, None, None),
+            }
+        }
+        Ok(KnownConclusion::False) => {
+            // the counterexample shows the path on which the formula is not reached
+            let trace = find_trace(&mut framework, &reachability, &result);
+            (result, trace, None, Some(StepBound::Infinite))
+        }
+        Ok(KnownConclusion::Dependent) => {
+            let param_classes = decide_param_classes(&mut framework, &reachability, &result);
+            (result, None, param_classes, None)
+        }
+        Err(_) => (result, None, None, None),
+    };
+
+    ExecResult {
+        result,
+        stats: framework.info(),
+        trace,
+        param_classes,
+        property_results: None,
+        step_bound,
+        subproperty_results: None,
+    }
+}
+
+/// Constructs the framework for verifying the given properties.
+///
+/// If requested, the framework is resumed from the checkpoint and saves the checkpoints
+/// periodically. A checkpoint that cannot be saved is only warned about.
+fn construct_framework<M: FullMachine>(
+    system: M,
+    strategy: Strategy,
+    limits: ResourceLimits,
+    checkpoints: Checkpoints,
+    properties: &[Property],
+) -> Result<Framework<M>, ExecError> {
+    let abstract_system = <M::Abstr as mck::abstr::Abstr<M>>::from_concrete(system);
+    let mut framework = Framework::<M>::new(abstract_system, strategy);
+    framework.set_limits(limits);
+
+    if let Some(resume_path) = checkpoints.resume {
+        let checkpoint = std::fs::read_to_string(&resume_path)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| {
+                serde_json::from_str::<Checkpoint>(&contents).map_err(|err| err.to_string())
+            })
+            .map_err(ExecError::CheckpointNotAccessible)?;
+        framework.load_checkpoint(&checkpoint, properties)?;
+        info!(
+            "Resumed from the checkpoint with {} refinements.",
+            checkpoint.stats().num_refinements
+        );
+    }
+
+    if let Some((save_path, interval)) = checkpoints.save {
+        framework.set_checkpointing(
+            interval,
+            Box::new(move |checkpoint| {
+                if let Err(err) = save_checkpoint(&save_path, &checkpoint) {
+                    warn!("Cannot save the checkpoint: {}", err);
+                } else {
+                    info!(
+                        "Saved the checkpoint with {} refinements.",
+                        checkpoint.stats().num_refinements
+                    );
+                }
+            }),
+        );
+    }
+    Ok(framework)
+}
+
+/// Saves the checkpoint to the file.
+///
+/// The checkpoint is written to a temporary file first and then renamed,
+/// so that the previous checkpoint is kept if the saving is interrupted.
+fn save_checkpoint(path: &Path, checkpoint: &Checkpoint) -> Result<(), ExecError> {
+    let contents = serde_json::to_string(checkpoint)
+        .map_err(|err| ExecError::CheckpointNotAccessible(err.to_string()))?;
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(".tmp");
+    std::fs::write(&temporary_path, contents)
+        .and_then(|()| std::fs::rename(&temporary_path, path))
+        .map_err(|err| ExecError::CheckpointNotAccessible(err.to_string()))
+}
+
+/// Returns the execution result to end with if the inherent property was verified
+/// and does not hold.
+fn require_inherent<M: FullMachine>(
+    framework: &mut Framework<M>,
+    inherent_result: Option<(Result<KnownConclusion, ExecError>, Option<Trace>)>,
+) -> Option<ExecResult> {
+    let Some((inherent_result, inherent_trace)) = inherent_result else {
+        warn!("Assuming that the inherent property holds. If it does not, the verification result will be unusable.");
+        return None;
+    };
+    let (result, trace) = match inherent_result {
+        Ok(KnownConclusion::True) => {
+            // Inherent holds, we can continue.
+            info!("The inherent property holds, proceeding to the given property.");
+            return None;
+        }
+        Ok(KnownConclusion::False) => (Err(ExecError::InherentPanic), inherent_trace),
+        Ok(KnownConclusion::Dependent) => (Err(ExecError::InherentPanicDependent), None),
+        // return the errors
+        Err(_) => (inherent_result, None),
+    };
+    Some(ExecResult {
+        result,
+        stats: framework.info(),
+        trace,
+        param_classes: None,
+        property_results: None,
+        step_bound: None,
+        subproperty_results: None,
+    })
+}
+
+/// Returns the statistics of a single property verification.
+///
+/// The totals are counted since the earlier statistics, the current numbers are kept.
+fn stats_since(earlier: &ExecStats, current: ExecStats) -> ExecStats {
+    ExecStats {
+        num_refinements: current.num_refinements - earlier.num_refinements,
+        num_generated_states: current.num_generated_states - earlier.num_generated_states,
+        num_generated_transitions: current.num_generated_transitions
+            - earlier.num_generated_transitions,
+        ..current
+    }
+}
+
+/// Combines the results of the named properties to the result of them all holding.
+///
+/// An error takes precedence, followed by a property not holding
+/// and a property depending on the parameters.
+fn combine_results(property_results: &[PropertyResult]) -> Result<KnownConclusion, ExecError> {
+    let mut combined = KnownConclusion::True;
+    for property_result in property_results {
+        match &property_result.result {
+            Ok(KnownConclusion::True) => {}
+            Ok(KnownConclusion::False) => combined = KnownConclusion::False,
+            Ok(KnownConclusion::Dependent) => {
+                if combined == KnownConclusion::True {
+                    combined = KnownConclusion::Dependent;
+                }
+            }
+            Err(err) => return Err(err.clone()),
+        }
+    }
+    Ok(combined)
+}
+
+/// Finds the counterexample or witness trace for a decided verification result
+/// and replays it on the concrete system.
+///
+/// Failing to find the trace does not invalidate the result, so it is only warned about.
+fn find_trace<M: FullMachine>(
+    framework: &mut Framework<M>,
+    property: &Property,
+    result: &Result<KnownConclusion, ExecError>,
+) -> Option<Trace> {
+    let holds = match result {
+        Ok(KnownConclusion::True) => true,
+        Ok(KnownConclusion::False) => false,
+        Ok(KnownConclusion::Dependent) | Err(_) => return None,
+    };
+    match framework.find_trace(property, holds) {
+        Ok(Some(trace)) => Some(replay_trace(framework, trace)),
+        Ok(None) => None,
+        Err(err) => {
+            warn!("Could not find the verification trace: {}", err);
+            None
+        }
+    }
+}
+
+/// Confirms the trace by replaying it on the concrete system.
+fn replay_trace<M: FullMachine>(framework: &Framework<M>, mut trace: Trace) -> Trace {
+    let replay = framework.replay_trace(&trace);
+    if replay.verdict != ReplayVerdict::Confirmed {
+        warn!(
+            "The trace replay on the concrete system is {}.",
+            replay.verdict
+        );
+    }
+    trace.replay = Some(replay);
+    trace
+}
+
+/// Returns the results of the displayed subproperties in the initial states.
+///
+/// The subproperties that are not displayed are skipped, returning the results
+/// of their displayed subproperties instead.
+fn subproperty_results<M: FullMachine>(
+    framework: &mut Framework<M>,
+    subproperty: &Subproperty,
+) -> Vec<SubpropertyResult> {
+    // the subproperties within an invisible one are hidden as well
+    if !subproperty.is_visible() {
+        return Vec::new();
+    }
+    let children = subproperty
+        .displayed_children()
+        .iter()
+        .flat_map(|child| subproperty_results(framework, child))
+        .collect();
+    let Some(name) = subproperty.display_str() else {
+        return children;
+    };
+    let result = framework.check_subproperty_in_initial(subproperty);
+    vec![SubpropertyResult {
+        name: String::from(name),
+        result,
+        children,
+    }]
+}
+
+/// Exports the compacted state space if requested.
+///
+/// The states are labelled by the displayed subproperties of the given properties if requested.
+/// Failing to export the state space does not invalidate the results, so it is only warned about.
+fn export_space<M: FullMachine>(
+    framework: &mut Framework<M>,
+    export: Option<SpaceExport>,
+    properties: &[Property],
+) {
+    let Some(export) = export else {
+        return;
+    };
+    let mut subproperties = Vec::new();
+    if export.with_labellings {
+        for property in properties {
+            displayed_subproperties(&property.root_subproperty(), &mut subproperties);
+        }
+    }
+    framework.make_compact();
+    let exported = framework
+        .export_space(export.format, &subproperties)
+        .and_then(|exported| {
+            std::fs::write(&export.path, exported)
+                .map_err(|err| ExecError::OtherError(err.to_string()))
+        });
+    match exported {
+        Ok(()) => info!("Exported the state space to {}.", export.path.display()),
+        Err(err) => warn!("Could not export the state space: {}", err),
+    }
+}
+
+/// Collects the displayed subproperties in the tree of the subproperty, parents first.
+fn displayed_subproperties(subproperty: &Subproperty, result: &mut Vec<Subproperty>) {
+    // the subproperties within an invisible one are hidden as well
+    if !subproperty.is_visible() {
+        return;
+    }
+    if subproperty.display_str().is_some() {
+        result.push(subproperty.clone());
+    }
+    for child in subproperty.displayed_children() {
+        displayed_subproperties(&child, result);
+    }
+}
+
+/// Decides the classes of parameter values for a result depending on the parameters.
+///
+/// Failing to decide them does not invalidate the result, so it is only warned about.
+fn decide_param_classes<M: FullMachine>(
+    framework: &mut Framework<M>,
+    property: &Property,
+    result: &Result<KnownConclusion, ExecError>,
+) -> Option<Vec<ParamClass>> {
+    if !matches!(result, Ok(KnownConclusion::Dependent)) {
+        return None;
+    }
+    info!("Deciding the property for the parameter classes.");
+    match framework.decide_param_classes(property) {
+        Ok(param_classes) => Some(param_classes),
+        Err(err) => {
+            warn!("Could not decide the parameter classes: {}", err);
+            None
+        }
     }
 }
@@ -0,0 +1,291 @@
+//! Tests of saving and resuming the verification checkpoints.
+
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use machine_check::{ExecArgs, ExecStrategy};
+use machine_check_common::{check::KnownConclusion, ExecError, ExecResult, ResourceLimit};
+
+use common::{counter, saturating, saturating_wide};
+
+/// The property needs many refinements to be verified.
+const REFINED_PROPERTY: &str = "AG![value <= param.max_value]";
+
+fn checkpoint_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "machine-check-test-{}-{}.json",
+        std::process::id(),
+        name
+    ))
+}
+
+/// Verifies the refined property until the refinement limit, saving the checkpoint to the path.
+fn save_limited_checkpoint(path: &Path) -> ExecResult {
+    let result = common::execute(
+        saturating::System {},
+        ExecArgs {
+            max_refinements: Some(10),
+            checkpoint: Some(path.to_path_buf()),
+            ..common::property_args(REFINED_PROPERTY)
+        },
+    );
+    assert!(matches!(
+        result.result,
+        Err(ExecError::ResourceLimit(ResourceLimit::Refinements, _))
+    ));
+    assert!(path.exists(), "The checkpoint should be saved at the limit");
+    result
+}
+
+/// Returns the sorted state labels and the transitions between them in the exported DOT space.
+///
+/// The node ids are left out, since they depend on the order of generating the states.
+fn space_labels(dot: &str) -> (Vec<String>, Vec<(String, String, String)>) {
+    let mut node_labels = BTreeMap::new();
+    let mut edges = Vec::new();
+    for line in dot.lines().map(str::trim) {
+        let Some((ids, attributes)) = line.split_once(" [") else {
+            continue;
+        };
+        // skip the default node attributes
+        if ids == "node" {
+            continue;
+        }
+        match ids.split_once(" -> ") {
+            Some((head_id, tail_id)) => edges.push((head_id, tail_id, attributes)),
+            None => {
+                node_labels.insert(ids, attributes);
+            }
+        }
+    }
+    let mut edges: Vec<_> = edges
+        .into_iter()
+        .map(|(head_id, tail_id, attributes)| {
+            (
+                node_labels[head_id].to_string(),
+                node_labels[tail_id].to_string(),
+                attributes.to_string(),
+            )
+        })
+        .collect();
+    edges.sort();
+    let mut node_labels: Vec<_> = node_labels.into_values().map(String::from).collect();
+    node_labels.sort();
+    (node_labels, edges)
+}
+
+fn incompatibility(result: ExecResult) -> String {
+    match result.result {
+        Err(ExecError::CheckpointIncompatible(reason)) => reason,
+        other => panic!("The checkpoint should be incompatible, have {:?}", other),
+    }
+}
+
+#[test]
+fn resumed_verification_matches() {
+    let path = checkpoint_path("resumed");
+    let limited = save_limited_checkpoint(&path);
+
+    let resumed_space = checkpoint_path("resumed-space").with_extension("dot");
+    let resumed = common::execute(
+        saturating::System {},
+        ExecArgs {
+            resume: Some(path.clone()),
+            export_space: Some(resumed_space.clone()),
+            ..common::property_args(REFINED_PROPERTY)
+        },
+    );
+    std::fs::remove_file(&path).expect("The checkpoint should be removed");
+
+    let uninterrupted_space = checkpoint_path("uninterrupted-space").with_extension("dot");
+    let uninterrupted = common::execute(
+        saturating::System {},
+        ExecArgs {
+            export_space: Some(uninterrupted_space.clone()),
+            ..common::property_args(REFINED_PROPERTY)
+        },
+    );
+
+    let read_space = |path: &Path| {
+        let space = std::fs::read_to_string(path).expect("The state space should be exported");
+        std::fs::remove_file(path).expect("The exported state space should be removed");
+        space
+    };
+    let resumed_space = read_space(&resumed_space);
+    let uninterrupted_space = read_space(&uninterrupted_space);
+
+    assert_eq!(
+        resumed
+            .result
+            .expect("The resumed verification should succeed"),
+        KnownConclusion::True
+    );
+    assert_eq!(
+        uninterrupted
+            .result
+            .expect("The uninterrupted verification should succeed"),
+        KnownConclusion::True
+    );
+    // the refinements before the checkpoint are counted as well
+    assert!(resumed.stats.num_refinements > limited.stats.num_refinements);
+    assert_eq!(
+        resumed.stats.num_refinements,
+        uninterrupted.stats.num_refinements
+    );
+    // the resumed verification ends in the same state space, but the number of generated states
+    // can differ, as the space is made compact after verifying the inherent property again
+    assert_eq!(
+        resumed.stats.num_final_states,
+        uninterrupted.stats.num_final_states
+    );
+    assert_eq!(
+        resumed.stats.num_final_transitions,
+        uninterrupted.stats.num_final_transitions
+    );
+    assert_eq!(
+        space_labels(&resumed_space),
+        space_labels(&uninterrupted_space)
+    );
+}
+
+#[test]
+fn resumed_stats_match_with_assumed_inherent() {
+    let path = checkpoint_path("assumed");
+    let refined_args = || ExecArgs {
+        assume_inherent: true,
+        ..common::property_args(REFINED_PROPERTY)
+    };
+    common::execute(
+        saturating::System {},
+        ExecArgs {
+            max_refinements: Some(10),
+            checkpoint: Some(path.clone()),
+            ..refined_args()
+        },
+    );
+
+    let resumed = common::execute(
+        saturating::System {},
+        ExecArgs {
+            resume: Some(path.clone()),
+            ..refined_args()
+        },
+    );
+    std::fs::remove_file(&path).expect("The checkpoint should be removed");
+    let uninterrupted = common::execute(saturating::System {}, refined_args());
+
+    // nothing is verified again after resuming, so even the generated states match
+    assert_eq!(
+        resumed
+            .result
+            .expect("The resumed verification should succeed"),
+        KnownConclusion::True
+    );
+    assert_eq!(resumed.stats, uninterrupted.stats);
+}
+
+#[test]
+fn incompatible_system() {
+    let path = checkpoint_path("system");
+    save_limited_checkpoint(&path);
+
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            resume: Some(path.clone()),
+            ..common::property_args("AG![value <= 9]")
+        },
+    );
+    std::fs::remove_file(&path).expect("The checkpoint should be removed");
+
+    assert_eq!(
+        incompatibility(result),
+        "saved for a system with 'max_value: Unsigned<4>' instead of no field among the state fields"
+    );
+}
+
+#[test]
+fn incompatible_field_width() {
+    let path = checkpoint_path("width");
+    save_limited_checkpoint(&path);
+
+    // the fields have the same names, but the state value is wider
+    let result = common::execute(
+        saturating_wide::System {},
+        ExecArgs {
+            resume: Some(path.clone()),
+            ..common::property_args(REFINED_PROPERTY)
+        },
+    );
+    std::fs::remove_file(&path).expect("The checkpoint should be removed");
+
+    assert_eq!(
+        incompatibility(result),
+        "saved for a system with 'value: Unsigned<4>' instead of 'value: Unsigned<8>' among the state fields"
+    );
+}
+
+#[test]
+fn incompatible_strategy() {
+    let path = checkpoint_path("strategy");
+    save_limited_checkpoint(&path);
+
+    let result = common::execute(
+        saturating::System {},
+        ExecArgs {
+            strategy: ExecStrategy::Naive,
+            resume: Some(path.clone()),
+            ..common::property_args(REFINED_PROPERTY)
+        },
+    );
+    std::fs::remove_file(&path).expect("The checkpoint should be removed");
+
+    assert_eq!(incompatibility(result), "saved with a different strategy");
+}
+
+#[test]
+fn incompatible_property() {
+    let path = checkpoint_path("property");
+    save_limited_checkpoint(&path);
+
+    let result = common::execute(
+        saturating::System {},
+        ExecArgs {
+            resume: Some(path.clone()),
+            ..common::property_args("AG![value <= 15]")
+        },
+    );
+    std::fs::remove_file(&path).expect("The checkpoint should be removed");
+
+    assert_eq!(
+        incompatibility(result),
+        "saved for a property which is not verified"
+    );
+}
+
+#[test]
+fn incompatible_version() {
+    let path = checkpoint_path("version");
+    save_limited_checkpoint(&path);
+
+    // pretend the checkpoint was saved in a future format
+    let contents = std::fs::read_to_string(&path).expect("The checkpoint should be readable");
+    assert!(contents.starts_with("{\"version\":2,"));
+    let contents = contents.replacen("{\"version\":2,", "{\"version\":3,", 1);
+    std::fs::write(&path, contents).expect("The checkpoint should be writable");
+
+    let result = common::execute(
+        saturating::System {},
+        ExecArgs {
+            resume: Some(path.clone()),
+            ..common::property_args(REFINED_PROPERTY)
+        },
+    );
+    std::fs::remove_file(&path).expect("The checkpoint should be removed");
+
+    assert_eq!(incompatibility(result), "saved in format version 3, not 2");
+}
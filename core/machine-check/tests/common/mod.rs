@@ -0,0 +1,634 @@
+//! Small systems shared by the verification tests.
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+
+use machine_check::{ExecArgs, ExecResult};
+use machine_check_common::{check::KnownConclusion, SubpropertyResult};
+use mck::concr::FullMachine;
+
+/// Executes **machine-check** silently with the given arguments.
+pub fn execute<M: FullMachine>(system: M, exec_args: ExecArgs) -> ExecResult {
+    machine_check::execute(
+        system,
+        ExecArgs {
+            silent: true,
+            ..exec_args
+        },
+    )
+}
+
+/// Returns the default arguments verifying the property.
+pub fn property_args(property: &str) -> ExecArgs {
+    ExecArgs {
+        property: vec![String::from(property)],
+        ..Default::default()
+    }
+}
+
+/// Verifies the property silently with the default arguments.
+pub fn verify<M: FullMachine>(system: M, property: &str) -> ExecResult {
+    execute(system, property_args(property))
+}
+
+/// Verifies the property silently with the default arguments, returning the conclusion.
+///
+/// Panics if the verification does not succeed.
+pub fn conclusion<M: FullMachine>(system: M, property: &str) -> KnownConclusion {
+    verify(system, property)
+        .result
+        .unwrap_or_else(|err| panic!("The verification of '{}' should succeed: {}", property, err))
+}
+
+/// Returns the concrete value of a described bit-vector field.
+///
+/// Panics if the field is not described or not concrete.
+pub fn concrete_field(fields: &BTreeMap<String, String>, name: &str) -> u64 {
+    let description = fields
+        .get(name)
+        .unwrap_or_else(|| panic!("Field '{}' should be described", name));
+    let bits = description.trim_matches('"');
+    u64::from_str_radix(bits, 2)
+        .unwrap_or_else(|_| panic!("Field '{}' should be concrete, not {}", name, description))
+}
+
+/// Flattens the subproperty results to their depths, names and results in the tree order.
+pub fn flat_subproperty_results(results: &[SubpropertyResult]) -> Vec<(usize, String, String)> {
+    fn flatten(
+        results: &[SubpropertyResult],
+        depth: usize,
+        flat: &mut Vec<(usize, String, String)>,
+    ) {
+        for result in results {
+            let value = match &result.result {
+                Ok(valuation) => valuation.to_string(),
+                Err(err) => format!("error ({})", err),
+            };
+            flat.push((depth, result.name.clone(), value));
+            flatten(&result.children, depth + 1, flat);
+        }
+    }
+    let mut flat = Vec::new();
+    flatten(results, 0, &mut flat);
+    flat
+}
+
+/// A counter that is incremented by the input and wraps from 9 to 0.
+#[machine_check::machine_description]
+pub mod counter {
+    use ::machine_check::{Ext, Unsigned};
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {
+        increment: Unsigned<1>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        value: Unsigned<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, _param: &Param) -> State {
+            State {
+                value: Unsigned::<4>::new(0),
+            }
+        }
+
+        fn next(&self, state: &State, input: &Input, _param: &Param) -> State {
+            let mut value = state.value + Ext::<4>::ext(input.increment);
+            if value == Unsigned::<4>::new(10) {
+                value = Unsigned::<4>::new(0);
+            }
+            State { value }
+        }
+    }
+}
+
+/// A value chosen by the input, saturated at the maximum given by the parameter.
+#[machine_check::machine_description]
+pub mod saturating {
+    use ::machine_check::Unsigned;
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {
+        value: Unsigned<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {
+        max_value: Unsigned<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        value: Unsigned<4>,
+        max_value: Unsigned<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, param: &Param) -> State {
+            State {
+                value: Unsigned::<4>::new(0),
+                max_value: param.max_value,
+            }
+        }
+
+        fn next(&self, state: &State, input: &Input, _param: &Param) -> State {
+            let mut value = input.value;
+            if value >= state.max_value {
+                value = state.max_value;
+            }
+            State {
+                value,
+                max_value: state.max_value,
+            }
+        }
+    }
+}
+
+/// A signed value given by the parameter, kept forever, and a constant plain bit-vector.
+#[machine_check::machine_description]
+pub mod signed_constant {
+    use ::machine_check::{Bitvector, Signed};
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {
+        value: Signed<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        value: Signed<4>,
+        bits: Bitvector<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, param: &Param) -> State {
+            State {
+                value: param.value,
+                bits: Bitvector::<4>::new(12),
+            }
+        }
+
+        fn next(&self, state: &State, _input: &Input, _param: &Param) -> State {
+            State {
+                value: state.value,
+                bits: state.bits,
+            }
+        }
+    }
+}
+
+/// Two complementary bits given by the parameter, both chosen by the input afterwards.
+#[machine_check::machine_description]
+pub mod complementary {
+    use ::machine_check::Unsigned;
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {
+        a: Unsigned<1>,
+        b: Unsigned<1>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {
+        flag: Unsigned<1>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        a: Unsigned<1>,
+        b: Unsigned<1>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, param: &Param) -> State {
+            State {
+                a: param.flag,
+                b: !param.flag,
+            }
+        }
+
+        fn next(&self, _state: &State, input: &Input, _param: &Param) -> State {
+            State {
+                a: input.a,
+                b: input.b,
+            }
+        }
+    }
+}
+
+/// Panics on the reserved opcodes at two sites with the same message,
+/// the first of which is never reached as the mode is kept zero.
+#[machine_check::machine_description]
+pub mod reserved {
+    use ::machine_check::Unsigned;
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+        panic,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {
+        opcode: Unsigned<2>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        mode: Unsigned<1>,
+        opcode: Unsigned<2>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, _param: &Param) -> State {
+            State {
+                mode: Unsigned::<1>::new(0),
+                opcode: Unsigned::<2>::new(0),
+            }
+        }
+
+        fn next(&self, state: &State, input: &Input, _param: &Param) -> State {
+            if state.mode == Unsigned::<1>::new(1) {
+                panic!("Reserved opcode");
+            }
+            if input.opcode == Unsigned::<2>::new(3) {
+                panic!("Reserved opcode");
+            }
+            State {
+                mode: state.mode,
+                opcode: input.opcode,
+            }
+        }
+    }
+}
+
+/// A timer counting the ticks up to 1000, by two ticks per step if the parameter is set.
+#[machine_check::machine_description]
+pub mod timer {
+    use ::machine_check::{Ext, Unsigned};
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {
+        fast: Unsigned<1>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        ticks: Unsigned<10>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, _param: &Param) -> State {
+            State {
+                ticks: Unsigned::<10>::new(0),
+            }
+        }
+
+        fn next(&self, state: &State, _input: &Input, param: &Param) -> State {
+            let mut ticks = state.ticks;
+            if ticks != Unsigned::<10>::new(1000) {
+                ticks = ticks + Unsigned::<10>::new(1) + Ext::<10>::ext(param.fast);
+            }
+            State { ticks }
+        }
+    }
+}
+
+/// A bit chosen by the input in the first step and set in all of the following steps.
+#[machine_check::machine_description]
+pub mod delayed {
+    use ::machine_check::Unsigned;
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {
+        bit: Unsigned<1>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        bit: Unsigned<1>,
+        started: Unsigned<1>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, _param: &Param) -> State {
+            State {
+                bit: Unsigned::<1>::new(0),
+                started: Unsigned::<1>::new(0),
+            }
+        }
+
+        fn next(&self, state: &State, input: &Input, _param: &Param) -> State {
+            let mut bit = Unsigned::<1>::new(1);
+            if state.started == Unsigned::<1>::new(0) {
+                bit = input.bit;
+            }
+            State {
+                bit,
+                started: Unsigned::<1>::new(1),
+            }
+        }
+    }
+}
+
+/// A wide counter wrapping around, with a flag that is never set.
+#[machine_check::machine_description]
+pub mod wrapping {
+    use ::machine_check::Unsigned;
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        ticks: Unsigned<16>,
+        flag: Unsigned<1>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, _param: &Param) -> State {
+            State {
+                ticks: Unsigned::<16>::new(0),
+                flag: Unsigned::<1>::new(0),
+            }
+        }
+
+        fn next(&self, state: &State, _input: &Input, _param: &Param) -> State {
+            State {
+                ticks: state.ticks + Unsigned::<16>::new(1),
+                flag: state.flag,
+            }
+        }
+    }
+}
+
+/// Registers of which only the second one is written, by the input unless it is all ones.
+#[machine_check::machine_description]
+pub mod registers {
+    use ::machine_check::{Bitvector, BitvectorArray};
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {
+        value: Bitvector<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        regs: BitvectorArray<2, 4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {
+        pub initial_regs: BitvectorArray<2, 4>,
+    }
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, _param: &Param) -> State {
+            State {
+                regs: Clone::clone(&self.initial_regs),
+            }
+        }
+
+        fn next(&self, state: &State, input: &Input, _param: &Param) -> State {
+            let mut regs = Clone::clone(&state.regs);
+            let mut value = input.value;
+            if value == Bitvector::<4>::new(15) {
+                value = Bitvector::<4>::new(0);
+            }
+            regs[Bitvector::<2>::new(1)] = value;
+            State { regs }
+        }
+    }
+}
+
+/// A latch storing the input in each step.
+#[machine_check::machine_description]
+pub mod latch {
+    use ::machine_check::Bitvector;
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {
+        value: Bitvector<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        value: Bitvector<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, _param: &Param) -> State {
+            State {
+                value: Bitvector::<4>::new(0),
+            }
+        }
+
+        fn next(&self, _state: &State, input: &Input, _param: &Param) -> State {
+            State { value: input.value }
+        }
+    }
+}
+
+/// The saturating system with the same field names, but a wider state value.
+#[machine_check::machine_description]
+pub mod saturating_wide {
+    use ::machine_check::{Ext, Unsigned};
+    use ::std::{
+        clone::Clone,
+        cmp::{Eq, PartialEq},
+        fmt::Debug,
+        hash::Hash,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Input {
+        value: Unsigned<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct Param {
+        max_value: Unsigned<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct State {
+        value: Unsigned<8>,
+        max_value: Unsigned<4>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    pub struct System {}
+
+    impl ::machine_check::Machine for System {
+        type Input = Input;
+        type Param = Param;
+        type State = State;
+
+        fn init(&self, _input: &Input, param: &Param) -> State {
+            State {
+                value: Unsigned::<8>::new(0),
+                max_value: param.max_value,
+            }
+        }
+
+        fn next(&self, state: &State, input: &Input, _param: &Param) -> State {
+            let mut value = input.value;
+            if value >= state.max_value {
+                value = state.max_value;
+            }
+            State {
+                value: Ext::<8>::ext(value),
+                max_value: state.max_value,
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Tests of the implication, equivalence and exclusive or of properties.
+
+mod common;
+
+use machine_check_common::{check::KnownConclusion, ExecError};
+
+use common::counter;
+
+#[test]
+fn connective_conclusions() {
+    let cases = [
+        ("AG![value == 9 -> EX![value == 0]]", KnownConclusion::True),
+        ("AG![value == 3 -> AF![value == 4]]", KnownConclusion::False),
+        ("AG![value < 5 -> value < 4]", KnownConclusion::False),
+        ("AG![EX![value == 1] <-> value <= 1]", KnownConclusion::True),
+        ("AG![value < 5 <-> value < 4]", KnownConclusion::False),
+        ("AG![(value < 5) ^ (value >= 5)]", KnownConclusion::True),
+        ("AG![(value < 5) ^ (value < 6)]", KnownConclusion::False),
+        // the fixed-point variable is only in the consequent, which is not negated
+        (
+            "lfp![X, value == 5 || (value < 5 -> EX![X])]",
+            KnownConclusion::True,
+        ),
+        (
+            "lfp![X, value == 5 || (value < 5 -> AX![X])]",
+            KnownConclusion::False,
+        ),
+    ];
+    for (property, expected) in cases {
+        assert_eq!(
+            common::conclusion(counter::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
+
+#[test]
+fn negated_fixed_variable() {
+    // the antecedent of the implication is negated, while the operands of the equivalence
+    // and exclusive or are duplicated both negated and not
+    for property in [
+        "lfp![X, EX![X] -> value == 5]",
+        "lfp![X, value == 5 <-> EX![X]]",
+        "gfp![X, (value == 5) ^ AX![X]]",
+    ] {
+        let result = common::verify(counter::System {}, property);
+        assert!(
+            matches!(result.result, Err(ExecError::NonMonotoneProperty)),
+            "{}: {:?}",
+            property,
+            result.result
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! Tests of exporting the state space.
+
+mod common;
+
+use machine_check::ExecArgs;
+use machine_check_common::check::KnownConclusion;
+
+use common::signed_constant;
+
+/// The property has subproperties with characters to be escaped.
+const ESCAPED_PROPERTY: &str = "AG![bits == 12 && as_unsigned(value) < 16]";
+
+/// Verifies the property, returning the state space exported to a file with the extension.
+fn export(property: &str, extension: &str, export_args: ExecArgs) -> String {
+    let path = std::env::temp_dir().join(format!(
+        "machine-check-test-{}-export-{}.{}",
+        std::process::id(),
+        export_args.export_labellings,
+        extension
+    ));
+    let result = common::execute(
+        signed_constant::System {},
+        ExecArgs {
+            property: vec![String::from(property)],
+            export_space: Some(path.clone()),
+            ..export_args
+        },
+    );
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::True
+    );
+    let exported = std::fs::read_to_string(&path).expect("The state space should be exported");
+    std::fs::remove_file(&path).expect("The exported state space should be removed");
+    exported
+}
+
+#[test]
+fn dot() {
+    assert_eq!(
+        export(ESCAPED_PROPERTY, "dot", ExecArgs::default()),
+        r#"digraph space {
+    node [shape=box];
+    0 [label="root"];
+    1 [label="bits = \"1100\"\npanic = \"00000000000000000000000000000000\"\nvalue = \"XXXX\""];
+    0 -> 1 [label=""];
+    1 -> 1 [label=""];
+}
+"#
+    );
+}
+
+#[test]
+fn dot_labellings() {
+    assert_eq!(
+        export(
+            ESCAPED_PROPERTY,
+            "gv",
+            ExecArgs {
+                export_labellings: true,
+                ..Default::default()
+            }
+        ),
+        r#"digraph space {
+    node [shape=box];
+    graph [subproperty0="AG![panic_id == 0]"];
+    graph [subproperty1="panic_id == 0"];
+    graph [subproperty2="AG![bits == 12 && as_unsigned(value) < 16]"];
+    graph [subproperty3="bits == 12 && as_unsigned(value) < 16"];
+    graph [subproperty4="bits == 12"];
+    graph [subproperty5="as_unsigned(value) < 16"];
+    0 [label="root"];
+    1 [label="bits = \"1100\"\npanic = \"00000000000000000000000000000000\"\nvalue = \"XXXX\"", subproperty0="true", subproperty1="true", subproperty2="true", subproperty3="true", subproperty4="true", subproperty5="true"];
+    0 -> 1 [label=""];
+    1 -> 1 [label=""];
+}
+"#
+    );
+}
+
+#[test]
+fn graphml_labellings() {
+    assert_eq!(
+        export(
+            ESCAPED_PROPERTY,
+            "graphml",
+            ExecArgs {
+                export_labellings: true,
+                ..Default::default()
+            }
+        ),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="label" for="all" attr.name="label" attr.type="string"/>
+  <key id="subproperty0" for="node" attr.name="AG![panic_id == 0]" attr.type="string"/>
+  <key id="subproperty1" for="node" attr.name="panic_id == 0" attr.type="string"/>
+  <key id="subproperty2" for="node" attr.name="AG![bits == 12 &amp;&amp; as_unsigned(value) &lt; 16]" attr.type="string"/>
+  <key id="subproperty3" for="node" attr.name="bits == 12 &amp;&amp; as_unsigned(value) &lt; 16" attr.type="string"/>
+  <key id="subproperty4" for="node" attr.name="bits == 12" attr.type="string"/>
+  <key id="subproperty5" for="node" attr.name="as_unsigned(value) &lt; 16" attr.type="string"/>
+  <graph id="space" edgedefault="directed">
+    <node id="n0">
+      <data key="label">root</data>
+    </node>
+    <node id="n1">
+      <data key="label">bits = &quot;1100&quot;
+panic = &quot;00000000000000000000000000000000&quot;
+value = &quot;XXXX&quot;</data>
+      <data key="subproperty0">true</data>
+      <data key="subproperty1">true</data>
+      <data key="subproperty2">true</data>
+      <data key="subproperty3">true</data>
+      <data key="subproperty4">true</data>
+      <data key="subproperty5">true</data>
+    </node>
+    <edge source="n0" target="n1">
+      <data key="label"></data>
+    </edge>
+    <edge source="n1" target="n1">
+      <data key="label"></data>
+    </edge>
+  </graph>
+</graphml>
+"#
+    );
+}
+
+#[test]
+fn hidden_translation_labellings() {
+    // the subproperties of the translated operators are not exported even without simplification
+    let exported = export(
+        "AG![bits == 12]",
+        "dot",
+        ExecArgs {
+            export_labellings: true,
+            no_simplify: true,
+            ..Default::default()
+        },
+    );
+    let declared: Vec<&str> = exported
+        .lines()
+        .filter(|line| line.trim_start().starts_with("graph ["))
+        .collect();
+    assert_eq!(
+        declared,
+        [
+            r#"    graph [subproperty0="AG![panic_id == 0]"];"#,
+            r#"    graph [subproperty1="panic_id == 0"];"#,
+            r#"    graph [subproperty2="AG![bits == 12]"];"#,
+            r#"    graph [subproperty3="bits == 12"];"#,
+        ]
+    );
+}
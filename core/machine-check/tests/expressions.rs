@@ -0,0 +1,47 @@
+//! Tests of the atomic properties comparing expressions with fields on both sides.
+
+mod common;
+
+use machine_check_common::check::KnownConclusion;
+
+use common::{counter, saturating};
+
+#[test]
+fn arithmetic_expressions() {
+    let cases = [
+        ("AG![value + 1 != 11]", KnownConclusion::True),
+        // the expressions are evaluated without wrapping
+        ("AG![value + 7 != 0]", KnownConclusion::True),
+        ("AG![value + 7 != 16]", KnownConclusion::False),
+        ("AG![(value & 8) == 0 || value < 10]", KnownConclusion::True),
+        ("EF![value - 3 == 6]", KnownConclusion::True),
+        ("AG![value + value != 18]", KnownConclusion::False),
+    ];
+    for (property, expected) in cases {
+        assert_eq!(
+            common::conclusion(counter::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
+
+#[test]
+fn field_comparisons() {
+    let cases = [
+        ("AG![value <= max_value]", KnownConclusion::True),
+        ("AG![max_value - value <= max_value]", KnownConclusion::True),
+        ("EF![value == max_value]", KnownConclusion::True),
+        ("AG![value < max_value]", KnownConclusion::False),
+        ("AG![value != max_value + 1]", KnownConclusion::True),
+    ];
+    for (property, expected) in cases {
+        assert_eq!(
+            common::conclusion(saturating::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
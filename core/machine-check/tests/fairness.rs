@@ -0,0 +1,49 @@
+//! Tests of the fairness constraints restricting the path quantifiers to fair paths.
+
+mod common;
+
+use machine_check::ExecArgs;
+use machine_check_common::check::KnownConclusion;
+
+use common::counter;
+
+fn verify_fair(property: &str, fairness: &[&str]) -> KnownConclusion {
+    let (property, ltl) = match property.strip_prefix("LTL ") {
+        Some(ltl) => (Vec::new(), Some(String::from(ltl))),
+        None => (vec![String::from(property)], None),
+    };
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            property,
+            ltl,
+            fairness: fairness.iter().copied().map(String::from).collect(),
+            ..Default::default()
+        },
+    );
+    result.result.expect("The verification should succeed")
+}
+
+#[test]
+fn fair_paths() {
+    // the counter can stay in a value forever, which is not fair here
+    assert_eq!(
+        verify_fair("AG![AF![value == 5]]", &[]),
+        KnownConclusion::False
+    );
+    assert_eq!(
+        verify_fair("AG![AF![value == 5]]", &["value == 5"]),
+        KnownConclusion::True
+    );
+    assert_eq!(
+        verify_fair("LTL G![F![value == 0]]", &["value == 0"]),
+        KnownConclusion::True
+    );
+
+    // there is no fair path staying below three
+    assert_eq!(verify_fair("EG![value < 3]", &[]), KnownConclusion::True);
+    assert_eq!(
+        verify_fair("EG![value < 3]", &["value == 0", "value == 1"]),
+        KnownConclusion::False
+    );
+}
@@ -0,0 +1,90 @@
+//! Tests of the propositions referencing the initial states.
+
+mod common;
+
+use machine_check_common::check::KnownConclusion;
+
+use common::{complementary, saturating};
+
+#[test]
+fn init_in_parametrised_initial_states() {
+    // the initial states differ in the maximum given by the parameter
+    for (property, expected) in [
+        ("AG![EF![init]]", KnownConclusion::True),
+        ("AG![init -> value == 0]", KnownConclusion::True),
+        ("AG![value == 0 -> init]", KnownConclusion::True),
+        ("EF![init && value == 3]", KnownConclusion::False),
+        // only the successors of the initial state with the zero maximum are initial
+        ("AX![init]", KnownConclusion::Dependent),
+    ] {
+        assert_eq!(
+            common::conclusion(saturating::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
+
+#[test]
+fn init_compares_with_single_initial_state() {
+    // each field of the states with equal bits is equal to the field in some initial state,
+    // but the states are not initial
+    for (property, expected) in [
+        ("AG![a == b -> !(init)]", KnownConclusion::True),
+        ("AG![init -> a != b]", KnownConclusion::True),
+        ("EF![a == b && init]", KnownConclusion::False),
+        ("EF![a == 1 && b == 1]", KnownConclusion::True),
+        ("AG![EF![init]]", KnownConclusion::True),
+    ] {
+        assert_eq!(
+            common::conclusion(complementary::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
+
+#[test]
+fn state_eq_init_in_some_initial_state() {
+    // the field is compared with each initial state separately
+    for (property, expected) in [
+        ("AG![state_eq_init(max_value)]", KnownConclusion::True),
+        ("AG![state_eq_init(value)]", KnownConclusion::Dependent),
+        (
+            "AG![value == 0 -> state_eq_init(value)]",
+            KnownConclusion::True,
+        ),
+        ("AG![value <= init.max_value]", KnownConclusion::True),
+        // only the initial states can be refined to decide the comparison
+        ("AG![init.max_value == 7]", KnownConclusion::True),
+        (
+            "EF![init.max_value == 7 && init.value == 1]",
+            KnownConclusion::False,
+        ),
+    ] {
+        assert_eq!(
+            common::conclusion(saturating::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+    for (property, expected) in [
+        (
+            "AG![state_eq_init(a) && state_eq_init(b)]",
+            KnownConclusion::True,
+        ),
+        ("AG![a + b == init.a + init.b]", KnownConclusion::False),
+        ("AG![a + b <= init.a + init.b]", KnownConclusion::False),
+        ("AG![a + b >= init.a + init.b - 1]", KnownConclusion::True),
+    ] {
+        assert_eq!(
+            common::conclusion(complementary::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
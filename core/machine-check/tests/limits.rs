@@ -0,0 +1,90 @@
+//! Tests of the resource limits of the verification.
+
+mod common;
+
+use machine_check::ExecArgs;
+use machine_check_common::{check::KnownConclusion, ExecError, ExecResult, ResourceLimit};
+
+use common::saturating;
+
+/// The property needs many refinements to be verified.
+const REFINED_PROPERTY: &str = "AG![value <= param.max_value]";
+
+fn verify_limited(limited_args: ExecArgs) -> ExecResult {
+    common::execute(
+        saturating::System {},
+        ExecArgs {
+            property: vec![String::from(REFINED_PROPERTY)],
+            ..limited_args
+        },
+    )
+}
+
+fn reached_limit(result: &ExecResult) -> ResourceLimit {
+    match &result.result {
+        Err(ExecError::ResourceLimit(limit, culprit)) => {
+            assert!(!culprit.path.is_empty());
+            *limit
+        }
+        other => panic!("A resource limit should be reached, have {:?}", other),
+    }
+}
+
+#[test]
+fn unlimited() {
+    let result = verify_limited(ExecArgs::default());
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::True
+    );
+    assert!(result.stats.num_refinements > 10);
+}
+
+#[test]
+fn refinement_limit() {
+    let result = verify_limited(ExecArgs {
+        max_refinements: Some(10),
+        ..Default::default()
+    });
+    assert_eq!(reached_limit(&result), ResourceLimit::Refinements);
+    assert_eq!(result.stats.num_refinements, 10);
+}
+
+#[test]
+fn state_limit() {
+    let result = verify_limited(ExecArgs {
+        max_states: Some(20),
+        ..Default::default()
+    });
+    assert_eq!(reached_limit(&result), ResourceLimit::States);
+    assert!(result.stats.num_final_states > 20);
+}
+
+#[test]
+fn time_limit() {
+    // the time is up before the first refinement
+    let result = verify_limited(ExecArgs {
+        timeout: Some(0),
+        ..Default::default()
+    });
+    assert_eq!(reached_limit(&result), ResourceLimit::Timeout);
+    assert_eq!(result.stats.num_refinements, 0);
+}
+
+#[test]
+fn limits_not_reached() {
+    // the limits are only checked when a refinement is needed
+    let result = common::execute(
+        saturating::System {},
+        ExecArgs {
+            timeout: Some(0),
+            max_refinements: Some(0),
+            max_states: Some(0),
+            ..common::property_args("AG![value <= 15]")
+        },
+    );
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::True
+    );
+}
@@ -0,0 +1,109 @@
+//! Tests of the Linear Temporal Logic properties.
+//!
+//! The properties are verified with refinements, so the labellings are updated
+//! incrementally as the state space changes. The LTL properties are translated
+//! to alternating fixed points, so the alternating fixed points are also tested directly.
+//! The incremental labellings are double-checked against the ones computed from scratch
+//! after each update.
+
+mod common;
+
+use machine_check::ExecArgs;
+use machine_check_common::check::KnownConclusion;
+
+use common::counter;
+
+fn enable_double_check() {
+    // the variable is read once per process, before the first check
+    std::env::set_var("MACHINE_CHECK_INCREMENTAL_DOUBLE_CHECK", "1");
+}
+
+fn verify_counter_ltl(ltl: &str) -> KnownConclusion {
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            ltl: Some(String::from(ltl)),
+            ..Default::default()
+        },
+    );
+    result
+        .result
+        .unwrap_or_else(|err| panic!("The verification of '{}' should succeed: {}", ltl, err))
+}
+
+#[test]
+fn ltl_conclusions() {
+    enable_double_check();
+    let cases = [
+        ("G![value < 10]", KnownConclusion::True),
+        ("F![value == 1]", KnownConclusion::False),
+        ("G![F![value == 0]]", KnownConclusion::False),
+        ("F![G![value != 12]]", KnownConclusion::True),
+        (
+            "G![!(value == 3) || F![value == 4]]",
+            KnownConclusion::False,
+        ),
+        (
+            "G![!(value == 3) || X![value == 3 || value == 4]]",
+            KnownConclusion::True,
+        ),
+        (
+            "G![!(value == 9) || X![value == 9 || value == 0]]",
+            KnownConclusion::True,
+        ),
+        ("U![value < 3, value == 3]", KnownConclusion::False),
+        (
+            "U![value < 4, value >= 4] || G![value < 4]",
+            KnownConclusion::True,
+        ),
+        ("R![value == 3, value < 4]", KnownConclusion::True),
+        ("R![value == 2, value < 2]", KnownConclusion::False),
+        ("F![G![value == 5]]", KnownConclusion::False),
+        (
+            "G![!(value == 5) || X![X![value != 8]]]",
+            KnownConclusion::True,
+        ),
+    ];
+    for (ltl, expected) in cases {
+        assert_eq!(verify_counter_ltl(ltl), expected, "{}", ltl);
+    }
+}
+
+#[test]
+fn alternating_fixed_points() {
+    enable_double_check();
+    let cases = [
+        (
+            "lfp![X, gfp![Y, AX![(value != 12 && Y) || X]]]",
+            KnownConclusion::True,
+        ),
+        (
+            "gfp![X, lfp![Y, AX![(value != 12 || Y) && X]]]",
+            KnownConclusion::True,
+        ),
+        (
+            "lfp![X, gfp![Y, value != 12 && AX![Y]] || AX![X]]",
+            KnownConclusion::True,
+        ),
+        (
+            "gfp![X, lfp![Y, (value == 3 && AX![X]) || AX![Y]]]",
+            KnownConclusion::False,
+        ),
+        (
+            "gfp![X, lfp![Y, EX![(value != 5 || Y) && X]]]",
+            KnownConclusion::True,
+        ),
+        (
+            "lfp![X, gfp![Y, EX![(value == 5 && Y) || X]]]",
+            KnownConclusion::True,
+        ),
+    ];
+    for (property, expected) in cases {
+        assert_eq!(
+            common::conclusion(counter::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Tests of verifying multiple properties in one run.
+
+mod common;
+
+use machine_check::ExecArgs;
+use machine_check_common::check::KnownConclusion;
+
+use common::{counter, saturating};
+
+#[test]
+fn property_results() {
+    let properties = ["AG![value <= 9]", "AG![value < 9]", "EF![value == 7]"];
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            property: properties
+                .iter()
+                .map(|property| String::from(*property))
+                .collect(),
+            ..Default::default()
+        },
+    );
+    // the result holds only if each of the properties holds
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::False
+    );
+
+    let property_results = result
+        .property_results
+        .expect("The property results should be present");
+    let expected = [
+        KnownConclusion::True,
+        KnownConclusion::False,
+        KnownConclusion::True,
+    ];
+    assert_eq!(property_results.len(), properties.len());
+    for ((property_result, property), expected) in
+        property_results.iter().zip(properties).zip(expected)
+    {
+        assert_eq!(property_result.name, property);
+        assert_eq!(
+            property_result
+                .result
+                .clone()
+                .expect("The property verification should succeed"),
+            expected,
+            "{}",
+            property
+        );
+        assert!(property_result.trace.is_some(), "{}", property);
+        assert!(property_result.param_classes.is_none(), "{}", property);
+    }
+
+    // the state space is kept between the properties, so their work adds up to at most the total
+    let property_refinements: usize = property_results
+        .iter()
+        .map(|property_result| property_result.stats.num_refinements)
+        .sum();
+    assert!(property_refinements <= result.stats.num_refinements);
+    assert_eq!(
+        property_results.last().unwrap().stats.num_final_states,
+        result.stats.num_final_states
+    );
+}
+
+#[test]
+fn dependent_property_results() {
+    let result = common::execute(
+        saturating::System {},
+        ExecArgs {
+            property: vec![
+                String::from("AG![value <= 15]"),
+                String::from("AG![value < 5]"),
+            ],
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::Dependent
+    );
+    let property_results = result
+        .property_results
+        .expect("The property results should be present");
+    assert_eq!(
+        property_results[0].result.clone().unwrap(),
+        KnownConclusion::True
+    );
+    assert!(property_results[0].param_classes.is_none());
+    assert_eq!(
+        property_results[1].result.clone().unwrap(),
+        KnownConclusion::Dependent
+    );
+    assert!(property_results[1].param_classes.is_some());
+}
+
+#[test]
+fn property_file_results() {
+    let path = std::env::temp_dir().join(format!(
+        "machine-check-test-{}-properties.txt",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        "// the counter wraps before reaching 10\n\
+         let small = value < 10;\n\
+         property always_small: AG![small];\n\
+         property reaches_large: EF![!(small)];\n",
+    )
+    .expect("The property file should be written");
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            property_file: Some(path.clone()),
+            ..Default::default()
+        },
+    );
+    std::fs::remove_file(&path).expect("The property file should be removed");
+
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::False
+    );
+    let property_results: Vec<_> = result
+        .property_results
+        .expect("The property results should be present")
+        .into_iter()
+        .map(|property_result| (property_result.name, property_result.result.unwrap()))
+        .collect();
+    assert_eq!(
+        property_results,
+        vec![
+            (String::from("always_small"), KnownConclusion::True),
+            (String::from("reaches_large"), KnownConclusion::False),
+        ]
+    );
+}
@@ -0,0 +1,138 @@
+//! Tests of observing and cancelling the verification.
+
+mod common;
+
+use std::{
+    ops::ControlFlow,
+    sync::{Arc, Mutex},
+};
+
+use machine_check_common::{check::KnownConclusion, property::Property, ExecError};
+use machine_check_exec::{Framework, Strategy, VerificationEvent, VerificationObserver};
+use mck::abstr::Abstr;
+
+use common::saturating;
+
+/// The property needs many refinements to be verified.
+const REFINED_PROPERTY: &str = "AG![value <= param.max_value]";
+
+/// Records the events, requesting cancellation after the given number of refinements.
+struct RecordingObserver {
+    events: Arc<Mutex<Vec<VerificationEvent>>>,
+    cancel_after_refinements: Option<usize>,
+    num_refinements: usize,
+}
+
+impl VerificationObserver for RecordingObserver {
+    fn observe(&mut self, event: &VerificationEvent) -> ControlFlow<()> {
+        self.events.lock().unwrap().push(event.clone());
+        if matches!(event, VerificationEvent::Refined { .. }) {
+            self.num_refinements += 1;
+        }
+        match self.cancel_after_refinements {
+            Some(max_refinements) if self.num_refinements >= max_refinements => {
+                ControlFlow::Break(())
+            }
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+fn framework() -> Framework<saturating::System> {
+    let abstract_system = <saturating::System as mck::concr::FullMachine>::Abstr::from_concrete(
+        saturating::System {},
+    );
+    Framework::new(
+        abstract_system,
+        Strategy {
+            naive_inputs: false,
+            use_decay: false,
+            num_threads: 1,
+        },
+    )
+}
+
+fn observe(
+    framework: &mut Framework<saturating::System>,
+    cancel_after_refinements: Option<usize>,
+) -> Arc<Mutex<Vec<VerificationEvent>>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    framework.set_observer(Box::new(RecordingObserver {
+        events: Arc::clone(&events),
+        cancel_after_refinements,
+        num_refinements: 0,
+    }));
+    events
+}
+
+fn num_refined(events: &[VerificationEvent]) -> usize {
+    events
+        .iter()
+        .filter(|event| matches!(event, VerificationEvent::Refined { .. }))
+        .count()
+}
+
+#[test]
+fn observing_does_not_change_verification() {
+    let property = Property::parse(REFINED_PROPERTY).unwrap();
+
+    let mut unobserved = framework();
+    let unobserved_result = unobserved.verify(&property);
+    let unobserved_stats = unobserved.info();
+
+    let mut observed = framework();
+    let events = observe(&mut observed, None);
+    let observed_result = observed.verify(&property);
+    let observed_stats = observed.info();
+
+    assert!(matches!(observed_result, Ok(KnownConclusion::True)));
+    assert!(matches!(unobserved_result, Ok(KnownConclusion::True)));
+    assert_eq!(observed_stats, unobserved_stats);
+
+    let events = events.lock().unwrap();
+    assert_eq!(num_refined(&events), observed_stats.num_refinements);
+    assert!(matches!(
+        events.last(),
+        Some(VerificationEvent::ModelChecked { .. })
+    ));
+}
+
+#[test]
+fn cancelled_before_next_refinement() {
+    let property = Property::parse(REFINED_PROPERTY).unwrap();
+    let mut framework = framework();
+    let events = observe(&mut framework, Some(3));
+
+    assert!(matches!(
+        framework.verify(&property),
+        Err(ExecError::Cancelled)
+    ));
+    assert_eq!(framework.info().num_refinements, 3);
+
+    // the state space is still model-checked after the cancelling refinement
+    let events = events.lock().unwrap();
+    assert_eq!(num_refined(&events), 3);
+    assert!(matches!(
+        events.last(),
+        Some(VerificationEvent::ModelChecked { .. })
+    ));
+}
+
+#[test]
+fn new_observer_discards_cancellation() {
+    let property = Property::parse(REFINED_PROPERTY).unwrap();
+    let mut framework = framework();
+    observe(&mut framework, Some(1));
+    assert!(matches!(
+        framework.verify(&property),
+        Err(ExecError::Cancelled)
+    ));
+
+    // the verification continues from the cancelled state space
+    let events = observe(&mut framework, None);
+    assert!(matches!(
+        framework.verify(&property),
+        Ok(KnownConclusion::True)
+    ));
+    assert!(num_refined(&events.lock().unwrap()) > 0);
+}
@@ -0,0 +1,42 @@
+//! Tests of the propositions referencing the panics.
+
+mod common;
+
+use machine_check::ExecArgs;
+use machine_check_common::check::KnownConclusion;
+
+use common::reserved;
+
+/// Verifies the property, assuming nothing about the panics.
+fn conclusion(property: &str) -> KnownConclusion {
+    common::execute(
+        reserved::System {},
+        ExecArgs {
+            assume_inherent: true,
+            ..common::property_args(property)
+        },
+    )
+    .result
+    .unwrap_or_else(|err| panic!("The verification of '{}' should succeed: {}", property, err))
+}
+
+#[test]
+fn shared_message_covers_all_panic_sites() {
+    // the panic ids of the sites follow the ones of the division and remainder by zero,
+    // only the second site with the message is reachable
+    assert_eq!(conclusion("AG![panic_id != 3]"), KnownConclusion::True);
+    assert_eq!(conclusion("AG![panic_id != 4]"), KnownConclusion::False);
+
+    assert_eq!(
+        conclusion(r#"AG![!(panic("Reserved opcode"))]"#),
+        KnownConclusion::False
+    );
+    assert_eq!(
+        conclusion(r#"EF![panic("Reserved opcode")]"#),
+        KnownConclusion::True
+    );
+    assert_eq!(
+        conclusion(r#"AG![panic("Reserved opcode") -> opcode == 3]"#),
+        KnownConclusion::True
+    );
+}
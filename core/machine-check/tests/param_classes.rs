@@ -0,0 +1,71 @@
+//! Tests of the classes of parameter values deciding the results depending on the parameters.
+
+mod common;
+
+use std::collections::BTreeMap;
+
+use machine_check_common::check::{KnownConclusion, ParamClass};
+
+use common::{saturating, signed_constant};
+
+fn class(field: &str, values: &str, holds: bool) -> ParamClass {
+    ParamClass {
+        param: BTreeMap::from([(String::from(field), String::from(values))]),
+        holds,
+    }
+}
+
+fn dependent_classes<M: mck::concr::FullMachine>(system: M, property: &str) -> Vec<ParamClass> {
+    let result = common::verify(system, property);
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::Dependent,
+        "{}",
+        property
+    );
+    result
+        .param_classes
+        .expect("The parameter classes should be decided")
+}
+
+#[test]
+fn value_ranges() {
+    // the classes differing in the values of a single field are merged to ranges
+    assert_eq!(
+        dependent_classes(saturating::System {}, "AG![value < 5]"),
+        vec![
+            class("max_value", "0..=4", true),
+            class("max_value", "5..=15", false)
+        ]
+    );
+    assert_eq!(
+        dependent_classes(saturating::System {}, "EF![value == 4]"),
+        vec![
+            class("max_value", "0..=3", false),
+            class("max_value", "4..=15", true)
+        ]
+    );
+    // the merged values need not be contiguous
+    assert_eq!(
+        dependent_classes(
+            saturating::System {},
+            "EF![value == 4 && param.max_value == 7]"
+        ),
+        vec![
+            class("max_value", "{0..=6, 8..=15}", false),
+            class("max_value", "7", true)
+        ]
+    );
+}
+
+#[test]
+fn signed_value_ranges() {
+    // the ranges are given by the declared signedness of the parameter
+    assert_eq!(
+        dependent_classes(signed_constant::System {}, "AG![value >= 0]"),
+        vec![
+            class("value", "0..=7", true),
+            class("value", "-8..=-1", false)
+        ]
+    );
+}
@@ -0,0 +1,57 @@
+//! Tests of the index quantifiers over array fields.
+
+mod common;
+
+use machine_check::{Bitvector, BitvectorArray};
+use machine_check_common::check::KnownConclusion;
+
+use common::registers;
+
+fn system() -> registers::System {
+    registers::System {
+        initial_regs: BitvectorArray::new_filled(Bitvector::new(0)),
+    }
+}
+
+/// Verifies the property, returning the conclusion and the number of refinements.
+fn verify(property: &str) -> (KnownConclusion, usize) {
+    let result = common::verify(system(), property);
+    let conclusion = result
+        .result
+        .unwrap_or_else(|err| panic!("The verification of '{}' should succeed: {}", property, err));
+    (conclusion, result.stats.num_refinements)
+}
+
+#[test]
+fn culprit_at_quantified_index() {
+    // only the second register depends on the input, so the culprit must be instantiated
+    // at its index, refining the same as if the register was written explicitly
+    for (quantified, explicit, expected) in [
+        (
+            "AG![forall i in 0..4: regs[i] != 15]",
+            "AG![regs[1] != 15]",
+            KnownConclusion::True,
+        ),
+        (
+            "EF![exists i in 0..4: regs[i] == 7]",
+            "EF![regs[1] == 7]",
+            KnownConclusion::True,
+        ),
+        (
+            "AG![forall i in 0..4: regs[i] != 7]",
+            "AG![regs[1] != 7]",
+            KnownConclusion::False,
+        ),
+    ] {
+        let (quantified_conclusion, quantified_refinements) = verify(quantified);
+        let (explicit_conclusion, explicit_refinements) = verify(explicit);
+        assert_eq!(quantified_conclusion, expected, "{}", quantified);
+        assert_eq!(explicit_conclusion, expected, "{}", explicit);
+        assert!(quantified_refinements > 0, "{}", quantified);
+        assert_eq!(
+            quantified_refinements, explicit_refinements,
+            "{}",
+            quantified
+        );
+    }
+}
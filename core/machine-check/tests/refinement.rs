@@ -0,0 +1,62 @@
+//! Tests of the refinements deduced from the culprits of unknown results.
+
+mod common;
+
+use machine_check_common::check::KnownConclusion;
+
+use common::{counter, latch};
+
+#[test]
+fn culprit_through_self_loop() {
+    // the culprit of the greatest fixed point loops in a state where the counter is not incremented,
+    // so the deduction revisits the state and must consider the labelling of its earlier iteration
+    let result = common::verify(
+        counter::System {},
+        "lfp![X, gfp![Y, AX![(value != 12 && Y) || X]]]",
+    );
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::True
+    );
+    assert!(result.stats.num_refinements > 0);
+}
+
+#[test]
+fn input_fields_in_stale_precision() {
+    // the input precision required by the culprit is already present, but the successors
+    // were generated before it was raised, so they must be regenerated instead of giving up
+    for (property, expected) in [
+        (
+            "AG![input.increment == 1 || value != 3]",
+            KnownConclusion::False,
+        ),
+        (
+            "EF![input.increment == 1 && value == 5]",
+            KnownConclusion::True,
+        ),
+    ] {
+        assert_eq!(
+            common::conclusion(counter::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
+
+#[test]
+fn sliced_culprit_marks_only_slice() {
+    // the tautology over a single bit only needs the bit to be precise,
+    // while the tautology over the whole field needs all of its bits
+    let sliced = common::verify(
+        latch::System {},
+        "AG![bit(value, 0) == 0 || bit(value, 0) == 1]",
+    );
+    let whole = common::verify(latch::System {}, "AG![value == 0 || value != 0]");
+    for result in [&sliced, &whole] {
+        assert_eq!(result.result.as_ref().ok(), Some(&KnownConclusion::True));
+    }
+    assert!(sliced.stats.num_refinements > 0);
+    assert!(sliced.stats.num_refinements < whole.stats.num_refinements);
+    assert!(sliced.stats.num_final_states < whole.stats.num_final_states);
+}
@@ -0,0 +1,63 @@
+//! Tests of the signedness of the fields in property comparisons.
+
+mod common;
+
+use machine_check_common::{check::KnownConclusion, ExecError};
+
+use common::{counter, signed_constant};
+
+#[test]
+fn declared_signedness() {
+    // the unsigned counter value is at most 9, the values 8 and 9 are negative when signed
+    for (property, expected) in [
+        ("AG![value <= 9]", KnownConclusion::True),
+        ("AG![value < 9]", KnownConclusion::False),
+        ("AG![as_signed(value) >= 0]", KnownConclusion::False),
+    ] {
+        assert_eq!(
+            common::conclusion(counter::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+
+    // the signed value is between -8 and 7 depending on the parameter
+    for (property, expected) in [
+        ("AG![value <= 7]", KnownConclusion::True),
+        ("AG![value >= -8]", KnownConclusion::True),
+        ("AG![value >= 0]", KnownConclusion::Dependent),
+        ("AG![as_unsigned(value) <= 7]", KnownConclusion::Dependent),
+        ("AG![as_unsigned(value) <= 15]", KnownConclusion::True),
+    ] {
+        assert_eq!(
+            common::conclusion(signed_constant::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+}
+
+#[test]
+fn plain_bitvector_signedness() {
+    // the plain bit-vector is 12 unsigned and -4 signed
+    for (property, expected) in [
+        ("AG![as_unsigned(bits) == 12]", KnownConclusion::True),
+        ("AG![as_signed(bits) == -4]", KnownConclusion::True),
+        ("AG![as_signed(bits) < 0]", KnownConclusion::True),
+    ] {
+        assert_eq!(
+            common::conclusion(signed_constant::System {}, property),
+            expected,
+            "{}",
+            property
+        );
+    }
+
+    // the signedness must be given for the plain bit-vector
+    assert!(matches!(
+        common::verify(signed_constant::System {}, "AG![bits < 13]").result,
+        Err(ExecError::SignednessNotEstabilished(_))
+    ));
+}
@@ -0,0 +1,69 @@
+//! Tests of simplifying the properties before model checking.
+
+mod common;
+
+use machine_check::ExecArgs;
+
+use common::counter;
+
+fn verify(property: &str, no_simplify: bool) -> (String, Vec<(usize, String, String)>) {
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            subproperty_results: true,
+            no_simplify,
+            ..common::property_args(property)
+        },
+    );
+    let conclusion = result
+        .result
+        .expect("The verification should succeed")
+        .to_string();
+    let subproperty_results = result
+        .subproperty_results
+        .expect("The subproperty results should be present");
+    (
+        conclusion,
+        common::flat_subproperty_results(&subproperty_results),
+    )
+}
+
+#[test]
+fn same_results() {
+    // the simplification does not change the result or the displayed root
+    for property in [
+        "!(!(AG![true && value < 9]))",
+        "AG![value <= 9] && (false || EF![value == 3])",
+        "AF![value == 5 || false] -> !(!(EG![true]))",
+        "EX![!(!(value == 1))] ^ AX![value == 1 && true]",
+    ] {
+        let (simplified_conclusion, simplified) = verify(property, false);
+        let (conclusion, unsimplified) = verify(property, true);
+        assert_eq!(simplified_conclusion, conclusion, "{}", property);
+        assert_eq!(simplified[0], unsimplified[0], "{}", property);
+    }
+}
+
+#[test]
+fn removed_subproperties() {
+    // the subproperties removed by the simplification are only reported without it
+    let property = "!(!(AG![true && value < 9]))";
+    let removed = [
+        (
+            1,
+            String::from("!(AG![true && value < 9])"),
+            String::from("true"),
+        ),
+        (
+            2,
+            String::from("AG![true && value < 9]"),
+            String::from("false"),
+        ),
+    ];
+    let (_, simplified) = verify(property, false);
+    let (_, unsimplified) = verify(property, true);
+    for subproperty in &removed {
+        assert!(!simplified.contains(subproperty), "{:?}", subproperty);
+        assert!(unsimplified.contains(subproperty), "{:?}", subproperty);
+    }
+}
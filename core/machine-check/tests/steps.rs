@@ -0,0 +1,136 @@
+//! Tests of the queries of the number of steps until a formula is reached.
+
+mod common;
+
+use machine_check::ExecArgs;
+use machine_check_common::{
+    check::{KnownConclusion, ReplayVerdict, Trace},
+    ExecResult, StepBound,
+};
+use mck::concr::FullMachine;
+
+use common::{concrete_field, counter, delayed, saturating, timer};
+
+fn max_steps_until<M: FullMachine>(system: M, formula: &str) -> ExecResult {
+    common::execute(
+        system,
+        ExecArgs {
+            max_steps_until: Some(String::from(formula)),
+            ..Default::default()
+        },
+    )
+}
+
+fn min_steps_until<M: FullMachine>(system: M, formula: &str) -> ExecResult {
+    common::execute(
+        system,
+        ExecArgs {
+            min_steps_until: Some(String::from(formula)),
+            ..Default::default()
+        },
+    )
+}
+
+fn field_values(trace: &Trace, name: &str) -> Vec<u64> {
+    trace
+        .steps
+        .iter()
+        .map(|step| concrete_field(&step.state, name))
+        .collect()
+}
+
+#[test]
+fn min_steps_with_witness() {
+    let result = min_steps_until(counter::System {}, "value == 5");
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::True
+    );
+    assert_eq!(result.step_bound, Some(StepBound::Finite(5)));
+    assert!(result.stats.num_refinements > 0);
+
+    // the witness increments the counter in each step
+    let trace = result.trace.expect("The steps should be shown by a trace");
+    assert_eq!(field_values(&trace, "value"), vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(trace.loop_start, None);
+    let replay = trace.replay.expect("The trace should be replayed");
+    assert_eq!(replay.verdict, ReplayVerdict::Confirmed);
+}
+
+#[test]
+fn unknown_states_refined() {
+    // the formula is reached in the second step on all paths, so its reachability is verified
+    // without finding out that the input can set the bit in the first step
+    let result = min_steps_until(delayed::System {}, "bit == 1");
+    assert_eq!(result.step_bound, Some(StepBound::Finite(1)));
+    let trace = result.trace.expect("The steps should be shown by a trace");
+    assert_eq!(field_values(&trace, "bit"), vec![0, 1]);
+
+    let result = max_steps_until(delayed::System {}, "bit == 1");
+    assert_eq!(result.step_bound, Some(StepBound::Finite(2)));
+}
+
+#[test]
+fn zero_steps_in_initial_states() {
+    for result in [
+        max_steps_until(counter::System {}, "value == 0"),
+        min_steps_until(counter::System {}, "value == 0"),
+    ] {
+        assert_eq!(result.step_bound, Some(StepBound::Finite(0)));
+        let trace = result.trace.expect("The steps should be shown by a trace");
+        assert_eq!(field_values(&trace, "value"), vec![0]);
+    }
+}
+
+#[test]
+fn infinite_max_steps_with_lasso() {
+    // the counter can stay at zero forever
+    let result = max_steps_until(counter::System {}, "value == 5");
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::False
+    );
+    assert_eq!(result.step_bound, Some(StepBound::Infinite));
+
+    let trace = result
+        .trace
+        .expect("The path not reaching the formula should be shown");
+    let loop_start = trace.loop_start.expect("The path should loop");
+    assert!(loop_start < trace.steps.len());
+    assert!(field_values(&trace, "value")
+        .into_iter()
+        .all(|value| value != 5));
+}
+
+#[test]
+fn steps_beyond_bounded_operators() {
+    // the slow timer needs the most steps, which exceed the bounds of the bounded operators
+    let result = max_steps_until(timer::System {}, "ticks >= 300");
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::True
+    );
+    assert_eq!(result.step_bound, Some(StepBound::Finite(300)));
+    let trace = result.trace.expect("The steps should be shown by a trace");
+    assert_eq!(trace.steps.len(), 301);
+
+    // the fast timer needs the least steps
+    let result = min_steps_until(timer::System {}, "ticks >= 300");
+    assert_eq!(result.step_bound, Some(StepBound::Finite(150)));
+}
+
+#[test]
+fn reachability_dependent_on_parameters() {
+    // the maximum value is kept from the parameter
+    let result = min_steps_until(saturating::System {}, "max_value == 15");
+    assert_eq!(
+        result.result.expect("The verification should succeed"),
+        KnownConclusion::Dependent
+    );
+    assert_eq!(result.step_bound, None);
+    let param_classes = result
+        .param_classes
+        .expect("The result should be given for each class of parameters");
+    assert!(param_classes.iter().any(|class| class.holds));
+    assert!(param_classes.iter().any(|class| !class.holds));
+}
@@ -0,0 +1,112 @@
+//! Tests of the results of the subproperties in the initial states.
+
+mod common;
+
+use machine_check::ExecArgs;
+use machine_check_common::SubpropertyResult;
+
+use common::{counter, saturating};
+
+fn flat(results: Option<Vec<SubpropertyResult>>) -> Vec<(usize, String, String)> {
+    common::flat_subproperty_results(&results.expect("The subproperty results should be present"))
+}
+
+fn expected(entries: &[(usize, &str, &str)]) -> Vec<(usize, String, String)> {
+    entries
+        .iter()
+        .map(|(depth, name, value)| (*depth, String::from(*name), String::from(*value)))
+        .collect()
+}
+
+#[test]
+fn subproperty_tree() {
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            subproperty_results: true,
+            ..common::property_args("AG![value < 9] || (value == 0 -> AX![value == 1])")
+        },
+    );
+    assert_eq!(
+        flat(result.subproperty_results),
+        expected(&[
+            (
+                0,
+                "AG![value < 9] || (value == 0 -> AX![value == 1])",
+                "false"
+            ),
+            (1, "AG![value < 9]", "false"),
+            (2, "value < 9", "true"),
+            (1, "value == 0 -> AX![value == 1]", "false"),
+            (2, "value == 0", "true"),
+            (2, "AX![value == 1]", "false"),
+            (3, "value == 1", "false"),
+        ])
+    );
+}
+
+#[test]
+fn dependent_subproperties() {
+    let result = common::execute(
+        saturating::System {},
+        ExecArgs {
+            subproperty_results: true,
+            ..common::property_args("AG![value < 5] && EF![value == 1]")
+        },
+    );
+    assert_eq!(
+        flat(result.subproperty_results),
+        expected(&[
+            (0, "AG![value < 5] && EF![value == 1]", "dependent"),
+            (1, "AG![value < 5]", "dependent"),
+            (2, "value < 5", "true"),
+            (1, "EF![value == 1]", "dependent"),
+            (2, "value == 1", "false"),
+        ])
+    );
+}
+
+#[test]
+fn hidden_translation() {
+    // the subproperties of the translated operators are not reported even without simplification
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            subproperty_results: true,
+            no_simplify: true,
+            ..common::property_args("AG![value < 9]")
+        },
+    );
+    assert_eq!(
+        flat(result.subproperty_results),
+        expected(&[(0, "AG![value < 9]", "false"), (1, "value < 9", "true")])
+    );
+}
+
+#[test]
+fn multiple_property_subproperties() {
+    // the subproperty results are reported for each property
+    let result = common::execute(
+        counter::System {},
+        ExecArgs {
+            property: vec![
+                String::from("AG![value < 5]"),
+                String::from("EF![value == 2]"),
+            ],
+            subproperty_results: true,
+            ..Default::default()
+        },
+    );
+    assert!(result.subproperty_results.is_none());
+    let property_results = result
+        .property_results
+        .expect("The property results should be present");
+    assert_eq!(
+        flat(property_results[0].subproperty_results.clone()),
+        expected(&[(0, "AG![value < 5]", "false"), (1, "value < 5", "true")])
+    );
+    assert_eq!(
+        flat(property_results[1].subproperty_results.clone()),
+        expected(&[(0, "EF![value == 2]", "true"), (1, "value == 2", "false")])
+    );
+}
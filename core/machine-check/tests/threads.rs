@@ -0,0 +1,53 @@
+//! Tests that the verification does not depend on the number of threads.
+
+mod common;
+
+use machine_check_common::property::Property;
+use machine_check_exec::{Framework, SpaceFormat, Strategy};
+use mck::{abstr::Abstr, concr::FullMachine};
+
+use common::{counter, saturating};
+
+/// Verifies the property using the given number of threads,
+/// returning the debug-formatted result, the statistics and the exported labelled state space.
+fn verify_threaded<M: FullMachine>(system: M, property: &str, num_threads: usize) -> String {
+    let property = Property::parse(property).unwrap();
+    let mut framework = Framework::<M>::new(
+        M::Abstr::from_concrete(system),
+        Strategy {
+            naive_inputs: false,
+            use_decay: false,
+            num_threads,
+        },
+    );
+    let result = framework.verify(&property);
+    let stats = framework.info();
+    let space = framework
+        .export_space(SpaceFormat::Dot, &[property.root_subproperty()])
+        .unwrap();
+    format!("{:?}\n{:?}\n{}", result, stats, space)
+}
+
+fn assert_same_threaded<M: FullMachine + Clone>(system: M, property: &str) {
+    let single = verify_threaded(system.clone(), property, 1);
+    for num_threads in [2, 4, 7] {
+        assert_eq!(
+            verify_threaded(system.clone(), property, num_threads),
+            single,
+            "{} threads should give the same verification as a single thread",
+            num_threads
+        );
+    }
+}
+
+#[test]
+fn same_refined_inputs() {
+    assert_same_threaded(counter::System {}, "AG![value <= 9]");
+    assert_same_threaded(counter::System {}, "EF![value == 5]");
+}
+
+#[test]
+fn same_refined_params() {
+    assert_same_threaded(saturating::System {}, "AG![value <= param.max_value]");
+    assert_same_threaded(saturating::System {}, "EF![value == 15]");
+}
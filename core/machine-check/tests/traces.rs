@@ -0,0 +1,208 @@
+//! Tests of the traces explaining the verification results.
+
+mod common;
+
+use std::collections::BTreeMap;
+
+use machine_check::{ExecArgs, ExecStrategy};
+use machine_check_common::check::{KnownConclusion, ReplayVerdict, Trace, TraceStep};
+use machine_check_common::{property::Property, StateId};
+use machine_check_exec::{Framework, Strategy};
+use mck::abstr::{Abstr, Manipulatable};
+
+use common::{concrete_field, counter, reserved, timer, wrapping};
+
+fn verify_counter(property: &str) -> (KnownConclusion, Trace) {
+    let result = common::verify(counter::System {}, property);
+    let conclusion = result.result.expect("The verification should succeed");
+    let trace = result
+        .trace
+        .expect("The result should be explained by a trace");
+    (conclusion, trace)
+}
+
+fn values(trace: &Trace) -> Vec<u64> {
+    trace
+        .steps
+        .iter()
+        .map(|step| concrete_field(&step.state, "value"))
+        .collect()
+}
+
+#[test]
+fn counterexample_reaches_violation() {
+    let (conclusion, trace) = verify_counter("AG![value != 5]");
+    assert_eq!(conclusion, KnownConclusion::False);
+    assert_eq!(trace.loop_start, None);
+
+    // the path starts in the initial state and ends in the violating one
+    let values = values(&trace);
+    assert_eq!(values.first(), Some(&0));
+    assert_eq!(values.last(), Some(&5));
+    for window in values.windows(2) {
+        assert!(window[1] == window[0] || window[1] == window[0] + 1);
+    }
+
+    let replay = trace.replay.expect("The trace should be replayed");
+    assert_eq!(replay.verdict, ReplayVerdict::Confirmed);
+}
+
+#[test]
+fn witness_reaches_formula() {
+    let (conclusion, trace) = verify_counter("EF![value == 3]");
+    assert_eq!(conclusion, KnownConclusion::True);
+    assert_eq!(trace.loop_start, None);
+
+    let values = values(&trace);
+    assert_eq!(values.first(), Some(&0));
+    assert_eq!(values.last(), Some(&3));
+}
+
+#[test]
+fn counterexample_loops_without_reaching() {
+    // the counter can stay at zero forever
+    let (conclusion, trace) = verify_counter("AF![value == 3]");
+    assert_eq!(conclusion, KnownConclusion::False);
+
+    let loop_start = trace
+        .loop_start
+        .expect("The counterexample to eventually reaching should loop");
+    assert!(loop_start < trace.steps.len());
+    assert!(!values(&trace).contains(&3));
+
+    // the concrete counter stays at zero as well, repeating the state at the loop start
+    let replay = trace.replay.expect("The trace should be replayed");
+    assert_eq!(replay.verdict, ReplayVerdict::Confirmed);
+}
+
+#[test]
+fn concrete_panic_reproduced() {
+    let result = common::execute(
+        reserved::System {},
+        ExecArgs {
+            assume_inherent: true,
+            ..common::property_args(r#"AG![!(panic("Reserved opcode"))]"#)
+        },
+    );
+    assert_eq!(result.result.ok(), Some(KnownConclusion::False));
+    let trace = result
+        .trace
+        .expect("The result should be explained by a trace");
+
+    // the concrete run panics in the last step with the same message
+    let replay = trace.replay.expect("The trace should be replayed");
+    assert_eq!(replay.verdict, ReplayVerdict::Confirmed);
+    assert_eq!(replay.steps.len(), trace.steps.len());
+    let last_step = replay.steps.last().expect("The replay should have steps");
+    assert_eq!(last_step.panic_message.as_deref(), Some("Reserved opcode"));
+}
+
+#[test]
+fn unrepeated_loop_inconclusive() {
+    // the ticks are decayed, so the abstract loop is closed immediately,
+    // but the concrete ticks do not repeat within the unrollings
+    let result = common::execute(
+        wrapping::System {},
+        ExecArgs {
+            strategy: ExecStrategy::Decay,
+            ..common::property_args("AF![flag == 1]")
+        },
+    );
+    assert_eq!(result.result.ok(), Some(KnownConclusion::False));
+    let trace = result
+        .trace
+        .expect("The result should be explained by a trace");
+    assert!(trace.loop_start.is_some());
+
+    let replay = trace.replay.expect("The trace should be replayed");
+    assert!(
+        matches!(replay.verdict, ReplayVerdict::Inconclusive { .. }),
+        "{}",
+        replay.verdict
+    );
+}
+
+#[test]
+fn path_mixing_params_spurious() {
+    let mut framework = Framework::<timer::System>::new(
+        <timer::System as mck::concr::FullMachine>::Abstr::from_concrete(timer::System {}),
+        Strategy {
+            naive_inputs: false,
+            use_decay: false,
+            num_threads: 1,
+        },
+    );
+    let property = Property::parse("AG![ticks < 4]").unwrap();
+    assert_eq!(
+        framework.verify(&property).ok(),
+        Some(KnownConclusion::False)
+    );
+
+    // the path takes a slow step and then a fast one, which no parameter value can
+    let space = framework.space();
+    let exact_ticks = |state_id: StateId| {
+        let ticks = space
+            .state_data(state_id)
+            .result
+            .get("ticks")
+            .expect("The ticks should be present");
+        let min = ticks
+            .min_unsigned()
+            .expect("The ticks should be a bit-vector");
+        (Some(min) == ticks.max_unsigned()).then_some(min)
+    };
+    let mut path = vec![space
+        .initial_iter()
+        .next()
+        .expect("There should be an initial state")];
+    for ticks in [1, 3] {
+        let head_id = *path.last().unwrap();
+        let tail_id = space
+            .direct_successor_iter(head_id.into())
+            .find(|successor_id| exact_ticks(*successor_id) == Some(ticks))
+            .expect("The path should be present in the state space");
+        path.push(tail_id);
+    }
+    let trace = Trace {
+        steps: path
+            .into_iter()
+            .map(|state_id| TraceStep {
+                state_id,
+                input: BTreeMap::new(),
+                param: BTreeMap::new(),
+                state: BTreeMap::new(),
+                panic: String::new(),
+            })
+            .collect(),
+        loop_start: None,
+        replay: None,
+    };
+
+    let replay = framework.replay_trace(&trace);
+    assert!(
+        matches!(replay.verdict, ReplayVerdict::Spurious { .. }),
+        "{}",
+        replay.verdict
+    );
+}
+
+#[test]
+fn param_chosen_along_trace() {
+    // the counterexample reaches the ticks by the fast steps, which the initial step does not decide
+    let result = common::verify(timer::System {}, "AG![ticks < 4]");
+    assert_eq!(result.result.ok(), Some(KnownConclusion::False));
+    let trace = result
+        .trace
+        .expect("The result should be explained by a trace");
+
+    let replay = trace.replay.expect("The trace should be replayed");
+    assert_eq!(replay.verdict, ReplayVerdict::Confirmed);
+    assert_eq!(concrete_field(&replay.param, "fast"), 1);
+}
+
+#[test]
+fn dependent_result_has_no_trace() {
+    let result = common::verify(common::saturating::System {}, "AG![value < 8]");
+    assert_eq!(result.result.ok(), Some(KnownConclusion::Dependent));
+    assert!(result.trace.is_none());
+}
@@ -3,7 +3,9 @@ use std::{collections::BTreeMap, fmt::Debug};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    abstr::{self, Abstr, BitvectorDomain, BitvectorElement, Field, ManipField, Phi},
+    abstr::{
+        self, Abstr, BitvectorDomain, BitvectorElement, BitvectorSlice, Field, ManipField, Phi,
+    },
     concr::{self, UnsignedBitvector},
     forward::ReadWrite,
     misc::MetaWrap,
@@ -32,6 +34,10 @@ impl<const I: u32, const W: u32> Abstr<concr::Array<I, W>> for Array<I, W> {
                 .map(|v| MetaWrap(abstr::Bitvector::from_concrete(*v))),
         }
     }
+
+    fn concrete_choice(&self) -> concr::Array<I, W> {
+        concr::Array::from_inner(self.inner.map(|v| v.0.concrete_choice()))
+    }
 }
 
 impl<const I: u32, const W: u32> Array<I, W> {
@@ -132,6 +138,19 @@ impl<const I: u32, const W: u32> ManipField for Array<I, W> {
         Some(&self.inner[index].0)
     }
 
+    fn index_run_starts(&self) -> Option<Vec<u64>> {
+        Some(
+            self.inner
+                .light_iter()
+                .map(|(index, _element)| index.as_bitvector().to_u64())
+                .collect(),
+        )
+    }
+
+    fn slice(&self, _lowest_bit: u32, _highest_bit: u32) -> Option<BitvectorSlice> {
+        None
+    }
+
     fn num_bits(&self) -> Option<u32> {
         None
     }
@@ -23,6 +23,10 @@ impl<const I: u32, const W: u32> Array<I, W> {
     pub fn from_inner(inner: LightArray<UnsignedBitvector<I>, concr::Bitvector<W>>) -> Self {
         Self { inner }
     }
+
+    pub fn into_inner(self) -> LightArray<UnsignedBitvector<I>, concr::Bitvector<W>> {
+        self.inner
+    }
 }
 
 impl<const I: u32, const W: u32> ReadWrite for &Array<I, W> {
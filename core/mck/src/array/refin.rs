@@ -264,6 +264,12 @@ impl<const I: u32, const W: u32> ManipField for Array<I, W> {
     fn mark(&mut self) {
         self.inner = LightArray::new_filled(MetaWrap(refin::Bitvector::<W>::dirty()));
     }
+
+    fn mark_bits(&mut self, lowest_bit: u32, highest_bit: u32) {
+        let mut element = refin::Bitvector::<W>::clean();
+        element.mark_bits(lowest_bit, highest_bit);
+        self.inner = LightArray::new_filled(MetaWrap(element));
+    }
 }
 
 fn index_importance(element_importance: NonZeroU8) -> NonZeroU8 {
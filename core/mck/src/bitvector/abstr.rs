@@ -16,6 +16,7 @@ pub trait BitvectorDomain<const W: u32>: Clone + Copy + Hash + Phi + ManipField
 
 pub(super) use combined::CombinedBitvector;
 use serde::{Deserialize, Serialize};
+pub use three_valued::BitvectorSlice;
 pub(super) use three_valued::ThreeValuedBitvector;
 
 pub(crate) use dual_interval::DualIntervalFieldValue;
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 mod ops;
 mod support;
 
@@ -5,8 +8,8 @@ use std::hash::Hash;
 
 use crate::{
     abstr::{
-        BitvectorDomain, BitvectorElement, BitvectorField, Boolean, Field, ManipField, PanicResult,
-        Phi, Test,
+        BitvectorDomain, BitvectorElement, BitvectorField, BitvectorSlice, Boolean, Field,
+        ManipField, PanicResult, Phi, Test,
     },
     bitvector::interval::{UnsignedInterval, WrappingInterval},
     concr::ConcreteBitvector,
@@ -141,6 +144,15 @@ impl<const W: u32> ManipField for CombinedBitvector<W> {
         None
     }
 
+    fn index_run_starts(&self) -> Option<Vec<u64>> {
+        None
+    }
+
+    fn slice(&self, lowest_bit: u32, highest_bit: u32) -> Option<BitvectorSlice> {
+        // the dual-interval domain does not track individual bits
+        self.three_valued.slice(lowest_bit, highest_bit)
+    }
+
     fn num_bits(&self) -> Option<u32> {
         Some(W)
     }
@@ -18,6 +18,20 @@ impl<const W: u32> Abstr<concr::Bitvector<W>> for CombinedBitvector<W> {
             dual_interval: DualInterval::from_value(value),
         }
     }
+
+    fn concrete_choice(&self) -> concr::Bitvector<W> {
+        // choose the smallest value of the first interval half also in the three-valued domain,
+        // the halves do not cross the sign boundary, so the unsigned interpretation is used
+        let (near_half, far_half) = self.dual_interval.opt_halves();
+        for half in [near_half, far_half].into_iter().flatten() {
+            if let Some(value) = self.three_valued.umin_at_least(half.min()) {
+                if value.to_u64() <= half.max().to_u64() {
+                    return value;
+                }
+            }
+        }
+        panic!("Combined bit-vector should contain a concrete value")
+    }
 }
 
 impl<const W: u32> MetaEq for CombinedBitvector<W> {
@@ -0,0 +1,39 @@
+use crate::{
+    abstr::Abstr,
+    bitvector::{
+        abstr::{dual_interval::DualInterval, three_valued::ThreeValuedBitvector},
+        interval::WrappingInterval,
+    },
+    concr::ConcreteBitvector,
+};
+
+use super::CombinedBitvector;
+
+// === SMALL-LENGTH-EXHAUSTIVE TESTS ===
+
+#[test]
+pub fn concrete_choice() {
+    for three_valued in ThreeValuedBitvector::<3>::all_with_length_iter() {
+        for start in ConcreteBitvector::<3>::all_with_width_iter() {
+            for end in ConcreteBitvector::<3>::all_with_width_iter() {
+                let dual_interval =
+                    DualInterval::from_wrapping_intervals(&[WrappingInterval::new(start, end)]);
+                let is_intersecting = ConcreteBitvector::<3>::all_with_width_iter().any(|value| {
+                    three_valued.contains_concr(&value) && dual_interval.contains_value(&value)
+                });
+                if !is_intersecting {
+                    continue;
+                }
+                let combined = CombinedBitvector::combine(three_valued, dual_interval);
+                let choice = combined.concrete_choice();
+                assert!(
+                    combined.three_valued.contains_concr(&choice)
+                        && combined.dual_interval.contains_value(&choice),
+                    "{:?} chosen from {:?}",
+                    choice,
+                    combined
+                );
+            }
+        }
+    }
+}
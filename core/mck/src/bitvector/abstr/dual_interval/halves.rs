@@ -10,7 +10,7 @@ use crate::{
 };
 
 impl<const W: u32> DualInterval<W> {
-    pub(super) fn opt_halves(self) -> (Option<SignlessInterval<W>>, Option<SignlessInterval<W>>) {
+    pub(crate) fn opt_halves(self) -> (Option<SignlessInterval<W>>, Option<SignlessInterval<W>>) {
         if self.near_half == self.far_half {
             if self.near_half.is_sign_bit_set() {
                 (None, Some(self.far_half))
@@ -17,4 +17,4 @@ pub struct ThreeValuedBitvector<const W: u32> {
     ones: ConcreteBitvector<W>,
 }
 
-pub use support::ThreeValuedFieldValue;
+pub use support::{BitvectorSlice, ThreeValuedFieldValue};
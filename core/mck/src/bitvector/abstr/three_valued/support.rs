@@ -27,6 +27,11 @@ impl<const W: u32> Abstr<concr::Bitvector<W>> for ThreeValuedBitvector<W> {
 
         Self::from_zeros_ones(zeros, ones)
     }
+
+    fn concrete_choice(&self) -> concr::Bitvector<W> {
+        // unknown bits are chosen to be zero
+        self.umin().as_bitvector()
+    }
 }
 
 impl<const W: u32> ThreeValuedBitvector<W> {
@@ -202,6 +207,39 @@ impl<const W: u32> ThreeValuedBitvector<W> {
         Self::from_zeros_ones(zeros, ones)
     }
 
+    /// Returns the smallest contained concrete value that is not below the given one
+    /// in the unsigned interpretation, or `None` if there is no such value.
+    #[must_use]
+    pub fn umin_at_least(&self, min: ConcreteBitvector<W>) -> Option<ConcreteBitvector<W>> {
+        if self.contains_concr(&min) {
+            return Some(min);
+        }
+        let mask = compute_u64_mask(W);
+        let min = min.to_u64();
+        let must_be_one = !self.zeros.to_u64() & mask;
+        let must_be_zero = !self.ones.to_u64() & mask;
+
+        // the result must share the bits above some position with the minimum,
+        // have a one at the position where the minimum has a zero, and be as small
+        // as possible below it; the lowest suitable position gives the smallest result
+        for position in 0..W {
+            let position_bit = 1u64 << position;
+            if min & position_bit != 0 || must_be_zero & position_bit != 0 {
+                continue;
+            }
+            let below_mask = position_bit - 1;
+            let above_mask = mask & !below_mask & !position_bit;
+            let above = min & above_mask;
+            if above & must_be_zero != 0 || !above & above_mask & must_be_one != 0 {
+                continue;
+            }
+            return Some(ConcreteBitvector::new(
+                above | position_bit | (must_be_one & below_mask),
+            ));
+        }
+        None
+    }
+
     pub fn all_with_length_iter() -> impl Iterator<Item = Self> {
         let zeros_iter = ConcreteBitvector::<W>::all_with_width_iter();
         zeros_iter.flat_map(|zeros| {
@@ -331,6 +369,14 @@ impl<const W: u32> ManipField for ThreeValuedBitvector<W> {
         None
     }
 
+    fn index_run_starts(&self) -> Option<Vec<u64>> {
+        None
+    }
+
+    fn slice(&self, lowest_bit: u32, highest_bit: u32) -> Option<BitvectorSlice> {
+        BitvectorSlice::new(W, self.field_value(), lowest_bit, highest_bit)
+    }
+
     fn description(&self) -> Field {
         Field::Bitvector(BitvectorField {
             bit_width: W,
@@ -339,6 +385,98 @@ impl<const W: u32> ManipField for ThreeValuedBitvector<W> {
     }
 }
 
+/// Bits sliced from a three-valued bit-vector, usable as a field on its own.
+#[derive(Clone, Copy, Debug)]
+pub struct BitvectorSlice {
+    bit_width: u32,
+    value: ThreeValuedFieldValue,
+}
+
+impl BitvectorSlice {
+    fn new(
+        bit_width: u32,
+        value: ThreeValuedFieldValue,
+        lowest_bit: u32,
+        highest_bit: u32,
+    ) -> Option<Self> {
+        if lowest_bit > highest_bit || highest_bit >= bit_width {
+            return None;
+        }
+        let slice_width = highest_bit - lowest_bit + 1;
+        let mask = compute_u64_mask(slice_width);
+        Some(Self {
+            bit_width: slice_width,
+            value: ThreeValuedFieldValue {
+                zeros: (value.zeros >> lowest_bit) & mask,
+                ones: (value.ones >> lowest_bit) & mask,
+            },
+        })
+    }
+
+    fn sign_extend(&self, value: u64) -> i64 {
+        // move the sign bit to the top and shift back arithmetically
+        let shift = u64::BITS - self.bit_width;
+        ((value << shift) as i64) >> shift
+    }
+}
+
+impl ManipField for BitvectorSlice {
+    fn index(&self, _index: u64) -> Option<&dyn ManipField> {
+        None
+    }
+
+    fn index_run_starts(&self) -> Option<Vec<u64>> {
+        None
+    }
+
+    fn slice(&self, lowest_bit: u32, highest_bit: u32) -> Option<BitvectorSlice> {
+        BitvectorSlice::new(self.bit_width, self.value, lowest_bit, highest_bit)
+    }
+
+    fn num_bits(&self) -> Option<u32> {
+        Some(self.bit_width)
+    }
+
+    fn min_unsigned(&self) -> Option<u64> {
+        // one only where it must be
+        Some(!self.value.zeros & compute_u64_mask(self.bit_width))
+    }
+
+    fn max_unsigned(&self) -> Option<u64> {
+        // one everywhere it can be
+        Some(self.value.ones)
+    }
+
+    fn min_signed(&self) -> Option<i64> {
+        // the signed value is smaller when the sign bit is one, set it if possible
+        let sign_bit_mask = 1u64 << (self.bit_width - 1);
+        let min_unsigned = self.min_unsigned()?;
+        Some(self.sign_extend(min_unsigned | (self.value.ones & sign_bit_mask)))
+    }
+
+    fn max_signed(&self) -> Option<i64> {
+        // the signed value is bigger when the sign bit is zero, clear it if possible
+        let sign_bit_mask = 1u64 << (self.bit_width - 1);
+        let max_unsigned = self.max_unsigned()?;
+        let result = if self.value.zeros & sign_bit_mask != 0 {
+            max_unsigned & !sign_bit_mask
+        } else {
+            max_unsigned
+        };
+        Some(self.sign_extend(result))
+    }
+
+    fn description(&self) -> Field {
+        Field::Bitvector(BitvectorField {
+            bit_width: self.bit_width,
+            element: BitvectorElement {
+                three_valued: Some(self.value),
+                dual_interval: None,
+            },
+        })
+    }
+}
+
 impl<const W: u32> Debug for ThreeValuedBitvector<W> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.field_value().write(f, W)
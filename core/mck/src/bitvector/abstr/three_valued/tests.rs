@@ -2,7 +2,7 @@
 mod op;
 
 use super::*;
-use crate::{forward::*, misc::MetaEq};
+use crate::{abstr::ManipField, forward::*, misc::MetaEq};
 
 // === ANECDOTAL TESTS ===
 
@@ -83,6 +83,33 @@ pub fn support() {
     );
 }
 
+#[test]
+pub fn slice() {
+    let partially_known = ThreeValuedBitvector::<16>::new_value_known(
+        ConcreteBitvector::new(0x1337),
+        ConcreteBitvector::new(0xF0F0),
+    );
+
+    let known_slice = partially_known.slice(4, 7).unwrap();
+    assert_eq!(known_slice.num_bits(), Some(4));
+    assert_eq!(known_slice.min_unsigned(), Some(3));
+    assert_eq!(known_slice.max_unsigned(), Some(3));
+
+    let mixed_slice = partially_known.slice(4, 11).unwrap();
+    assert_eq!(mixed_slice.num_bits(), Some(8));
+    assert_eq!(mixed_slice.min_unsigned(), Some(0x03));
+    assert_eq!(mixed_slice.max_unsigned(), Some(0xF3));
+    assert_eq!(mixed_slice.min_signed(), Some(-125));
+    assert_eq!(mixed_slice.max_signed(), Some(0x73));
+
+    let sign_slice = partially_known.slice(12, 12).unwrap();
+    assert_eq!(sign_slice.min_signed(), Some(-1));
+    assert_eq!(sign_slice.max_signed(), Some(-1));
+
+    assert!(partially_known.slice(7, 4).is_none());
+    assert!(partially_known.slice(0, 16).is_none());
+}
+
 #[test]
 #[should_panic]
 pub fn bitvec_too_large() {
@@ -146,3 +173,23 @@ bi_op_test!(arith_shr, true);
 // extension tests
 ext_op_test!(uext);
 ext_op_test!(sext);
+
+// --- SUPPORT TESTS ---
+
+#[test]
+pub fn umin_at_least() {
+    for value in ThreeValuedBitvector::<4>::all_with_length_iter() {
+        for min in ConcreteBitvector::<4>::all_with_width_iter() {
+            let expected = ConcreteBitvector::<4>::all_with_width_iter()
+                .filter(|candidate| candidate.to_u64() >= min.to_u64())
+                .find(|candidate| value.contains_concr(candidate));
+            assert_eq!(
+                value.umin_at_least(min),
+                expected,
+                "{:?} at least {:?}",
+                value,
+                min
+            );
+        }
+    }
+}
@@ -55,6 +55,10 @@ impl<const W: u32> ManipField for CombinedMark<W> {
         *self = Self::dirty();
     }
 
+    fn mark_bits(&mut self, lowest_bit: u32, highest_bit: u32) {
+        self.0.mark_bits(lowest_bit, highest_bit);
+    }
+
     fn index(&self, _index: u64) -> Option<&dyn ManipField> {
         None
     }
@@ -1,7 +1,7 @@
 use std::num::NonZeroU8;
 
 use crate::{
-    bitvector::{abstr::ThreeValuedBitvector, refin::FromRefin},
+    bitvector::{abstr::ThreeValuedBitvector, refin::FromRefin, util::compute_u64_mask},
     concr::ConcreteBitvector,
     forward::{self, HwArith},
     refin::{Boolean, ManipField, Refine},
@@ -120,6 +120,16 @@ impl<const W: u32> ManipField for MarkBitvector<W> {
         *self = Self::dirty();
     }
 
+    fn mark_bits(&mut self, lowest_bit: u32, highest_bit: u32) {
+        // keep the bits that are already marked
+        let mask = compute_u64_mask(highest_bit + 1) & !compute_u64_mask(lowest_bit);
+        let mask = mask | self.marked_bits().to_u64();
+        let Some(mark) = ConcreteBitvector::try_new(mask) else {
+            panic!("Marked bits should be within the bit-vector");
+        };
+        *self = Self::new_from_flag(mark);
+    }
+
     fn index(&self, _index: u64) -> Option<&dyn ManipField> {
         None
     }
@@ -1,5 +1,6 @@
-use crate::abstr::{Field, PanicResult};
+use crate::abstr::{BitvectorSlice, Field, PanicResult};
 use crate::concr::FullMachine;
+pub use machine_check_common::Signedness;
 use std::fmt::Debug;
 use std::hash::Hash;
 
@@ -8,6 +9,8 @@ use super::misc::MetaEq;
 pub trait Abstr<C> {
     #[must_use]
     fn from_concrete(value: C) -> Self;
+    #[must_use]
+    fn concrete_choice(&self) -> C;
 }
 
 pub trait Input<C: FullMachine>:
@@ -80,6 +83,14 @@ where
 
 pub trait ManipField {
     fn index(&self, index: u64) -> Option<&dyn ManipField>;
+    /// Returns the indices at which the runs of equal elements start, in ascending order.
+    ///
+    /// Returns none if the field is not an array.
+    fn index_run_starts(&self) -> Option<Vec<u64>>;
+    /// Returns the bits from the lowest to the highest, inclusive, as a new field.
+    ///
+    /// Returns none if the field is not a bit-vector or the bits are out of range.
+    fn slice(&self, lowest_bit: u32, highest_bit: u32) -> Option<BitvectorSlice>;
     fn num_bits(&self) -> Option<u32>;
     fn min_unsigned(&self) -> Option<u64>;
     fn max_unsigned(&self) -> Option<u64>;
@@ -94,4 +105,9 @@ pub trait Manipulatable {
     fn get_mut(&mut self, name: &str) -> Option<&mut dyn ManipField>;
     #[must_use]
     fn field_names() -> Vec<&'static str>;
+    /// Returns the signedness the field of the given name was declared with.
+    ///
+    /// Returns [`Signedness::None`] for plain bit-vectors, arrays, and unknown fields.
+    #[must_use]
+    fn signedness(name: &str) -> Signedness;
 }
@@ -60,4 +60,6 @@ pub trait IntoMck {
     type Type;
     #[must_use]
     fn into_mck(self) -> Self::Type;
+    #[must_use]
+    fn from_mck(value: Self::Type) -> Self;
 }
@@ -77,4 +77,7 @@ impl<M: Meta<P>, P: Clone> Iterator for ProtoIterator<M, P> {
 
 pub trait PanicMessage {
     fn panic_message(panic_id: u32) -> &'static str;
+
+    /// Returns the panic messages in the order of their panic ids, starting from 1.
+    fn panic_messages() -> Vec<&'static str>;
 }
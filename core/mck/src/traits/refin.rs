@@ -139,6 +139,10 @@ pub trait ManipField {
     fn index_mut(&mut self, index: u64) -> Option<&mut dyn ManipField>;
     fn num_bits(&self) -> Option<u32>;
     fn mark(&mut self);
+    /// Marks only the bits from the lowest to the highest, inclusive.
+    ///
+    /// For arrays, the bits are marked in all elements.
+    fn mark_bits(&mut self, lowest_bit: u32, highest_bit: u32);
 }
 pub trait Manipulatable {
     #[must_use]
@@ -35,6 +35,11 @@ pub fn execute_with_args(exec_args: ExecArgs, system_args: SystemArgs) -> ExecRe
                     "Could not read hex file",
                 ))),
                 stats: ExecStats::default(),
+                trace: None,
+                param_classes: None,
+                property_results: None,
+                step_bound: None,
+                subproperty_results: None,
             };
         }
     };
@@ -22,13 +22,12 @@ fn avr_test(group: &str, release: bool, name: &str, property: Option<String>, ex
     let exec_result = machine_check_avr::execute_with_args(
         ExecArgs {
             silent: true,
-            verbose: 0,
             batch: true,
-            gui: false,
             inherent: check_inherent,
             assume_inherent: !check_inherent,
-            property,
+            property: property.into_iter().collect(),
             strategy: ExecStrategy::Default,
+            ..Default::default()
         },
         SystemArgs { hex_file },
     );
@@ -19,6 +19,9 @@ pub struct Config {
     pub batch: bool,
     pub gui: bool,
     pub property: Option<String>,
+    pub ltl: Option<String>,
+    pub property_file: Option<Utf8PathBuf>,
+    pub fairness: Vec<String>,
     pub verbose: u8,
     pub use_decay: bool,
 }
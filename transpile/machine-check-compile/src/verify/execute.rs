@@ -22,11 +22,22 @@ pub(super) fn execute(config: &Config, artifact_path: &Path) -> Result<ExecResul
     // forward property
     if let Some(property) = &config.property {
         command.arg("--property").arg(property);
+    } else if let Some(ltl) = &config.ltl {
+        command.arg("--ltl").arg(ltl);
+    } else if let Some(property_file) = &config.property_file {
+        command.arg("--property-file").arg(property_file);
     } else {
         // default to inherent
         command.arg("--inherent");
     }
 
+    // forward fairness, it only applies to a given property
+    if config.property.is_some() || config.ltl.is_some() || config.property_file.is_some() {
+        for fairness in &config.fairness {
+            command.arg("--fairness").arg(fairness);
+        }
+    }
+
     // forward verbose
     for _ in 0..config.verbose {
         command.arg("--verbose");
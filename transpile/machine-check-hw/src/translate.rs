@@ -4,7 +4,14 @@ use crate::CheckError;
 
 mod btor2;
 
-pub fn translate(system_path: &Utf8Path) -> std::result::Result<syn::File, CheckError> {
+/// A system translated to a machine.
+pub struct Translation {
+    pub machine: syn::File,
+    /// Fairness constraints given by the system, to be used when verifying properties.
+    pub fairness: Vec<String>,
+}
+
+pub fn translate(system_path: &Utf8Path) -> std::result::Result<Translation, CheckError> {
     let Some(extension) = system_path.extension() else {
         return Err(CheckError::SystemType(String::from("(no extension)")));
     };
@@ -16,7 +16,7 @@ use log::trace;
 use std::io::BufRead;
 use syn::parse_quote;
 
-pub fn translate(system_path: &Utf8Path) -> Result<syn::File, CheckError> {
+pub fn translate(system_path: &Utf8Path) -> Result<super::Translation, CheckError> {
     let file = fs::File::open(system_path)
         .map_err(|err| CheckError::OpenFile(system_path.to_path_buf(), err))?;
 
@@ -25,11 +25,15 @@ pub fn translate(system_path: &Utf8Path) -> Result<syn::File, CheckError> {
         lines_result.map_err(|err| CheckError::ReadFile(system_path.to_path_buf(), err))?;
     let btor2 = Btor2::parse(lines.iter().map(|str| str.as_ref()))
         .map_err(|err| CheckError::Translate(format!("Btor2 parsing: {}", err)))?;
-    let translator = Translator::new(btor2);
-    translator
-        .map_err(|err| CheckError::Translate(format!("Btor2 translation: {}", err)))?
+    let translator = Translator::new(btor2)
+        .map_err(|err| CheckError::Translate(format!("Btor2 translation: {}", err)))?;
+    let machine = translator
         .translate()
-        .map_err(|err| CheckError::Translate(format!("Btor2 translation: {}", err)))
+        .map_err(|err| CheckError::Translate(format!("Btor2 translation: {}", err)))?;
+    Ok(super::Translation {
+        machine,
+        fairness: translator.fairness(),
+    })
 }
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -54,8 +58,6 @@ pub(crate) enum Error {
 
     #[error("State not found for node id {0}")]
     StateNotFound(Nid),
-    #[error("Fairness constraint in node id {0} not supported")]
-    FairnessNotSupported(Nid),
     #[error("Justice constraint in node id {0} not supported")]
     JusticeNotSupported(Nid),
     #[error("Arrays not supported")]
@@ -73,6 +75,7 @@ struct Translator {
     state_info_map: BTreeMap<Nid, StateInfo>,
     constraints: Vec<Rnid>,
     bads: Vec<Rnid>,
+    fairs: Vec<Rnid>,
 }
 
 impl Translator {
@@ -80,6 +83,7 @@ impl Translator {
         let mut state_info_map = BTreeMap::new();
         let mut constraints = Vec::new();
         let mut bads = Vec::new();
+        let mut fairs = Vec::new();
         for (nid, node) in &btor2.nodes {
             match node {
                 Node::State(state) => {
@@ -112,9 +116,12 @@ impl Translator {
                     DrainType::Constraint => {
                         constraints.push(drain.rnid);
                     }
-                    DrainType::Fair => return Err(Error::FairnessNotSupported(*nid)),
+                    DrainType::Fair => {
+                        fairs.push(drain.rnid);
+                    }
                     DrainType::Output => {}
                 },
+                Node::Justice(_) => return Err(Error::JusticeNotSupported(*nid)),
                 _ => (),
             };
         }
@@ -123,9 +130,17 @@ impl Translator {
             state_info_map,
             constraints,
             bads,
+            fairs,
         })
     }
 
+    /// Fairness constraints to verify with, one for each fairness node.
+    pub fn fairness(&self) -> Vec<String> {
+        (0..self.fairs.len())
+            .map(|index| format!("{} == 1", fair_field_name(index)))
+            .collect()
+    }
+
     pub fn translate(&self) -> Result<syn::File, Error> {
         // construct input and state fields
         let input_fields = self.create_input_fields()?;
@@ -195,3 +210,7 @@ impl Translator {
         Ok(system)
     }
 }
+
+fn fair_field_name(index: usize) -> String {
+    format!("fair_{}", index)
+}
@@ -6,6 +6,7 @@ use proc_macro2::Span;
 use syn::{Field, Ident, Type};
 
 use super::{
+    fair_field_name,
     util::{create_nid_ident, create_sid_type, create_single_bit_type},
     Error, Translator,
 };
@@ -55,7 +56,12 @@ impl Translator {
         state_fields.push(create_field(safe_ident, bit_type.clone()));
         // add 'eq_init' state field
         let eq_init_ident = Ident::new("eq_init", Span::call_site());
-        state_fields.push(create_field(eq_init_ident, bit_type));
+        state_fields.push(create_field(eq_init_ident, bit_type.clone()));
+        // add fairness state fields
+        for index in 0..self.fairs.len() {
+            let fair_ident = Ident::new(&fair_field_name(index), Span::call_site());
+            state_fields.push(create_field(fair_ident, bit_type.clone()));
+        }
     }
 
     fn create_nid_field(&self, nid: Nid, sid: Sid) -> Result<Field, Error> {
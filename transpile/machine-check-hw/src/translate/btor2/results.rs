@@ -1,9 +1,11 @@
 use btor2rs::id::Nid;
-use syn::{parse_quote, Expr, FieldValue};
+use proc_macro2::Span;
+use syn::{parse_quote, Expr, FieldValue, Ident};
 
 use crate::translate::btor2::util::create_nid_init_eq_ident;
 
 use super::{
+    fair_field_name,
     util::{create_nid_ident, create_rnid_expr, single_bits_and},
     Error, Translator,
 };
@@ -76,6 +78,13 @@ impl Translator {
         // combine and add to field values
         field_values.push(parse_quote!(safe: (#not_constraint_expr | #not_bad_expr)));
 
+        // fairness fields just hold the fairness node values
+        for (index, fair) in self.fairs.iter().enumerate() {
+            let fair_ident = Ident::new(&fair_field_name(index), Span::call_site());
+            let fair_expr = create_rnid_expr(*fair);
+            field_values.push(parse_quote!(#fair_ident: #fair_expr));
+        }
+
         if is_init {
             field_values.push(parse_quote!(eq_init: ::machine_check::Bitvector::<1>::new(1)));
         } else {
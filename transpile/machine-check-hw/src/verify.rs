@@ -4,23 +4,46 @@ use log::{debug, info};
 use machine_check_common::ExecResult;
 use serde::{Deserialize, Serialize};
 
-use crate::CheckError;
+use crate::{translate::Translation, CheckError};
 
 #[derive(Debug, Clone, Args)]
 #[clap(group(ArgGroup::new("property-group")
 .required(true)
 .multiple(true)
-.args(&["property", "inherent", "gui"]),
+.args(&["property", "ltl", "property_file", "inherent", "gui"]),
 ))]
 pub struct Cli {
     /// Whether to show the Graphical User Interface.
-    #[arg(short, long, conflicts_with("property"), conflicts_with("inherent"))]
+    #[arg(
+        short,
+        long,
+        conflicts_with("property"),
+        conflicts_with("ltl"),
+        conflicts_with("property_file"),
+        conflicts_with("inherent")
+    )]
     pub gui: bool,
 
     /// Computation Tree Logic property to verify.
     #[arg(long)]
     pub property: Option<String>,
 
+    /// Linear Temporal Logic property to verify.
+    #[arg(long, conflicts_with("property"))]
+    pub ltl: Option<String>,
+
+    /// Property file with named properties to verify.
+    ///
+    /// Each of the named properties is verified and the results are reported for each name.
+    #[arg(long, conflicts_with("property"), conflicts_with("ltl"))]
+    pub property_file: Option<Utf8PathBuf>,
+
+    /// Fairness constraint restricting the verified property to fair paths.
+    ///
+    /// Can be given multiple times. The fairness constraints given by the system are added automatically.
+    #[arg(long, conflicts_with("inherent"))]
+    pub fairness: Vec<String>,
+
     /// Whether to verify the inherent property instead of a supplied one.
     #[arg(long)]
     pub inherent: bool,
@@ -56,17 +79,22 @@ pub struct VerifyStats {
 }
 
 pub(crate) fn run(args: super::Cli, verify_args: Cli) -> Result<(), CheckError> {
-    let abstract_machine = process_machine(&verify_args.system_path)?;
+    let translation = process_machine(&verify_args.system_path)?;
+    let mut fairness = verify_args.fairness;
+    fairness.extend(translation.fairness);
 
     // if no property is supplied, we will verify the inherent one
 
     let config = machine_check_compile::VerifyConfig {
-        abstract_machine,
+        abstract_machine: translation.machine,
         machine_path: verify_args.machine_path,
         preparation_path: verify_args.preparation_path,
         batch: args.batch,
         gui: verify_args.gui,
         property: verify_args.property,
+        ltl: verify_args.ltl,
+        property_file: verify_args.property_file,
+        fairness,
         verbose: args.verbose,
         use_decay: verify_args.use_decay,
     };
@@ -82,14 +110,20 @@ pub(crate) fn run(args: super::Cli, verify_args: Cli) -> Result<(), CheckError>
         "Used {} states and {} refinements.",
         exec_result.stats.num_final_states, exec_result.stats.num_refinements
     );
+    // print the results of the named properties
+    for property_result in exec_result.property_results.iter().flatten() {
+        match &property_result.result {
+            Ok(conclusion) => info!("Property '{}': {}", property_result.name, conclusion),
+            Err(err) => info!("Property '{}': error ({})", property_result.name, err),
+        }
+    }
     // print conclusion or return exec error
     let conclusion = exec_result.result.map_err(CheckError::ExecError)?;
     info!("Reached conclusion: {}", conclusion);
     Ok(())
 }
 
-fn process_machine(system_path: &Utf8Path) -> Result<syn::File, CheckError> {
+fn process_machine(system_path: &Utf8Path) -> Result<Translation, CheckError> {
     debug!("Constructing machine from path {:?}.", &system_path);
-    let machine_file = super::translate::translate(system_path)?;
-    Ok(machine_file)
+    super::translate::translate(system_path)
 }